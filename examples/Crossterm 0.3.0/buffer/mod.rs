@@ -0,0 +1,48 @@
+//!
+//! Examples of drawing frames with the buffer and the renderer.
+//!
+
+extern crate crossterm;
+use self::crossterm::buffer::{Buffer, Renderer};
+use self::crossterm::style::{Color, ObjectStyle};
+use self::crossterm::Context;
+
+use std::thread;
+use std::time::Duration;
+
+/// Let a ball bounce over the screen, only the changed cells are written each frame | demonstration.
+pub fn bouncing_ball() {
+    let context = Context::new();
+    let mut renderer = Renderer::new(context.clone());
+
+    let (width, height) = (40, 20);
+    let mut buffer = Buffer::new(width, height);
+
+    let border = ObjectStyle::new().fg(Color::Cyan);
+    let ball = ObjectStyle::new().fg(Color::Red);
+
+    let (mut x, mut y, mut dx, mut dy) = (1i32, 1i32, 1i32, 1i32);
+
+    for _ in 0..200 {
+        buffer.clear();
+
+        for column in 0..width {
+            buffer.set_char(column, 0, '-', &border);
+            buffer.set_char(column, height - 1, '-', &border);
+        }
+
+        if x + dx <= 0 || x + dx >= width as i32 - 1 {
+            dx = -dx;
+        }
+        if y + dy <= 0 || y + dy >= height as i32 - 1 {
+            dy = -dy;
+        }
+        x += dx;
+        y += dy;
+
+        buffer.set_char(x as u16, y as u16, 'o', &ball);
+
+        renderer.render(&buffer).unwrap();
+        thread::sleep(Duration::from_millis(30));
+    }
+}
@@ -4,7 +4,7 @@
 
 extern crate crossterm;
 
-use self::crossterm::style::Color;
+use self::crossterm::style::{Color, Hyperlink, StylePolicy};
 use self::crossterm::terminal;
 use self::crossterm::Context;
 
@@ -151,7 +151,6 @@ pub fn print_all_background_colors() {
     );
     println!("Grey : \t\t {}", terminal.paint("  ").on(Color::Grey));
     println!("White : \t {}", terminal.paint("  ").on(Color::White));
-    #[cfg(unix)]
     println!(
         "RGB (10,10,10): \t {}",
         terminal.paint("  ").on(Color::Rgb {
@@ -160,15 +159,13 @@ pub fn print_all_background_colors() {
             b: 10
         })
     );
-    #[cfg(unix)]
     println!(
         "RGB (10,10,10): \t {}",
         terminal.paint("  ").on(Color::AnsiValue(50))
     );
 }
 
-/// Print font with all available attributes. Note that this can only be used at unix systems and that some are not supported widely | demonstration..
-#[cfg(unix)]
+/// Print font with all available attributes. Note that the windows console ignores them and that some are not supported widely | demonstration..
 pub fn print_font_with_attributes() {
     let context = Context::new();
     let terminal = terminal::terminal(&context);
@@ -186,7 +183,6 @@ pub fn print_font_with_attributes() {
 }
 
 /// Print all supported rgb colors  | demonstration.
-#[cfg(unix)]
 pub fn print_supported_colors() {
     let context = Context::new();
     let terminal = terminal::terminal(&context);
@@ -204,3 +200,57 @@ pub fn print_supported_colors() {
         );
     }
 }
+
+/// Print clickable hyperlinks, terminals without hyperlink support show plain text | demonstration.
+pub fn print_hyperlinks() {
+    let context = Context::new();
+    let terminal = terminal::terminal(&context);
+
+    println!("Hyperlinks supported: {}", terminal.supports_hyperlinks());
+
+    println!(
+        "{}",
+        terminal
+            .paint("crossterm on github")
+            .with(Color::Blue)
+            .link(Hyperlink::new("https://github.com/TimonPost/crossterm"))
+    );
+
+    // Both parts are highlighted as one link because they have the same id.
+    print!("{} ", terminal.paint("Cargo").link(Hyperlink::with_id("file:///Cargo.toml", "manifest")));
+    println!("{}", terminal.paint("manifest").link(Hyperlink::with_id("file:///Cargo.toml", "manifest")));
+}
+
+/// Print the detected color support, RGB colors are shown as the nearest color the terminal supports | demonstration.
+pub fn print_color_support() {
+    let context = Context::new();
+    let terminal = terminal::terminal(&context);
+
+    println!("Color support: {:?}", crossterm::style::color(&context).color_support());
+
+    for i in 0..32 {
+        print!(
+            "{}",
+            terminal.paint(" ").on(Color::Rgb {
+                r: i * 8,
+                g: 255 - i * 8,
+                b: 128
+            })
+        );
+    }
+    println!();
+}
+
+/// Print styled text with the styling policies, `Auto` prints plain text when the output is redirected or `NO_COLOR` is set | demonstration.
+pub fn print_with_style_policy() {
+    let context = Context::new();
+    let terminal = terminal::terminal(&context);
+
+    println!("{}", terminal.paint("styled when the output is a terminal").with(Color::Green));
+
+    context.set_style_policy(StylePolicy::Never);
+    println!("{}", terminal.paint("never styled").with(Color::Green));
+
+    context.set_style_policy(StylePolicy::Always);
+    println!("{}", terminal.paint("always styled").with(Color::Green));
+}
@@ -0,0 +1,46 @@
+//!
+//! Examples of working with the terminal actions as commands.
+//!
+
+#[macro_use]
+extern crate crossterm;
+use self::crossterm::command::{Clear, ClearType, Command, Goto, Hide, Print, ResetColor, SetFg, Show};
+use self::crossterm::style::Color;
+
+use std::io::{stdout, Write};
+
+/// Draw a border around the screen with one flush | demonstration.
+pub fn draw_border() {
+    let mut stdout = stdout();
+
+    queue!(stdout, Hide, Clear(ClearType::All), SetFg(Color::Cyan)).unwrap();
+
+    for x in 0..40 {
+        queue!(stdout, Goto(x, 0), Print("-"), Goto(x, 20), Print("-")).unwrap();
+    }
+
+    for y in 0..21 {
+        queue!(stdout, Goto(0, y), Print("|"), Goto(40, y), Print("|")).unwrap();
+    }
+
+    // Nothing is shown until the commands are flushed.
+    execute!(stdout, ResetColor, Goto(0, 22), Show).unwrap();
+}
+
+/// Store commands and write them later | demonstration.
+pub fn store_commands() {
+    let commands: Vec<Box<Command>> = vec![
+        Box::new(Goto(5, 5)),
+        Box::new(SetFg(Color::Red)),
+        Box::new(Print("stored")),
+        Box::new(ResetColor),
+    ];
+
+    let mut stdout = stdout();
+
+    for command in commands.iter() {
+        queue!(stdout, command).unwrap();
+    }
+
+    stdout.flush().unwrap();
+}
@@ -7,7 +7,7 @@ pub fn use_crossterm_cursor()
 {
     let crossterm = Crossterm::new();
     let mut cursor = crossterm.cursor();
-    cursor.goto(5,5).print("test");
+    cursor.goto(5,5).unwrap().print("test").unwrap();
 }
 
 use crossterm::style::Color;
@@ -56,12 +56,12 @@ pub fn create_alternate_screen_from_crossterm()
 
     {
         // move into alternate screen
-        let alternate_screen = AlternateScreen::from(crossterm.context());
+        let alternate_screen = AlternateScreen::from(crossterm.context()).unwrap();
 
         // this will move the cursor and print `some text` on the alternate screen.
-        crossterm.cursor().goto(10, 10).print("Some text");
+        crossterm.cursor().goto(10, 10).unwrap().print("Some text").unwrap();
     } // <- alternate screen ends here an will be switched back to main screen.
 
     // print "Some other text" on the mainscreen at x: 0, y: 10
-    crossterm.cursor().goto(0,10).print("Some other text");
+    crossterm.cursor().goto(0,10).unwrap().print("Some other text").unwrap();
 }
\ No newline at end of file
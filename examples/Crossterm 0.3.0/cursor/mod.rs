@@ -3,7 +3,7 @@
 //!
 
 extern crate crossterm;
-use self::crossterm::cursor::{cursor, TerminalCursor};
+use self::crossterm::cursor::{cursor, CursorShape, TerminalCursor};
 use self::crossterm::Context;
 
 /// Set the cursor to position X: 10, Y: 5 in the terminal.
@@ -23,7 +23,7 @@ pub fn pos() {
     // Get the cursor
     let mut cursor = cursor(&context);
     // get the cursor position.
-    let (x, y) = cursor.pos();
+    let (x, y) = cursor.pos().unwrap();
 }
 
 /// Move the cursor 3 up | demonstration.
@@ -90,7 +90,7 @@ pub fn print() {
        Crossterm provides method chaining so that the above points can be inlined.
     */
 
-    cursor.goto(10, 5).print("@");
+    cursor.goto(10, 5).unwrap().print("@").unwrap();
 }
 
 /// Save and reset cursor position | demonstration..
@@ -139,3 +139,12 @@ pub fn blink_cursor() {
     cursor.blink(false);
     cursor.blink(false);
 }
+
+/// Change the shape of the cursor, only works on certain terminals. | demonstration
+pub fn cursor_shape() {
+    let context = Context::new();
+
+    let cursor = cursor(&context);
+    cursor.set_shape(CursorShape::SteadyBar).unwrap();
+    println!("The cursor is a steady bar now.");
+}
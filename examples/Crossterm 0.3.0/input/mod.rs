@@ -0,0 +1,175 @@
+//!
+//! Examples of actions that could be performed with the terminal input.
+//!
+
+extern crate crossterm;
+use self::crossterm::input::{input, KeyEvent};
+use self::crossterm::raw::IntoRawMode;
+use self::crossterm::Context;
+
+use std::io::stdout;
+
+/// Print the keys that are pressed until `q` or `Ctrl+c` is pressed | demonstration.
+pub fn read_keys() {
+    let context = Context::new();
+
+    // Put the terminal into raw mode so that every key is passed through directly.
+    let _raw = stdout().into_raw_mode(context.clone()).unwrap();
+
+    let input = input(&context);
+
+    loop {
+        match input.read_key() {
+            Ok(KeyEvent::Char('q')) | Ok(KeyEvent::Ctrl('c')) => break,
+            Ok(key) => println!("{:?}\r", key),
+            Err(e) => {
+                println!("Error while reading a key: {}\r", e);
+                break;
+            }
+        }
+    }
+}
+
+/// Print the mouse events until `q` is pressed | demonstration.
+pub fn read_mouse_events() {
+    use self::crossterm::input::{Event, MouseEventKind};
+
+    let context = Context::new();
+    let _raw = stdout().into_raw_mode(context.clone()).unwrap();
+
+    let input = input(&context);
+    // Mouse events are reported until `_mouse` goes out of scope.
+    let _mouse = input.enable_mouse_capture();
+
+    loop {
+        match input.read() {
+            Ok(Event::Key(KeyEvent::Char('q'))) => break,
+            Ok(Event::Mouse(mouse)) => match mouse.kind {
+                MouseEventKind::Press(button) => {
+                    println!("{:?} pressed at x: {} y: {}\r", button, mouse.x, mouse.y)
+                }
+                kind => println!("{:?}\r", kind),
+            },
+            Ok(_) => {}
+            Err(_) => break,
+        }
+    }
+}
+
+/// Move a character around with the arrow keys while a counter keeps running | demonstration.
+pub fn poll_events() {
+    use self::crossterm::cursor::cursor;
+    use self::crossterm::input::Event;
+    use std::time::Duration;
+
+    let context = Context::new();
+    let _raw = stdout().into_raw_mode(context.clone()).unwrap();
+
+    let input = input(&context);
+    let mut cursor = cursor(&context);
+
+    let (mut x, mut y) = (10, 10);
+    let mut ticks = 0;
+
+    loop {
+        // Wait at most 50 milliseconds for a key, so that the counter keeps going.
+        if input.poll(Duration::from_millis(50)).unwrap() {
+            match input.read() {
+                Ok(Event::Key(KeyEvent::Esc)) => break,
+                Ok(Event::Key(KeyEvent::Left)) if x > 0 => x -= 1,
+                Ok(Event::Key(KeyEvent::Right)) => x += 1,
+                Ok(Event::Key(KeyEvent::Up)) if y > 1 => y -= 1,
+                Ok(Event::Key(KeyEvent::Down)) => y += 1,
+                _ => {}
+            }
+        }
+
+        ticks += 1;
+        cursor.goto(0, 0).unwrap().print(format!("ticks: {}", ticks)).unwrap();
+        cursor.goto(x, y).unwrap().print("@").unwrap();
+    }
+}
+
+/// Print the new size every time the terminal is resized until `q` is pressed | demonstration.
+pub fn read_resize_events() {
+    use self::crossterm::input::Event;
+
+    let context = Context::new();
+    let _raw = stdout().into_raw_mode(context.clone()).unwrap();
+
+    let input = input(&context);
+
+    loop {
+        match input.read() {
+            Ok(Event::Key(KeyEvent::Char('q'))) => break,
+            Ok(Event::Resize(columns, rows)) => println!("new size: {}, {}\r", columns, rows),
+            Ok(_) => {}
+            Err(_) => break,
+        }
+    }
+}
+
+/// Print the new size every time the terminal is resized, without reading input | demonstration.
+pub fn resize_callback() {
+    use self::crossterm::terminal::terminal;
+    use std::thread;
+    use std::time::Duration;
+
+    let context = Context::new();
+    let terminal = terminal(&context);
+
+    let _handle = terminal
+        .on_resize(|columns, rows| println!("new size: {}, {}", columns, rows))
+        .unwrap();
+
+    // Do something else while the callback reports the resizes.
+    thread::sleep(Duration::from_secs(10));
+}
+
+/// Print the pasted text as a whole until `q` is pressed | demonstration.
+pub fn read_pasted_text() {
+    use self::crossterm::input::Event;
+
+    let context = Context::new();
+    let _raw = stdout().into_raw_mode(context.clone()).unwrap();
+
+    let input = input(&context);
+    // Pasted text is reported as `Event::Paste` until `_paste` goes out of scope.
+    let _paste = input.enable_bracketed_paste();
+
+    loop {
+        match input.read() {
+            Ok(Event::Key(KeyEvent::Char('q'))) => break,
+            Ok(Event::Paste(text)) => println!("pasted: {:?}\r", text),
+            Ok(Event::Key(key)) => println!("typed: {:?}\r", key),
+            Ok(_) => {}
+            Err(_) => break,
+        }
+    }
+}
+
+/// Suspend the program when `Ctrl+z` is pressed, until `q` is pressed | demonstration.
+pub fn suspend_on_ctrl_z() {
+    use self::crossterm::input::Event;
+    use self::crossterm::terminal::terminal;
+
+    let context = Context::new();
+    let _raw = stdout().into_raw_mode(context.clone()).unwrap();
+
+    let input = input(&context);
+    let terminal = terminal(&context);
+
+    println!("Press Ctrl+z to suspend, type `fg` in the shell to come back.\r");
+
+    loop {
+        match input.read() {
+            Ok(Event::Key(KeyEvent::Char('q'))) => break,
+            // The terminal is in raw mode again when `suspend` returns.
+            Ok(Event::Key(KeyEvent::Ctrl('z'))) => terminal
+                .suspend(|| println!("Welcome back, press q to quit.\r"))
+                .unwrap(),
+            Ok(_) => {}
+            Err(_) => break,
+        }
+    }
+}
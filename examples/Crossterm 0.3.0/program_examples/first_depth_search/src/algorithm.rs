@@ -70,7 +70,7 @@ impl<'crossterm> FirstDepthSearch<'crossterm>
             let x = pos.x as u16;
             let y = pos.y as u16;
 
-            cursor.goto(x,y).print(cell);
+            cursor.goto(x,y).unwrap().print(cell).unwrap();
             ::std::io::stdout().flush();
 
             thread::sleep(time::Duration::from_millis(2));
@@ -40,7 +40,7 @@ pub fn run()
 fn start_algorithm(crossterm: &mut Crossterm)
 {
     // we first want to switch to alternate screen. On the alternate screen we are going to run or firstdepthsearch algorithm
-    let alternate_screen = screen::AlternateScreen::from(crossterm.context());
+    let alternate_screen = screen::AlternateScreen::from(crossterm.context()).unwrap();
 
     // setup the map size and the position to start searching for a path.
     let map_size = Size::new(100,40);
@@ -4,7 +4,7 @@ use crossterm::cursor::cursor;
 use crossterm::Crossterm;
 use crossterm::style::{ObjectStyle, StyledObject, Color};
 use crossterm::Context;
-use std::rc::Rc;
+use std::sync::Arc;
 
 use std::fmt::Display;
 
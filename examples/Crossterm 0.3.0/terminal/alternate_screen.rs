@@ -7,10 +7,10 @@ use crossterm::terminal::{self, ClearType};
 use crossterm::Context;
 
 use std::io::{stdout, Write};
-use std::rc::Rc;
+use std::sync::Arc;
 use std::{thread, time};
 
-fn print_wait_screen(context: Rc<Context>) {
+fn print_wait_screen(context: Arc<Context>) {
     let mut terminal = terminal::terminal(&context);
     terminal.clear(ClearType::All);
 
@@ -39,12 +39,12 @@ fn print_wait_screen(context: Rc<Context>) {
 }
 
 /// print wait screen on alternate screen, then swich back.
-pub fn print_wait_screen_on_alternate_window(context: Rc<Context>) {
+pub fn print_wait_screen_on_alternate_window(context: Arc<Context>) {
     // create scope. If this scope ends the screen will be switched back to mainscreen.
     // because `AlternateScreen` switches back to main screen when switching back.
     {
         // create new alternate screen instance and switch to the alternate screen.
-        let mut screen = AlternateScreen::from(context.clone());
+        let mut screen = AlternateScreen::from(context.clone()).unwrap();
 
         write!(screen,  "test");
         println!();
@@ -60,7 +60,7 @@ pub fn switch_between_main_and_alternate_screen() {
 
     {
         // create new alternate screen instance and switch to the alternate screen.
-        let mut screen = AlternateScreen::from(context.clone());
+        let mut screen = AlternateScreen::from(context.clone()).unwrap();
         cursor.goto(0, 0);
         write!(screen, "we are at the alternate screen!");
         screen.flush();
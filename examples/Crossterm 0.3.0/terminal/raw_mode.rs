@@ -6,21 +6,21 @@ use crossterm::terminal::{self, ClearType};
 use crossterm::Context;
 
 use std::io::{stdout, Write};
-use std::rc::Rc;
+use std::sync::Arc;
 use std::{thread, time};
 
 use crossterm::raw::IntoRawMode;
 
 // raw screen is not working correctly currently
-fn print_wait_screen(context: Rc<Context>) {
+fn print_wait_screen(context: Arc<Context>) {
     terminal::terminal(&context).clear(ClearType::All);
 
     let mut cursor = cursor(&context);
-    cursor.goto(0, 0).print("Welcome to the wait screen.");
+    cursor.goto(0, 0).unwrap().print("Welcome to the wait screen.").unwrap();
     cursor
         .goto(0, 1)
         .print("Please wait a few seconds until we arrive back at the main screen.");
-    cursor.goto(0, 2).print("Progress: ");
+    cursor.goto(0, 2).unwrap().print("Progress: ").unwrap();
 
     // print some progress example.
     for i in 1..5 {
@@ -43,7 +43,7 @@ pub fn print_wait_screen_on_alternate_window() {
         // create new alternate screen instance this call is also switching the screen to alternate screen.
         // then convert the output of the program to raw mode.
         // then print the wait screen on the alternate screen in raw mode.
-        let mut screen = AlternateScreen::from(context.clone());
+        let mut screen = AlternateScreen::from(context.clone()).unwrap();
         let raw_screen = screen.into_raw_mode(context.clone());
 
         // Print the wait screen.
@@ -54,3 +54,20 @@ pub fn print_wait_screen_on_alternate_window() {
 
     println!("Whe are back at the main screen");
 }
+
+pub fn restore_terminal_on_panic() {
+    use crossterm::guard::TerminalGuard;
+
+    let context = Context::new();
+
+    // while this guard is alive the terminal is restored when the program panics or is terminated by a signal.
+    let _guard = TerminalGuard::new(context.clone()).unwrap();
+
+    let mut screen = AlternateScreen::from(context.clone()).unwrap();
+    let raw_screen = screen.into_raw_mode(context.clone());
+
+    print_wait_screen(context.clone());
+
+    // the panic message is printed on the main screen and the shell is not left in raw mode.
+    panic!("Something went wrong while printing the wait screen");
+}
@@ -5,7 +5,7 @@
 extern crate crossterm;
 
 use crossterm::cursor;
-use crossterm::terminal::{terminal, ClearType};
+use crossterm::terminal::{terminal, ClearType, ClipboardSelection};
 use crossterm::Context;
 
 fn print_test_data() {
@@ -98,7 +98,7 @@ pub fn print_terminal_size() {
     // Get terminal
     let mut terminal = terminal(&context);
     // Get terminal size
-    let terminal_size = terminal.terminal_size();
+    let terminal_size = terminal.terminal_size().unwrap();
     // Print results
     print!("X: {}, y: {}", terminal_size.0, terminal_size.1);
 }
@@ -149,6 +149,81 @@ pub fn resize_terminal() {
     terminal.set_size(10, 10);
 }
 
+/// Keep a header and a footer in place while the lines in between scroll | demonstration.
+pub fn scroll_region() {
+    let context = Context::new();
+
+    // Get terminal
+    let terminal = terminal(&context);
+    let (_, rows) = terminal.terminal_size().unwrap();
+
+    cursor::cursor(&context).goto(0, 0).unwrap().print("Header").unwrap();
+    cursor::cursor(&context).goto(0, rows - 1).unwrap().print("Footer").unwrap();
+
+    // Only the lines between the header and the footer scroll.
+    terminal.set_scroll_region(1, rows - 2).unwrap();
+    cursor::cursor(&context).goto(0, 1).unwrap();
+
+    print_test_data();
+
+    // Let the whole screen scroll again.
+    terminal.reset_scroll_region().unwrap();
+}
+
+/// Insert and delete lines and characters at the cursor | demonstration.
+pub fn insert_and_delete() {
+    let context = Context::new();
+
+    print_test_data();
+
+    // Get terminal
+    let terminal = terminal(&context);
+
+    cursor::cursor(&context).goto(0, 10).unwrap();
+
+    // Insert two blank lines and delete one of them again.
+    terminal.insert_lines(2).unwrap();
+    terminal.delete_lines(1).unwrap();
+
+    // Move the text 4 characters to the right, erase 2 of them and delete the remaining 2.
+    terminal.insert_chars(4).unwrap();
+    terminal.erase_chars(2).unwrap();
+    terminal.delete_chars(2).unwrap();
+}
+
+/// Show the current job in the title of the terminal window | demonstration.
+pub fn set_title() {
+    let context = Context::new();
+
+    // Get terminal
+    let terminal = terminal(&context);
+
+    terminal.set_title("Crossterm - counting").unwrap();
+    terminal.set_icon_name("counting").unwrap();
+
+    for i in 0..10 {
+        terminal.set_title(&format!("Crossterm - counting {}", i)).unwrap();
+        ::std::thread::sleep(::std::time::Duration::from_millis(200));
+    }
+
+    // The original title is restored when the context is dropped.
+}
+
+/// Copy text to the clipboard and read it back, only works on terminals that allow it | demonstration.
+pub fn clipboard() {
+    let context = Context::new();
+
+    // Get terminal
+    let terminal = terminal(&context);
+
+    terminal.set_clipboard(ClipboardSelection::Clipboard, "copied by crossterm").unwrap();
+
+    match terminal.get_clipboard(ClipboardSelection::Clipboard) {
+        Ok(text) => println!("The clipboard contains: {}", text),
+        Err(e) => println!("The terminal does not allow reading the clipboard: {}", e),
+    }
+}
+
 /// exit the current proccess.
 pub fn exit() {
     let context = Context::new();
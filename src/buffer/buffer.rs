@@ -0,0 +1,154 @@
+//! This module contains the `Buffer`, a grid of cells that represents one frame of the screen.
+//!
+//! Note that the positions are 0 based, just like the position used by `TerminalCursor::goto`.
+//! Every cell is one column wide, double width characters like most CJK characters are not supported.
+
+use super::Cell;
+use style::ObjectStyle;
+
+/// A grid of cells that can be drawn to the screen with the `Renderer`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Buffer {
+    width: u16,
+    height: u16,
+    cells: Vec<Cell>,
+}
+
+impl Buffer {
+    /// Create a new buffer of the given size, all cells are empty.
+    pub fn new(width: u16, height: u16) -> Buffer {
+        Buffer {
+            width,
+            height,
+            cells: vec![Cell::default(); width as usize * height as usize],
+        }
+    }
+
+    /// Get the width of the buffer.
+    pub fn width(&self) -> u16 {
+        self.width
+    }
+
+    /// Get the height of the buffer.
+    pub fn height(&self) -> u16 {
+        self.height
+    }
+
+    /// Get the cell at the given position, returns `None` when the position is outside the buffer.
+    pub fn get(&self, x: u16, y: u16) -> Option<&Cell> {
+        self.index_of(x, y).map(move |index| &self.cells[index])
+    }
+
+    /// Get the cell at the given position mutably, returns `None` when the position is outside the buffer.
+    pub fn get_mut(&mut self, x: u16, y: u16) -> Option<&mut Cell> {
+        match self.index_of(x, y) {
+            Some(index) => Some(&mut self.cells[index]),
+            None => None,
+        }
+    }
+
+    /// Replace the cell at the given position, positions outside the buffer are ignored.
+    pub fn set(&mut self, x: u16, y: u16, cell: Cell) {
+        if let Some(current) = self.get_mut(x, y) {
+            *current = cell;
+        }
+    }
+
+    /// Set the character and style of the cell at the given position, positions outside the buffer are ignored.
+    pub fn set_char(&mut self, x: u16, y: u16, symbol: char, style: &ObjectStyle) {
+        if let Some(cell) = self.get_mut(x, y) {
+            cell.symbol.clear();
+            cell.symbol.push(symbol);
+            cell.style = style.clone();
+        }
+    }
+
+    /// Write the given string from the given position to the right, one character per cell.
+    ///
+    /// The string is cut off at the right side of the buffer, newlines are not interpreted.
+    pub fn set_string(&mut self, x: u16, y: u16, string: &str, style: &ObjectStyle) {
+        for (offset, symbol) in string.chars().enumerate() {
+            let column = x as usize + offset;
+
+            if column >= self.width as usize {
+                break;
+            }
+
+            self.set_char(column as u16, y, symbol, style);
+        }
+    }
+
+    /// Make all cells empty.
+    pub fn clear(&mut self) {
+        for cell in self.cells.iter_mut() {
+            cell.reset();
+        }
+    }
+
+    /// Change the size of the buffer, cells that are still within the new size are kept.
+    pub fn resize(&mut self, width: u16, height: u16) {
+        let mut resized = Buffer::new(width, height);
+
+        for y in 0..height.min(self.height) {
+            for x in 0..width.min(self.width) {
+                let index = self.index_of(x, y).unwrap();
+                resized.set(x, y, self.cells[index].clone());
+            }
+        }
+
+        *self = resized;
+    }
+
+    /// Get all cells, row by row from the top left to the bottom right.
+    pub fn cells(&self) -> &[Cell] {
+        &self.cells
+    }
+
+    fn index_of(&self, x: u16, y: u16) -> Option<usize> {
+        if x < self.width && y < self.height {
+            Some(y as usize * self.width as usize + x as usize)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Buffer;
+    use buffer::Cell;
+    use style::ObjectStyle;
+
+    #[test]
+    fn positions_outside_the_buffer_are_ignored() {
+        let mut buffer = Buffer::new(3, 2);
+        buffer.set_char(3, 0, 'x', &ObjectStyle::new());
+        buffer.set_char(0, 2, 'x', &ObjectStyle::new());
+
+        assert_eq!(buffer, Buffer::new(3, 2));
+        assert_eq!(buffer.get(3, 0), None);
+    }
+
+    #[test]
+    fn set_string_is_cut_off_at_the_right_side() {
+        let mut buffer = Buffer::new(3, 1);
+        buffer.set_string(1, 0, "abc", &ObjectStyle::new());
+
+        let symbols: Vec<&str> = buffer.cells().iter().map(|cell| cell.symbol.as_str()).collect();
+        assert_eq!(symbols, vec![" ", "a", "b"]);
+    }
+
+    #[test]
+    fn resize_keeps_the_cells_within_the_new_size() {
+        let mut buffer = Buffer::new(3, 2);
+        buffer.set_string(0, 0, "abc", &ObjectStyle::new());
+        buffer.set_string(0, 1, "def", &ObjectStyle::new());
+
+        buffer.resize(2, 3);
+
+        assert_eq!(buffer.width(), 2);
+        assert_eq!(buffer.height(), 3);
+        assert_eq!(buffer.get(1, 1), Some(&Cell::new("e", ObjectStyle::new())));
+        assert_eq!(buffer.get(1, 2), Some(&Cell::default()));
+    }
+}
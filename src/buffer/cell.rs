@@ -0,0 +1,34 @@
+//! This module contains the `Cell`, a single position on the screen.
+
+use style::ObjectStyle;
+
+/// A single cell of a `Buffer`, it holds the symbol that is drawn and the style it is drawn with.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cell {
+    /// The symbol that is drawn, this is a single character or a grapheme like a letter with an accent.
+    pub symbol: String,
+    pub style: ObjectStyle,
+}
+
+impl Cell {
+    /// Create a new cell with the given symbol and style.
+    pub fn new<S: Into<String>>(symbol: S, style: ObjectStyle) -> Cell {
+        Cell {
+            symbol: symbol.into(),
+            style,
+        }
+    }
+
+    /// Make this cell empty, an empty cell is a space without style.
+    pub fn reset(&mut self) {
+        self.symbol.clear();
+        self.symbol.push(' ');
+        self.style = ObjectStyle::new();
+    }
+}
+
+impl Default for Cell {
+    fn default() -> Cell {
+        Cell::new(" ", ObjectStyle::new())
+    }
+}
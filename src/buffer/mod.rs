@@ -0,0 +1,33 @@
+//! With this module you can draw a whole screen at once without flickering.
+//!
+//! Full-screen programs, like games and dashboards, draw each frame into a `Buffer`: a grid of cells
+//! where every cell holds a symbol and the `ObjectStyle` it is drawn with.
+//! The `Renderer` compares the new frame with the frame it has drawn before
+//! and only writes the cells that changed, with as few cursor movements and color changes as possible.
+//!
+//! ```rust,no_run
+//! extern crate crossterm;
+//! use crossterm::buffer::{Buffer, Renderer};
+//! use crossterm::style::{Color, ObjectStyle};
+//! use crossterm::Context;
+//!
+//! let context = Context::new();
+//! let mut renderer = Renderer::new(context.clone());
+//! let mut buffer = Buffer::new(80, 24);
+//!
+//! for score in 0..100 {
+//!     buffer.clear();
+//!     buffer.set_string(0, 0, &format!("score: {}", score), &ObjectStyle::new().fg(Color::Green));
+//!     renderer.render(&buffer).unwrap();
+//! }
+//! ```
+//!
+//! Note that the renderer writes ANSI escape codes, so it only works on terminals that support them.
+
+pub mod buffer;
+pub mod cell;
+pub mod renderer;
+
+pub use self::buffer::Buffer;
+pub use self::cell::Cell;
+pub use self::renderer::Renderer;
@@ -0,0 +1,376 @@
+//! This module contains the `Renderer` that draws a `Buffer` to the screen.
+//!
+//! The renderer remembers the last frame it has drawn. When a new frame is rendered only the cells that differ are written,
+//! the cursor is only moved when the next changed cell is not directly after the last written cell
+//! and the colors, attributes and hyperlinks are only changed when they differ from the style of the last written cell.
+//! The whole frame is written to the `ScreenManager` at once.
+
+use super::{Buffer, Cell};
+use command::{BeginHyperlink, EndHyperlink, Goto, MoveRight, ResetColor, SetAttr, SetBg, SetFg};
+use style::ObjectStyle;
+use {Command, Context, Result};
+
+use std::io::Write;
+use std::sync::Arc;
+
+/// Draws buffers to the screen of the given context, only the changes since the previous frame are written.
+pub struct Renderer {
+    context: Arc<Context>,
+    previous: Option<Buffer>,
+}
+
+impl Renderer {
+    /// Create a new renderer, the first frame it renders is drawn completely.
+    pub fn new(context: Arc<Context>) -> Renderer {
+        Renderer {
+            context,
+            previous: None,
+        }
+    }
+
+    /// Draw the given buffer, only the cells that differ from the previous frame are written.
+    ///
+    /// When the size of the buffer differs from the previous frame the whole buffer is drawn.
+    ///
+    /// #Example
+    ///
+    /// ```rust,no_run
+    ///
+    /// extern crate crossterm;
+    /// use self::crossterm::buffer::{Buffer, Renderer};
+    /// use self::crossterm::style::{Color, ObjectStyle};
+    /// use self::crossterm::Context;
+    ///
+    /// let context = Context::new();
+    /// let mut renderer = Renderer::new(context.clone());
+    /// let mut buffer = Buffer::new(20, 5);
+    ///
+    /// for frame in 0..20 {
+    ///     buffer.clear();
+    ///     buffer.set_char(frame, 2, '@', &ObjectStyle::new().fg(Color::Red));
+    ///
+    ///     // Only the old and the new position of `@` are written.
+    ///     renderer.render(&buffer).unwrap();
+    /// }
+    ///
+    /// ```
+    pub fn render(&mut self, buffer: &Buffer) -> Result<()> {
+        let output = match self.previous {
+            Some(ref previous) if previous.width() == buffer.width() && previous.height() == buffer.height() => {
+                diff(Some(previous), buffer)
+            }
+            _ => diff(None, buffer),
+        };
+
+        if !output.is_empty() {
+            let mut screen_manager = self.context.screen_manager.lock().unwrap();
+            screen_manager.write_string(output)?;
+            screen_manager.flush()?;
+        }
+
+        self.previous = Some(buffer.clone());
+        Ok(())
+    }
+
+    /// Forget the previous frame so that the next frame is drawn completely.
+    ///
+    /// Use this when the screen has been changed without this renderer, for example after clearing the terminal or after a resize.
+    pub fn invalidate(&mut self) {
+        self.previous = None;
+    }
+}
+
+/// Create the output that changes the `previous` frame into the `next` frame, `None` draws the whole frame.
+fn diff(previous: Option<&Buffer>, next: &Buffer) -> String {
+    let mut output = String::new();
+    // The cursor position and the style are unknown until they are set by the renderer.
+    let mut cursor: Option<(u16, u16)> = None;
+    let mut style: Option<ObjectStyle> = None;
+
+    for y in 0..next.height() {
+        for x in 0..next.width() {
+            let cell = next.get(x, y).unwrap();
+
+            if let Some(previous) = previous {
+                if previous.get(x, y) == Some(cell) {
+                    continue;
+                }
+            }
+
+            move_cursor(&mut output, cursor, x, y);
+            change_style(&mut output, style.as_ref(), &cell.style);
+            write_symbol(&mut output, cell);
+
+            style = Some(cell.style.clone());
+
+            // The cursor does not move past the last column, it waits there until the next character is written.
+            cursor = if x + 1 < next.width() { Some((x + 1, y)) } else { None };
+        }
+    }
+
+    if let Some(mut style) = style {
+        // A link that is still open would also link the text that is written after the frame.
+        if style.hyperlink.take().is_some() {
+            output.push_str(&EndHyperlink.ansi_code());
+        }
+
+        if style != ObjectStyle::new() {
+            output.push_str(&ResetColor.ansi_code());
+        }
+    }
+
+    output
+}
+
+/// Move the cursor from the given position to the given position with the shortest escape code.
+fn move_cursor(output: &mut String, cursor: Option<(u16, u16)>, x: u16, y: u16) {
+    match cursor {
+        Some((cursor_x, cursor_y)) if cursor_y == y && cursor_x == x => {}
+        Some((cursor_x, cursor_y)) if cursor_y == y && cursor_x < x => {
+            let move_right = MoveRight(x - cursor_x).ansi_code();
+            let goto = Goto(x, y).ansi_code();
+
+            output.push_str(if move_right.len() < goto.len() { &move_right } else { &goto });
+        }
+        _ => output.push_str(&Goto(x, y).ansi_code()),
+    }
+}
+
+/// Change the style from the given style to the given style, `None` means that the current style is unknown.
+fn change_style(output: &mut String, current: Option<&ObjectStyle>, next: &ObjectStyle) {
+    let empty = ObjectStyle::new();
+
+    // Resetting the style does not end a link, no link is open when the style is unknown since a frame ends all links.
+    let current_link = current.and_then(|current| current.hyperlink.as_ref());
+
+    if current_link != next.hyperlink.as_ref() {
+        match next.hyperlink {
+            Some(ref link) => output.push_str(&BeginHyperlink(link.clone()).ansi_code()),
+            None => output.push_str(&EndHyperlink.ansi_code()),
+        }
+    }
+
+    let current = match current {
+        Some(current) if current == next => return,
+        // Attributes can only be turned off by resetting the whole style.
+        Some(current) if !removes_attributes(current, next) => current,
+        _ => {
+            output.push_str(&ResetColor.ansi_code());
+            &empty
+        }
+    };
+
+    if current.fg_color != next.fg_color {
+        match next.fg_color {
+            Some(color) => output.push_str(&SetFg(color).ansi_code()),
+            None => output.push_str(csi!("39m")),
+        }
+    }
+
+    if current.bg_color != next.bg_color {
+        match next.bg_color {
+            Some(color) => output.push_str(&SetBg(color).ansi_code()),
+            None => output.push_str(csi!("49m")),
+        }
+    }
+
+    for attr in next.attrs.iter() {
+        if !current.attrs.contains(attr) {
+            output.push_str(&SetAttr(*attr).ansi_code());
+        }
+    }
+}
+
+fn removes_attributes(current: &ObjectStyle, next: &ObjectStyle) -> bool {
+    current.attrs.iter().any(|attr| !next.attrs.contains(attr))
+}
+
+/// Write the symbol of the cell, control characters would move the cursor so they are written as a space.
+fn write_symbol(output: &mut String, cell: &Cell) {
+    if cell.symbol.is_empty() || cell.symbol.chars().any(|c| c.is_control()) {
+        output.push(' ');
+    } else {
+        output.push_str(&cell.symbol);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{diff, Renderer};
+    use buffer::Buffer;
+    use style::{Attribute, Hyperlink, ObjectStyle};
+    use Context;
+
+    use std::io::{self, Write};
+    use std::sync::{Arc, Mutex};
+
+    /// An output that can still be read after it is given to a context.
+    #[derive(Clone)]
+    struct SharedOutput(Arc<Mutex<Vec<u8>>>);
+
+    impl SharedOutput {
+        fn take(&self) -> String {
+            let bytes = ::std::mem::take(&mut *self.0.lock().unwrap());
+            String::from_utf8(bytes).unwrap()
+        }
+    }
+
+    impl Write for SharedOutput {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn bold() -> ObjectStyle {
+        let mut style = ObjectStyle::new();
+        style.add_attr(Attribute::Bold);
+        style
+    }
+
+    #[test]
+    fn unchanged_frame_writes_nothing() {
+        let mut buffer = Buffer::new(4, 2);
+        buffer.set_string(0, 0, "ab", &bold());
+
+        assert_eq!(diff(Some(&buffer), &buffer), "");
+    }
+
+    // The style is unknown before the first written cell, so it is reset first.
+    #[test]
+    fn single_changed_cell_is_written() {
+        let previous = Buffer::new(4, 2);
+        let mut next = previous.clone();
+        next.set_char(3, 1, 'x', &ObjectStyle::new());
+
+        assert_eq!(diff(Some(&previous), &next), "\x1B[2;4H\x1B[0mx");
+    }
+
+    #[test]
+    fn adjacent_cells_do_not_move_the_cursor() {
+        let previous = Buffer::new(4, 1);
+        let mut next = previous.clone();
+        next.set_string(1, 0, "ab", &ObjectStyle::new());
+
+        assert_eq!(diff(Some(&previous), &next), "\x1B[1;2H\x1B[0mab");
+    }
+
+    #[test]
+    fn cursor_moves_right_on_the_same_line() {
+        let previous = Buffer::new(20, 2);
+        let mut next = previous.clone();
+        next.set_char(1, 0, 'a', &ObjectStyle::new());
+        next.set_char(10, 0, 'b', &ObjectStyle::new());
+
+        // `ESC [ 8 C` is shorter than `ESC [ 1 ; 11 H`.
+        assert_eq!(diff(Some(&previous), &next), "\x1B[1;2H\x1B[0ma\x1B[8Cb");
+    }
+
+    #[test]
+    fn cursor_goes_to_an_other_line() {
+        let previous = Buffer::new(20, 2);
+        let mut next = previous.clone();
+        next.set_char(1, 0, 'a', &ObjectStyle::new());
+        next.set_char(5, 1, 'b', &ObjectStyle::new());
+
+        assert_eq!(diff(Some(&previous), &next), "\x1B[1;2H\x1B[0ma\x1B[2;6Hb");
+    }
+
+    #[test]
+    fn cursor_position_is_unknown_after_the_last_column() {
+        let previous = Buffer::new(2, 2);
+        let mut next = previous.clone();
+        next.set_char(1, 0, 'a', &ObjectStyle::new());
+        next.set_char(0, 1, 'b', &ObjectStyle::new());
+
+        assert_eq!(diff(Some(&previous), &next), "\x1B[1;2H\x1B[0ma\x1B[2;1Hb");
+    }
+
+    #[test]
+    fn removing_an_attribute_resets_the_style() {
+        let previous = Buffer::new(4, 1);
+        let mut next = previous.clone();
+        next.set_char(0, 0, 'a', &bold());
+        next.set_char(1, 0, 'b', &ObjectStyle::new());
+
+        assert_eq!(
+            diff(Some(&previous), &next),
+            "\x1B[1;1H\x1B[0m\x1B[1ma\x1B[0mb"
+        );
+    }
+
+    #[test]
+    fn adding_an_attribute_keeps_the_style() {
+        let previous = Buffer::new(4, 1);
+        let mut next = previous.clone();
+        next.set_char(0, 0, 'a', &ObjectStyle::new());
+        next.set_char(1, 0, 'b', &bold());
+
+        // The style is reset at the end, so that text written after the frame is not bold.
+        assert_eq!(
+            diff(Some(&previous), &next),
+            "\x1B[1;1H\x1B[0ma\x1B[1mb\x1B[0m"
+        );
+    }
+
+    #[test]
+    fn control_characters_are_written_as_space() {
+        let previous = Buffer::new(2, 1);
+        let mut next = previous.clone();
+        next.set_char(0, 0, '\n', &ObjectStyle::new());
+
+        assert_eq!(diff(Some(&previous), &next), "\x1B[1;1H\x1B[0m ");
+    }
+
+    #[test]
+    fn size_change_redraws_the_whole_frame() {
+        let output = SharedOutput(Arc::new(Mutex::new(Vec::new())));
+        let context = Context::with_output(Box::new(output.clone()));
+        let mut renderer = Renderer::new(context);
+
+        let mut buffer = Buffer::new(2, 1);
+        buffer.set_string(0, 0, "ab", &ObjectStyle::new());
+
+        renderer.render(&buffer).unwrap();
+        assert_eq!(output.take(), "\x1B[1;1H\x1B[0mab");
+
+        renderer.render(&buffer).unwrap();
+        assert_eq!(output.take(), "");
+
+        buffer.resize(3, 1);
+        renderer.render(&buffer).unwrap();
+        assert_eq!(output.take(), "\x1B[1;1H\x1B[0mab ");
+    }
+
+    #[test]
+    fn hyperlinks_begin_and_end_between_cells() {
+        let previous = Buffer::new(4, 1);
+        let mut next = previous.clone();
+        let linked = ObjectStyle::new().link(Hyperlink::new("https://example.com"));
+        next.set_string(0, 0, "ab", &linked);
+        next.set_char(2, 0, 'c', &ObjectStyle::new());
+
+        assert_eq!(
+            diff(Some(&previous), &next),
+            "\x1B[1;1H\x1B]8;;https://example.com\x1B\\\x1B[0mab\x1B]8;;\x1B\\c"
+        );
+    }
+
+    #[test]
+    fn open_hyperlink_is_ended_with_the_frame() {
+        let previous = Buffer::new(4, 1);
+        let mut next = previous.clone();
+        let first = ObjectStyle::new().link(Hyperlink::with_id("https://example.com", "1"));
+        let second = bold().link(Hyperlink::new("file:///etc/hosts"));
+        next.set_char(0, 0, 'a', &first);
+        next.set_char(1, 0, 'b', &second);
+
+        assert_eq!(
+            diff(Some(&previous), &next),
+            "\x1B[1;1H\x1B]8;id=1;https://example.com\x1B\\\x1B[0ma\x1B]8;;file:///etc/hosts\x1B\\\x1B[1mb\x1B]8;;\x1B\\\x1B[0m"
+        );
+    }
+}
@@ -0,0 +1,65 @@
+//! With this module you can work with the terminal actions as values.
+//!
+//! Every action like moving the cursor or setting the color is a type that implements `Command`.
+//! A command knows the ANSI escape code that performs the action, so commands can be stored, composed and written in batches
+//! to anything that implements `std::io::Write` with the `queue!` and `execute!` macros.
+//!
+//! ```rust,no_run
+//! #[macro_use]
+//! extern crate crossterm;
+//!
+//! use crossterm::command::{Clear, ClearType, Goto, Print, SetFg};
+//! use crossterm::style::Color;
+//!
+//! use std::io::{stdout, Write};
+//!
+//! fn main() {
+//!     let mut stdout = stdout();
+//!
+//!     // Queue the commands, nothing is written to the terminal yet.
+//!     queue!(stdout, Clear(ClearType::All), Goto(5, 5), SetFg(Color::Red)).unwrap();
+//!     queue!(stdout, Print("Hello")).unwrap();
+//!
+//!     // Write the queued commands to the terminal.
+//!     stdout.flush().unwrap();
+//!
+//!     // Or queue and flush in one go.
+//!     execute!(stdout, Goto(0, 0), Print("Bye")).unwrap();
+//! }
+//! ```
+//!
+//! Note that commands only work for terminals that support ANSI escape codes,
+//! on older windows consoles use the modules like `cursor` and `terminal` that fall back to the WinApi.
+
+pub use cursor::commands::{
+    Blink, Goto, Hide, MoveDown, MoveLeft, MoveRight, MoveUp, ResetPosition, ResetShape,
+    SavePosition, SetShape, Show,
+};
+pub use cursor::CursorShape;
+pub use style::commands::{
+    BeginHyperlink, EndHyperlink, Print, ResetColor, SetAttr, SetBg, SetFg,
+};
+pub use terminal::commands::{
+    Clear, DeleteChars, DeleteLines, EraseChars, InsertChars, InsertLines, Passthrough, PopTitle,
+    PushTitle, ResetScrollRegion, ScrollDown, ScrollUp, SetClipboard, SetIconName,
+    SetScrollRegion, SetSize, SetTitle, SetTitleAndIconName,
+};
+pub use terminal::{ClearType, ClipboardSelection, Multiplexer};
+
+/// A terminal action that can be performed by writing an ANSI escape code.
+pub trait Command {
+    /// Get the ANSI escape code that performs this action.
+    fn ansi_code(&self) -> String;
+}
+
+impl<'a, T: Command + ?Sized> Command for &'a T {
+    fn ansi_code(&self) -> String {
+        (**self).ansi_code()
+    }
+}
+
+impl<T: Command + ?Sized> Command for Box<T> {
+    fn ansi_code(&self) -> String {
+        (**self).ansi_code()
+    }
+}
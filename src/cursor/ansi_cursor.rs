@@ -2,97 +2,99 @@
 //! This module is used for windows 10 terminals and unix terminals by default.
 //! Note that the cursor position is 0 based. This means that we start counting at 0 when setting the cursor position ect.
 
+use super::commands::*;
 use super::*;
 use shared::functions;
-use Context;
+use state::commands::shared_commands::{CursorShapeCommand, HideCursorCommand};
+use {Command, Context, Result};
 
 /// This struct is an ansi implementation for cursor related actions.
 pub struct AnsiCursor {
-    context: Rc<Context>,
+    context: Arc<Context>,
 }
 
 impl AnsiCursor {
-    pub fn new(context: Rc<Context>) -> Box<AnsiCursor> {
+    pub fn new(context: Arc<Context>) -> Box<AnsiCursor> {
         Box::from(AnsiCursor { context })
     }
 }
 
 impl ITerminalCursor for AnsiCursor {
-    fn goto(&self, x: u16, y: u16) {
+    fn goto(&self, x: u16, y: u16) -> Result<()> {
         let mut screen = self.context.screen_manager.lock().unwrap();
-        {
-            screen.write_string(format!(csi!("{};{}H"), y + 1, x + 1));
-        }
+        screen.write_string(Goto(x, y).ansi_code())?;
+        Ok(())
     }
 
-    fn pos(&self) -> (u16, u16) {
-        functions::get_cursor_position(self.context.clone())
+    fn pos(&self, timeout: Duration) -> Result<(u16, u16)> {
+        functions::get_cursor_position(self.context.clone(), timeout)
     }
 
-    fn move_up(&self, count: u16) {
+    fn move_up(&self, count: u16) -> Result<()> {
         let mut screen = self.context.screen_manager.lock().unwrap();
-        {
-            screen.write_string(format!(csi!("{}A"), count));
-        }
+        screen.write_string(MoveUp(count).ansi_code())?;
+        Ok(())
     }
 
-    fn move_right(&self, count: u16) {
+    fn move_right(&self, count: u16) -> Result<()> {
         let mut screen = self.context.screen_manager.lock().unwrap();
-        {
-            screen.write_string(format!(csi!("{}C"), count));
-        }
+        screen.write_string(MoveRight(count).ansi_code())?;
+        Ok(())
     }
 
-    fn move_down(&self, count: u16) {
+    fn move_down(&self, count: u16) -> Result<()> {
         let mut screen = self.context.screen_manager.lock().unwrap();
-        {
-            screen.write_string(format!(csi!("{}B"), count));
-        }
+        screen.write_string(MoveDown(count).ansi_code())?;
+        Ok(())
     }
 
-    fn move_left(&self, count: u16) {
+    fn move_left(&self, count: u16) -> Result<()> {
         let mut screen = self.context.screen_manager.lock().unwrap();
-        {
-            screen.write_string(format!(csi!("{}D"), count));
-        }
+        screen.write_string(MoveLeft(count).ansi_code())?;
+        Ok(())
     }
 
-    fn save_position(&self) {
+    fn save_position(&self) -> Result<()> {
         let mut screen = self.context.screen_manager.lock().unwrap();
-        {
-            screen.write_str(csi!("s"));
-        }
+        screen.write_string(SavePosition.ansi_code())?;
+        Ok(())
     }
 
-    fn reset_position(&self) {
+    fn reset_position(&self) -> Result<()> {
         let mut screen = self.context.screen_manager.lock().unwrap();
-        {
-            screen.write_str(csi!("u"));
-        }
+        screen.write_string(ResetPosition.ansi_code())?;
+        Ok(())
     }
 
-    fn hide(&self) {
-        let mut screen = self.context.screen_manager.lock().unwrap();
-        {
-            screen.write_str(csi!("?25l"));
-        }
+    fn hide(&self) -> Result<()> {
+        // The command takes the place of the previous hide command, executing it hides the cursor.
+        HideCursorCommand::new(&self.context)?;
+        Ok(())
     }
 
-    fn show(&self) {
-        let mut screen = self.context.screen_manager.lock().unwrap();
-        {
-            screen.write_str(csi!("?25h"));
+    fn show(&self) -> Result<()> {
+        let mut state = self.context.state_manager.lock().unwrap();
+
+        match state.named_change(HideCursorCommand::NAME) {
+            // Removing the command undoes it, which shows the cursor.
+            Some(id) => state.remove(id),
+            None => {
+                let mut screen = self.context.screen_manager.lock().unwrap();
+                screen.write_string(Show.ansi_code())?;
+                Ok(())
+            }
         }
     }
 
-    fn blink(&self, blink: bool) {
+    fn blink(&self, blink: bool) -> Result<()> {
         let mut screen = self.context.screen_manager.lock().unwrap();
-        {
-            if blink {
-                screen.write_str(csi!("?12h"));
-            } else {
-                screen.write_str(csi!("?12l"));
-            }
-        }
+        screen.write_string(Blink(blink).ansi_code())?;
+        Ok(())
+    }
+
+    fn set_shape(&self, shape: CursorShape) -> Result<()> {
+        // The command takes the place of the previous shape command, executing it sets the shape.
+        CursorShapeCommand::new(&self.context, shape)?;
+        Ok(())
     }
 }
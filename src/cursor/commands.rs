@@ -0,0 +1,139 @@
+//! This module contains the commands for cursor related actions, see the `command` module for how to use them.
+//!
+//! Note that the cursor position is 0 based, just like the position used by `TerminalCursor::goto`.
+
+use super::CursorShape;
+use command::Command;
+
+/// Goto some position (x,y) in the terminal.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Goto(pub u16, pub u16);
+
+impl Command for Goto {
+    fn ansi_code(&self) -> String {
+        format!(csi!("{};{}H"), self.1.saturating_add(1), self.0.saturating_add(1))
+    }
+}
+
+/// Move the cursor `n` times up.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct MoveUp(pub u16);
+
+impl Command for MoveUp {
+    fn ansi_code(&self) -> String {
+        format!(csi!("{}A"), self.0)
+    }
+}
+
+/// Move the cursor `n` times down.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct MoveDown(pub u16);
+
+impl Command for MoveDown {
+    fn ansi_code(&self) -> String {
+        format!(csi!("{}B"), self.0)
+    }
+}
+
+/// Move the cursor `n` times to the right.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct MoveRight(pub u16);
+
+impl Command for MoveRight {
+    fn ansi_code(&self) -> String {
+        format!(csi!("{}C"), self.0)
+    }
+}
+
+/// Move the cursor `n` times to the left.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct MoveLeft(pub u16);
+
+impl Command for MoveLeft {
+    fn ansi_code(&self) -> String {
+        format!(csi!("{}D"), self.0)
+    }
+}
+
+/// Save the cursor position so that it can be recalled with `ResetPosition`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct SavePosition;
+
+impl Command for SavePosition {
+    fn ansi_code(&self) -> String {
+        csi!("s").to_string()
+    }
+}
+
+/// Return to the saved cursor position.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ResetPosition;
+
+impl Command for ResetPosition {
+    fn ansi_code(&self) -> String {
+        csi!("u").to_string()
+    }
+}
+
+/// Hide the terminal cursor.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Hide;
+
+impl Command for Hide {
+    fn ansi_code(&self) -> String {
+        csi!("?25l").to_string()
+    }
+}
+
+/// Show the terminal cursor.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Show;
+
+impl Command for Show {
+    fn ansi_code(&self) -> String {
+        csi!("?25h").to_string()
+    }
+}
+
+/// Enable or disable the blinking of the cursor.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Blink(pub bool);
+
+impl Command for Blink {
+    fn ansi_code(&self) -> String {
+        if self.0 {
+            csi!("?12h").to_string()
+        } else {
+            csi!("?12l").to_string()
+        }
+    }
+}
+
+/// Change the shape of the cursor.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct SetShape(pub CursorShape);
+
+impl Command for SetShape {
+    fn ansi_code(&self) -> String {
+        let value = match self.0 {
+            CursorShape::BlinkingBlock => 1,
+            CursorShape::SteadyBlock => 2,
+            CursorShape::BlinkingUnderline => 3,
+            CursorShape::SteadyUnderline => 4,
+            CursorShape::BlinkingBar => 5,
+            CursorShape::SteadyBar => 6,
+        };
+
+        format!(csi!("{} q"), value)
+    }
+}
+
+/// Reset the shape of the cursor to the default shape of the terminal.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ResetShape;
+
+impl Command for ResetShape {
+    fn ansi_code(&self) -> String {
+        csi!("0 q").to_string()
+    }
+}
@@ -5,23 +5,27 @@
 
 use super::super::shared::functions;
 use super::*;
-use std::io::Write;
-use Context;
+use {Context, Result};
 
 use std::fmt::Display;
-use std::rc::Rc;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// The time `pos()` waits for the terminal to report the cursor position.
+const POSITION_TIMEOUT: Duration = Duration::from_secs(2);
 
 /// Struct that stores an specific platform implementation for cursor related actions.
 pub struct TerminalCursor {
-    context: Rc<Context>,
+    context: Arc<Context>,
     terminal_cursor: Box<ITerminalCursor>,
 }
 
 impl TerminalCursor {
     /// Create new cursor instance whereon cursor related actions can be performed.
-    pub fn new(context: Rc<Context>) -> TerminalCursor {
+    pub fn new(context: Arc<Context>) -> TerminalCursor {
         #[cfg(target_os = "windows")]
-        let cursor = functions::get_module::<Box<ITerminalCursor>>(
+        let cursor = functions::get_module_for_context::<Box<ITerminalCursor>>(
+            &context,
             WinApiCursor::new(context.screen_manager.clone()),
             AnsiCursor::new(context.clone()),
         ).unwrap();
@@ -56,13 +60,19 @@ impl TerminalCursor {
     ///    }
     ///
     /// ```
-    pub fn goto(&mut self, x: u16, y: u16) -> &mut TerminalCursor {
-        self.terminal_cursor.goto(x, y);
-        self
+    pub fn goto(&mut self, x: u16, y: u16) -> Result<&mut TerminalCursor> {
+        self.terminal_cursor.goto(x, y)?;
+        Ok(self)
     }
 
     /// Get current cursor position (x,y) in the terminal.
     ///
+    /// The position is 0-based, just like the position used by `goto`.
+    /// Note that on unix systems it used to be 1-based.
+    ///
+    /// On unix systems the terminal is asked for the position, this fails with `ErrorKind::Timeout`
+    /// when the terminal does not answer within two seconds.
+    ///
     /// #Example
     ///
     /// ```rust
@@ -78,12 +88,41 @@ impl TerminalCursor {
     ///      // Get the cursor
     ///      let mut cursor = cursor(&context);
     ///      // get the cursor position.
-    ///      let (x,y) = cursor.pos();
+    ///      let (x,y) = cursor.pos().unwrap();
     ///  }
     ///
     /// ```
-    pub fn pos(&mut self) -> (u16, u16) {
-        self.terminal_cursor.pos()
+    pub fn pos(&mut self) -> Result<(u16, u16)> {
+        self.terminal_cursor.pos(POSITION_TIMEOUT)
+    }
+
+    /// Get current cursor position (x,y) in the terminal, waits at most `timeout` for the terminal to report it.
+    ///
+    /// The position is 0-based, just like the position used by `goto`.
+    /// Keys that are pressed while waiting are not lost, these are returned by `TerminalInput::read()`.
+    /// Only a key that is reported like a position, such as `ESC [ 1 ; 5 R` for F3 with Ctrl, is taken as the position.
+    ///
+    /// #Example
+    ///
+    /// ```rust,no_run
+    ///
+    /// extern crate crossterm;
+    /// use self::crossterm::cursor::cursor;
+    /// use self::crossterm::{Context, ErrorKind};
+    ///
+    /// use std::time::Duration;
+    ///
+    /// let context = Context::new();
+    ///
+    /// match cursor(&context).pos_timeout(Duration::from_millis(100)) {
+    ///     Ok((x, y)) => println!("{}, {}", x, y),
+    ///     Err(ref error) if error.kind() == ErrorKind::Timeout => println!("the terminal does not report the position"),
+    ///     Err(error) => println!("{}", error),
+    /// }
+    ///
+    /// ```
+    pub fn pos_timeout(&mut self, timeout: Duration) -> Result<(u16, u16)> {
+        self.terminal_cursor.pos(timeout)
     }
 
     /// Move the current cursor position `n` times up.
@@ -107,9 +146,9 @@ impl TerminalCursor {
     /// }
     ///
     /// ```
-    pub fn move_up(&mut self, count: u16) -> &mut TerminalCursor {
-        self.terminal_cursor.move_up(count);
-        self
+    pub fn move_up(&mut self, count: u16) -> Result<&mut TerminalCursor> {
+        self.terminal_cursor.move_up(count)?;
+        Ok(self)
     }
 
     /// Move the current cursor position `n` times right.
@@ -131,9 +170,9 @@ impl TerminalCursor {
     ///      cursor.move_right(3);
     ///  }
     /// ```
-    pub fn move_right(&mut self, count: u16) -> &mut TerminalCursor {
-        self.terminal_cursor.move_right(count);
-        self
+    pub fn move_right(&mut self, count: u16) -> Result<&mut TerminalCursor> {
+        self.terminal_cursor.move_right(count)?;
+        Ok(self)
     }
 
     /// Move the current cursor position `n` times down.
@@ -157,9 +196,9 @@ impl TerminalCursor {
     /// }
     ///
     /// ```
-    pub fn move_down(&mut self, count: u16) -> &mut TerminalCursor {
-        self.terminal_cursor.move_down(count);
-        self
+    pub fn move_down(&mut self, count: u16) -> Result<&mut TerminalCursor> {
+        self.terminal_cursor.move_down(count)?;
+        Ok(self)
     }
 
     /// Move the current cursor position `n` times left.
@@ -183,9 +222,9 @@ impl TerminalCursor {
     ///  }
     ///
     /// ```
-    pub fn move_left(&mut self, count: u16) -> &mut TerminalCursor {
-        self.terminal_cursor.move_left(count);
-        self
+    pub fn move_left(&mut self, count: u16) -> Result<&mut TerminalCursor> {
+        self.terminal_cursor.move_left(count)?;
+        Ok(self)
     }
 
     /// Print an value at the current cursor position.
@@ -220,24 +259,20 @@ impl TerminalCursor {
     ///
     /// // but now we can chain the methods so it looks cleaner and it automatically flushes the buffer.  
     /// cursor::cursor(&context)
-    /// .goto(10,10)
-    /// .print("@");
+    /// .goto(10,10).unwrap()
+    /// .print("@").unwrap();
     ///
     /// ```
-    pub fn print<D: Display>(&mut self, value: D) -> &mut TerminalCursor {
+    pub fn print<D: Display>(&mut self, value: D) -> Result<&mut TerminalCursor> {
         {
             use std::fmt::Write;
             let mut string = String::new();
             write!(string, "{}", value).unwrap();
 
-            let mut mutex = &self.context.screen_manager;
-            {
-                let mut screen_manager = mutex.lock().unwrap();
-                screen_manager.write_string(string);
-                screen_manager.flush();
-            }
+            let mut screen_manager = self.context.screen_manager.lock().unwrap();
+            screen_manager.write_content(&string)?;
         }
-        self
+        Ok(self)
     }
 
     /// Save cursor position for recall later.
@@ -256,8 +291,8 @@ impl TerminalCursor {
     /// cursor::cursor(&context).safe_position();
     ///
     /// ```
-    pub fn save_position(&self) {
-        self.terminal_cursor.save_position();
+    pub fn save_position(&self) -> Result<()> {
+        self.terminal_cursor.save_position()
     }
 
     /// Return to saved cursor position
@@ -276,12 +311,14 @@ impl TerminalCursor {
     /// cursor(&context).reset_position();
     ///
     /// ```
-    pub fn reset_position(&self) {
-        self.terminal_cursor.reset_position();
+    pub fn reset_position(&self) -> Result<()> {
+        self.terminal_cursor.reset_position()
     }
 
     /// Hide de cursor in the console.
     ///
+    /// The cursor is shown again when the terminal is restored and hidden again when the process continues after `Terminal::suspend`.
+    ///
     /// #Example
     ///
     /// ```rust
@@ -294,8 +331,8 @@ impl TerminalCursor {
     /// cursor(&context).hide();
     ///
     /// ```
-    pub fn hide(&self) {
-        self.terminal_cursor.hide();
+    pub fn hide(&self) -> Result<()> {
+        self.terminal_cursor.hide()
     }
 
     /// Show the cursor in the console.
@@ -312,8 +349,8 @@ impl TerminalCursor {
     /// cursor(&context).show();
     ///
     /// ```
-    pub fn show(&self) {
-        self.terminal_cursor.show();
+    pub fn show(&self) -> Result<()> {
+        self.terminal_cursor.show()
     }
 
     /// Enable or disable blinking of the terminal.
@@ -334,8 +371,29 @@ impl TerminalCursor {
     /// cursor.blink(false);
     ///
     /// ```
-    pub fn blink(&self, blink: bool) {
-            self.terminal_cursor.blink(blink);
+    pub fn blink(&self, blink: bool) -> Result<()> {
+        self.terminal_cursor.blink(blink)
+    }
+
+    /// Change the shape of the cursor, like a steady bar for an insert mode.
+    ///
+    /// The default shape of the terminal is restored when the changes to the terminal are undone.
+    /// Terminals that do not support this ignore it, on the WinApi console this does nothing.
+    ///
+    /// #Example
+    ///
+    /// ```rust
+    ///
+    /// extern crate crossterm;
+    /// use self::crossterm::cursor::{cursor, CursorShape};
+    /// use self::crossterm::Context;
+    ///
+    /// let context = Context::new();
+    /// cursor(&context).set_shape(CursorShape::SteadyBar).unwrap();
+    ///
+    /// ```
+    pub fn set_shape(&self, shape: CursorShape) -> Result<()> {
+        self.terminal_cursor.set_shape(shape)
     }
 }
 
@@ -366,6 +424,6 @@ impl TerminalCursor {
 /// cursor::cursor(&context).goto(5,10);
 ///
 /// ```
-pub fn cursor(context: &Rc<Context>) -> Box<TerminalCursor> {
+pub fn cursor(context: &Arc<Context>) -> Box<TerminalCursor> {
     Box::from(TerminalCursor::new(context.clone()))
 }
\ No newline at end of file
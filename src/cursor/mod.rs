@@ -8,6 +8,7 @@
 //! so that the cursor related actions can be preformed on both unix and windows systems.
 //!
 
+pub mod commands;
 pub mod cursor;
 mod ansi_cursor;
 #[cfg(target_os = "windows")]
@@ -19,7 +20,23 @@ use self::winapi_cursor::WinApiCursor;
 
 pub use self::cursor::{cursor, TerminalCursor};
 
-use std::rc::Rc;
+use Result;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Enum that can be used for the shape of the cursor, the cursor blinks or is steady.
+///
+/// Terminals that do not support changing the shape ignore it, the WinApi console can not change the shape.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CursorShape {
+    BlinkingBlock,
+    SteadyBlock,
+    BlinkingUnderline,
+    SteadyUnderline,
+    BlinkingBar,
+    SteadyBar,
+}
 
 ///! This trait defines the actions that can be preformed with the terminal cursor.
 ///! This trait can be implemented so that an concrete implementation of the ITerminalCursor can forfill
@@ -29,27 +46,29 @@ use std::rc::Rc;
 ///!
 ///! This trait is implemented for `WINAPI` (Windows specific) and `ANSI` (Unix specific),
 ///! so that cursor related actions can be preformed on both unix and windows systems.
-pub trait ITerminalCursor {
+pub trait ITerminalCursor: Send + Sync {
     /// Goto some location (x,y) in the context.
-    fn goto(&self, x: u16, y: u16);
-    /// Get the location (x,y) of the current cusror in the context
-    fn pos(&self) -> (u16, u16);
+    fn goto(&self, x: u16, y: u16) -> Result<()>;
+    /// Get the location (x,y) of the current cusror in the context, waits at most `timeout` when the terminal has to report it.
+    fn pos(&self, timeout: Duration) -> Result<(u16, u16)>;
     /// Move cursor n times up
-    fn move_up(&self, count: u16);
+    fn move_up(&self, count: u16) -> Result<()>;
     /// Move the cursor `n` times to the right.
-    fn move_right(&self, count: u16);
+    fn move_right(&self, count: u16) -> Result<()>;
     /// Move the cursor `n` times down.
-    fn move_down(&self, count: u16);
+    fn move_down(&self, count: u16) -> Result<()>;
     /// Move the cursor `n` times left.
-    fn move_left(&self, count: u16);
+    fn move_left(&self, count: u16) -> Result<()>;
     /// Save cursor position so that its saved position can be recalled later. Note that this position is stored program based not per instance of the cursor struct.
-    fn save_position(&self);
+    fn save_position(&self) -> Result<()>;
     /// Return to saved cursor position
-    fn reset_position(&self);
+    fn reset_position(&self) -> Result<()>;
     /// Hide the terminal cursor.
-    fn hide(&self);
+    fn hide(&self) -> Result<()>;
     /// Show the terminal cursor
-    fn show(&self);
+    fn show(&self) -> Result<()>;
     /// enable or disable the blinking of the cursor.
-    fn blink(&self, blink: bool);
+    fn blink(&self, blink: bool) -> Result<()>;
+    /// Change the shape of the cursor, the shape is reset when the changes to the terminal are undone.
+    fn set_shape(&self, shape: CursorShape) -> Result<()>;
 }
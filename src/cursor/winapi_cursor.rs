@@ -2,68 +2,81 @@
 //! This module is used for windows terminals that do not support ANSI escape codes.
 //! Note that the cursor position is 0 based. This means that we start counting at 0 when setting the cursor position ect.
 use super::super::manager::{IScreenManager, ScreenManager, WinApiScreenManager};
-use super::ITerminalCursor;
+use super::{CursorShape, ITerminalCursor};
 
 use kernel::windows_kernel::{cursor, kernel};
+use Result;
 
-use std::rc::Rc;
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 /// This struct is an windows implementation for cursor related actions.
 pub struct WinApiCursor {
-    screen_manager: Rc<Mutex<ScreenManager>>,
+    screen_manager: Arc<Mutex<ScreenManager>>,
 }
 
 impl WinApiCursor {
-    pub fn new(screen_manager: Rc<Mutex<ScreenManager>>) -> Box<WinApiCursor> {
+    pub fn new(screen_manager: Arc<Mutex<ScreenManager>>) -> Box<WinApiCursor> {
         Box::from(WinApiCursor { screen_manager })
     }
 }
 
 impl ITerminalCursor for WinApiCursor {
-    fn goto(&self, x: u16, y: u16) {
+    fn goto(&self, x: u16, y: u16) -> Result<()> {
         kernel::set_console_cursor_position(x as i16, y as i16, &self.screen_manager);
+        Ok(())
     }
 
-    fn pos(&self) -> (u16, u16) {
-        cursor::pos(&self.screen_manager)
+    // The console reports the position directly, there is nothing to wait for.
+    fn pos(&self, _timeout: Duration) -> Result<(u16, u16)> {
+        Ok(cursor::pos(&self.screen_manager))
     }
 
-    fn move_up(&self, count: u16) {
-        let (xpos, ypos) = self.pos();
-        self.goto(xpos, ypos - count);
+    fn move_up(&self, count: u16) -> Result<()> {
+        let (xpos, ypos) = cursor::pos(&self.screen_manager);
+        self.goto(xpos, ypos - count)
     }
 
-    fn move_right(&self, count: u16) {
-        let (xpos, ypos) = self.pos();
-        self.goto(xpos + count, ypos);
+    fn move_right(&self, count: u16) -> Result<()> {
+        let (xpos, ypos) = cursor::pos(&self.screen_manager);
+        self.goto(xpos + count, ypos)
     }
 
-    fn move_down(&self, count: u16) {
-        let (xpos, ypos) = self.pos();
-        self.goto(xpos, ypos + count);
+    fn move_down(&self, count: u16) -> Result<()> {
+        let (xpos, ypos) = cursor::pos(&self.screen_manager);
+        self.goto(xpos, ypos + count)
     }
 
-    fn move_left(&self, count: u16) {
-        let (xpos, ypos) = self.pos();
-        self.goto(xpos - count, ypos);
+    fn move_left(&self, count: u16) -> Result<()> {
+        let (xpos, ypos) = cursor::pos(&self.screen_manager);
+        self.goto(xpos - count, ypos)
     }
 
-    fn save_position(&self) {
+    fn save_position(&self) -> Result<()> {
         cursor::save_cursor_pos(&self.screen_manager);
+        Ok(())
     }
 
-    fn reset_position(&self) {
+    fn reset_position(&self) -> Result<()> {
         cursor::reset_to_saved_position(&self.screen_manager);
+        Ok(())
     }
 
-    fn hide(&self) {
+    fn hide(&self) -> Result<()> {
         kernel::cursor_visibility(false, &self.screen_manager);
+        Ok(())
     }
 
-    fn show(&self) {
+    fn show(&self) -> Result<()> {
         kernel::cursor_visibility(true, &self.screen_manager);
+        Ok(())
     }
 
-    fn blink(&self, blink: bool) {}
+    fn blink(&self, _blink: bool) -> Result<()> {
+        Ok(())
+    }
+
+    fn set_shape(&self, _shape: CursorShape) -> Result<()> {
+        Ok(())
+    }
 }
@@ -0,0 +1,814 @@
+//! This module decodes the bytes that an ANSI compatible terminal sends when a key is pressed or the mouse is used.
+//!
+//! Printable characters are send as UTF-8 and control keys as a single control byte (`Ctrl+a` is `0x01`).
+//! Keys like the arrows, home/end and the function keys are send as escape sequences.
+//! Depending on the terminal these are `CSI` (`ESC [`) or `SS3` (`ESC O`) sequences.
+//! xterm and VTE add the held modifier keys as a parameter (`ESC [ 1 ; 5 A` is `Ctrl+Up`),
+//! rxvt uses the final character for that (`ESC [ 2 ^` is `Ctrl+Insert`)
+//! and the linux console reports `F1`-`F5` as `ESC [ [ A`-`ESC [ [ E`.
+//!
+//! Mouse events are reported in one of three encodings, depending on what the terminal supports:
+//! - X10 `ESC [ M Cb Cx Cy` where the button and coordinates are single bytes offset by 32.
+//! - urxvt `ESC [ Cb ; Cx ; Cy M` where the same values are written as decimal numbers.
+//! - SGR `ESC [ < Cb ; Cx ; Cy M` for presses and `... m` for releases, here the button is not offset.
+//!
+//! In bracketed paste mode pasted text is send between `ESC [ 200 ~` and `ESC [ 201 ~`.
+//! With focus reporting enabled the terminal sends `ESC [ I` when it gains focus and `ESC [ O` when it loses focus.
+
+use super::{Event, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+
+use std::io;
+use std::str;
+
+const ESC: u8 = 0x1B;
+
+pub const PASTE_START: &[u8] = b"\x1B[200~";
+const PASTE_END: &[u8] = b"\x1B[201~";
+
+/// Escape sequences send by terminals are short, anything longer is garbage that should be discarded.
+const MAX_SEQUENCE_LENGTH: usize = 32;
+
+/// Try to parse an event from the given bytes.
+///
+/// Returns `Ok(None)` when the bytes are the start of an event that is not complete yet.
+/// `more_input` tells whether more bytes are waiting to be read,
+/// this is used to tell a lone `Esc` apart from the start of an escape sequence.
+pub fn parse_event(buffer: &[u8], more_input: bool) -> io::Result<Option<Event>> {
+    if buffer.is_empty() {
+        return Ok(None);
+    }
+
+    let key = match buffer[0] {
+        ESC => return parse_escape(buffer, more_input),
+        b'\r' | b'\n' => KeyEvent::Enter,
+        b'\t' => KeyEvent::Tab,
+        0x7F | 0x08 => KeyEvent::Backspace,
+        0x00 => KeyEvent::Null,
+        // `Ctrl+a` till `Ctrl+z`
+        c @ 0x01..=0x1A => KeyEvent::Ctrl((c + 0x60) as char),
+        // `Ctrl+\`, `Ctrl+]`, `Ctrl+^` and `Ctrl+_`
+        c @ 0x1C..=0x1F => KeyEvent::Ctrl((c + 0x40) as char),
+        _ => match parse_utf8_char(buffer)? {
+            Some(c) => KeyEvent::Char(c),
+            None => return Ok(None),
+        },
+    };
+
+    Ok(Some(Event::Key(key)))
+}
+
+/// Parse the bytes that start with `ESC`.
+fn parse_escape(buffer: &[u8], more_input: bool) -> io::Result<Option<Event>> {
+    if buffer.len() == 1 {
+        return Ok(if more_input { None } else { Some(Event::Key(KeyEvent::Esc)) });
+    }
+
+    match buffer[1] {
+        // `ESC [` and `ESC O` on their own are `Alt+[` and `Alt+O`.
+        b'[' | b'O' if buffer.len() == 2 => Ok(if more_input {
+            None
+        } else {
+            Some(Event::Key(KeyEvent::Alt(buffer[1] as char)))
+        }),
+        b'[' => parse_csi(buffer),
+        b'O' => Ok(parse_ss3(buffer)?.map(Event::Key)),
+        // Terminals send alt + key as `ESC` followed by the key.
+        _ => Ok(parse_event(&buffer[1..], more_input)?.map(|event| match event {
+            Event::Key(key) => Event::Key(with_alt(key)),
+            event => event,
+        })),
+    }
+}
+
+/// Parse an `CSI` sequence (`ESC [ parameters final`).
+fn parse_csi(buffer: &[u8]) -> io::Result<Option<Event>> {
+    if buffer.starts_with(PASTE_START) {
+        return Ok(parse_paste(buffer));
+    }
+
+    match buffer[2] {
+        b'M' => return parse_x10_mouse(buffer),
+        b'<' => return parse_sgr_mouse(buffer),
+        _ => {}
+    }
+
+    // The linux console reports F1 - F5 as `ESC [ [ A` - `ESC [ [ E`.
+    if buffer[2] == b'[' {
+        return match buffer.get(3) {
+            None => Ok(None),
+            Some(&c @ b'A'..=b'E') => Ok(Some(Event::Key(KeyEvent::F(c - b'A' + 1)))),
+            Some(_) => Err(could_not_parse()),
+        };
+    }
+
+    let final_byte = buffer[buffer.len() - 1];
+
+    // rxvt ends modified keys with `$` (shift) which is not a final byte according to the standard.
+    if !is_final_byte(final_byte) && final_byte != b'$' {
+        if buffer.len() > MAX_SEQUENCE_LENGTH {
+            return Err(could_not_parse());
+        }
+        return Ok(None);
+    }
+
+    let params = parse_params(&buffer[2..buffer.len() - 1])?;
+    let modifiers = params.get(1).map_or(KeyModifiers::default(), |p| parse_modifiers(*p));
+
+    let key = match final_byte {
+        b'A' => KeyEvent::Up,
+        b'B' => KeyEvent::Down,
+        b'C' => KeyEvent::Right,
+        b'D' => KeyEvent::Left,
+        b'H' => KeyEvent::Home,
+        b'F' => KeyEvent::End,
+        b'P' => KeyEvent::F(1),
+        b'Q' => KeyEvent::F(2),
+        b'R' => KeyEvent::F(3),
+        b'S' => KeyEvent::F(4),
+        b'Z' => KeyEvent::BackTab,
+        b'I' if params.is_empty() => return Ok(Some(Event::FocusGained)),
+        b'O' if params.is_empty() => return Ok(Some(Event::FocusLost)),
+        b'M' if params.len() == 3 => return parse_urxvt_mouse(&params).map(Some),
+        // rxvt reports shift + arrow as `ESC [ a` - `ESC [ d`.
+        b'a'..=b'd' => {
+            return Ok(Some(Event::Key(with_modifiers(arrow_key(final_byte), shift()))))
+        }
+        b'~' | b'^' | b'$' | b'@' => {
+            let key = params
+                .first()
+                .and_then(|code| tilde_key(*code))
+                .ok_or_else(could_not_parse)?;
+
+            let modifiers = match final_byte {
+                b'^' => KeyModifiers { ctrl: true, ..KeyModifiers::default() },
+                b'$' => shift(),
+                b'@' => KeyModifiers { ctrl: true, shift: true, ..KeyModifiers::default() },
+                _ => modifiers,
+            };
+
+            return Ok(Some(Event::Key(with_modifiers(key, modifiers))));
+        }
+        _ => return Err(could_not_parse()),
+    };
+
+    Ok(Some(Event::Key(with_modifiers(key, modifiers))))
+}
+
+/// Parse pasted text (`ESC [ 200 ~ text ESC [ 201 ~`), the text is complete when the end marker is read.
+fn parse_paste(buffer: &[u8]) -> Option<Event> {
+    if buffer.len() < PASTE_START.len() + PASTE_END.len() || !buffer.ends_with(PASTE_END) {
+        return None;
+    }
+
+    let text = String::from_utf8_lossy(&buffer[PASTE_START.len()..buffer.len() - PASTE_END.len()]);
+
+    // Terminals send a newline as `\r`, like the enter key.
+    Some(Event::Paste(text.replace("\r\n", "\n").replace('\r', "\n")))
+}
+
+/// Parse an X10 encoded mouse event (`ESC [ M Cb Cx Cy`).
+fn parse_x10_mouse(buffer: &[u8]) -> io::Result<Option<Event>> {
+    if buffer.len() < 6 {
+        return Ok(None);
+    }
+
+    // The values are offset by 32 so that they are printable characters.
+    let button = u16::from(buffer[3].saturating_sub(32));
+    let x = u16::from(buffer[4].saturating_sub(32));
+    let y = u16::from(buffer[5].saturating_sub(32));
+
+    Ok(Some(parse_mouse(button, x, y, None)?))
+}
+
+/// Parse an urxvt encoded mouse event (`ESC [ Cb ; Cx ; Cy M`), the parameters are already parsed.
+fn parse_urxvt_mouse(params: &[u16]) -> io::Result<Event> {
+    parse_mouse(params[0].saturating_sub(32), params[1], params[2], None)
+}
+
+/// Parse an SGR encoded mouse event (`ESC [ < Cb ; Cx ; Cy M` or `ESC [ < Cb ; Cx ; Cy m`).
+fn parse_sgr_mouse(buffer: &[u8]) -> io::Result<Option<Event>> {
+    let final_byte = buffer[buffer.len() - 1];
+
+    if final_byte != b'M' && final_byte != b'm' {
+        if buffer.len() > MAX_SEQUENCE_LENGTH {
+            return Err(could_not_parse());
+        }
+        return Ok(None);
+    }
+
+    let params = parse_params(&buffer[3..buffer.len() - 1])?;
+
+    if params.len() != 3 {
+        return Err(could_not_parse());
+    }
+
+    Ok(Some(parse_mouse(
+        params[0],
+        params[1],
+        params[2],
+        Some(final_byte == b'm'),
+    )?))
+}
+
+/// Create a mouse event from the button value and the 1-based coordinates the terminal reported.
+///
+/// `released` is only known for the SGR encoding,
+/// the other encodings report a release as button `3` without telling which button was released.
+fn parse_mouse(button: u16, x: u16, y: u16, released: Option<bool>) -> io::Result<Event> {
+    let modifiers = KeyModifiers {
+        shift: button & 4 != 0,
+        alt: button & 8 != 0,
+        ctrl: button & 16 != 0,
+    };
+
+    let is_motion = button & 32 != 0;
+    let is_wheel = button & 64 != 0;
+
+    let mouse_button = match button & 3 {
+        0 => Some(MouseButton::Left),
+        1 => Some(MouseButton::Middle),
+        2 => Some(MouseButton::Right),
+        _ => None,
+    };
+
+    let kind = if is_wheel {
+        match button & 3 {
+            0 => MouseEventKind::ScrollUp,
+            1 => MouseEventKind::ScrollDown,
+            2 => MouseEventKind::ScrollLeft,
+            _ => MouseEventKind::ScrollRight,
+        }
+    } else if is_motion {
+        match mouse_button {
+            Some(mouse_button) => MouseEventKind::Drag(mouse_button),
+            None => MouseEventKind::Move,
+        }
+    } else if released == Some(true) {
+        MouseEventKind::Release(mouse_button)
+    } else {
+        match mouse_button {
+            Some(mouse_button) => MouseEventKind::Press(mouse_button),
+            None => MouseEventKind::Release(None),
+        }
+    };
+
+    if x == 0 || y == 0 {
+        return Err(could_not_parse());
+    }
+
+    // The terminal starts counting at 1, crossterm starts counting at 0.
+    Ok(Event::Mouse(MouseEvent {
+        kind,
+        x: x - 1,
+        y: y - 1,
+        modifiers,
+    }))
+}
+
+/// Parse an `SS3` sequence (`ESC O final`), these are send by terminals in application cursor mode.
+fn parse_ss3(buffer: &[u8]) -> io::Result<Option<KeyEvent>> {
+    let final_byte = buffer[buffer.len() - 1];
+
+    // Some terminals put the modifier between `ESC O` and the final byte, like `ESC O 5 P`.
+    if !final_byte.is_ascii_alphabetic() {
+        if buffer.len() > MAX_SEQUENCE_LENGTH {
+            return Err(could_not_parse());
+        }
+        return Ok(None);
+    }
+
+    let params = parse_params(&buffer[2..buffer.len() - 1])?;
+    let modifiers = params.last().map_or(KeyModifiers::default(), |p| parse_modifiers(*p));
+
+    let key = match final_byte {
+        b'A' => KeyEvent::Up,
+        b'B' => KeyEvent::Down,
+        b'C' => KeyEvent::Right,
+        b'D' => KeyEvent::Left,
+        b'H' => KeyEvent::Home,
+        b'F' => KeyEvent::End,
+        b'P' => KeyEvent::F(1),
+        b'Q' => KeyEvent::F(2),
+        b'R' => KeyEvent::F(3),
+        b'S' => KeyEvent::F(4),
+        b'M' => KeyEvent::Enter,
+        // rxvt reports ctrl + arrow as `ESC O a` - `ESC O d`.
+        b'a'..=b'd' => {
+            let ctrl = KeyModifiers { ctrl: true, ..KeyModifiers::default() };
+            return Ok(Some(with_modifiers(arrow_key(final_byte), ctrl)));
+        }
+        // The keypad in application mode.
+        b'j' => KeyEvent::Char('*'),
+        b'k' => KeyEvent::Char('+'),
+        b'm' => KeyEvent::Char('-'),
+        b'n' => KeyEvent::Char('.'),
+        b'o' => KeyEvent::Char('/'),
+        b'p'..=b'y' => KeyEvent::Char((final_byte - b'p' + b'0') as char),
+        _ => return Err(could_not_parse()),
+    };
+
+    Ok(Some(with_modifiers(key, modifiers)))
+}
+
+/// Parse an UTF-8 encoded character, returns `None` if more bytes are needed.
+fn parse_utf8_char(buffer: &[u8]) -> io::Result<Option<char>> {
+    match str::from_utf8(buffer) {
+        Ok(string) => Ok(string.chars().next()),
+        // The character is not complete yet.
+        Err(ref e) if e.error_len().is_none() => Ok(None),
+        Err(_) => Err(could_not_parse()),
+    }
+}
+
+/// Parse the `;` separated numeric parameters of an escape sequence, empty parameters are 0.
+fn parse_params(params: &[u8]) -> io::Result<Vec<u16>> {
+    if params.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let params = str::from_utf8(params).map_err(|_| could_not_parse())?;
+
+    params
+        .split(';')
+        .map(|param| {
+            if param.is_empty() {
+                Ok(0)
+            } else {
+                param.parse::<u16>().map_err(|_| could_not_parse())
+            }
+        }).collect()
+}
+
+/// Parse the xterm modifier parameter, this is 1 + a bitmask of the modifier keys.
+fn parse_modifiers(param: u16) -> KeyModifiers {
+    let mask = param.saturating_sub(1);
+
+    KeyModifiers {
+        shift: mask & 1 != 0,
+        // 8 is the meta key, which most users will know as alt.
+        alt: mask & 2 != 0 || mask & 8 != 0,
+        ctrl: mask & 4 != 0,
+    }
+}
+
+/// Get the key that belongs to the code of an `ESC [ code ~` sequence.
+fn tilde_key(code: u16) -> Option<KeyEvent> {
+    Some(match code {
+        1 | 7 => KeyEvent::Home,
+        2 => KeyEvent::Insert,
+        3 => KeyEvent::Delete,
+        4 | 8 => KeyEvent::End,
+        5 => KeyEvent::PageUp,
+        6 => KeyEvent::PageDown,
+        11..=15 => KeyEvent::F((code - 10) as u8),
+        17..=21 => KeyEvent::F((code - 11) as u8),
+        23..=24 => KeyEvent::F((code - 12) as u8),
+        _ => return None,
+    })
+}
+
+/// Get the arrow key that belongs to the lowercase rxvt final byte.
+fn arrow_key(final_byte: u8) -> KeyEvent {
+    match final_byte {
+        b'a' => KeyEvent::Up,
+        b'b' => KeyEvent::Down,
+        b'c' => KeyEvent::Right,
+        _ => KeyEvent::Left,
+    }
+}
+
+/// Add the given modifiers to the key.
+fn with_modifiers(key: KeyEvent, modifiers: KeyModifiers) -> KeyEvent {
+    if modifiers.is_empty() {
+        return key;
+    }
+
+    match key {
+        KeyEvent::Tab if modifiers == shift() => KeyEvent::BackTab,
+        KeyEvent::Modified(current, key) => KeyEvent::Modified(
+            KeyModifiers {
+                shift: current.shift || modifiers.shift,
+                alt: current.alt || modifiers.alt,
+                ctrl: current.ctrl || modifiers.ctrl,
+            },
+            key,
+        ),
+        key => KeyEvent::Modified(modifiers, Box::new(key)),
+    }
+}
+
+/// Add the alt modifier to the key.
+fn with_alt(key: KeyEvent) -> KeyEvent {
+    match key {
+        KeyEvent::Char(c) => KeyEvent::Alt(c),
+        key => with_modifiers(key, KeyModifiers { alt: true, ..KeyModifiers::default() }),
+    }
+}
+
+fn shift() -> KeyModifiers {
+    KeyModifiers { shift: true, ..KeyModifiers::default() }
+}
+
+/// Check if the byte ends an escape sequence.
+fn is_final_byte(byte: u8) -> bool {
+    (0x40..=0x7E).contains(&byte)
+}
+
+fn could_not_parse() -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, "Could not parse an event")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_event;
+    use input::{Event, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+
+    fn key(key: KeyEvent) -> Option<Event> {
+        Some(Event::Key(key))
+    }
+
+    fn modified(shift: bool, alt: bool, ctrl: bool, key: KeyEvent) -> Option<Event> {
+        Some(Event::Key(KeyEvent::Modified(
+            KeyModifiers { shift, alt, ctrl },
+            Box::new(key),
+        )))
+    }
+
+    fn mouse(kind: MouseEventKind, x: u16, y: u16) -> Option<Event> {
+        mouse_with(kind, x, y, KeyModifiers::default())
+    }
+
+    fn mouse_with(kind: MouseEventKind, x: u16, y: u16, modifiers: KeyModifiers) -> Option<Event> {
+        Some(Event::Mouse(MouseEvent { kind, x, y, modifiers }))
+    }
+
+    /// Parse complete input, there are no more bytes waiting.
+    fn parse(bytes: &[u8]) -> Option<Event> {
+        parse_event(bytes, false).unwrap()
+    }
+
+    #[test]
+    fn single_byte_keys() {
+        let cases: Vec<(&[u8], Option<Event>)> = vec![
+            (b"a", key(KeyEvent::Char('a'))),
+            (b"A", key(KeyEvent::Char('A'))),
+            (b"\r", key(KeyEvent::Enter)),
+            (b"\n", key(KeyEvent::Enter)),
+            (b"\t", key(KeyEvent::Tab)),
+            (b"\x7F", key(KeyEvent::Backspace)),
+            (b"\x08", key(KeyEvent::Backspace)),
+            (b"\x00", key(KeyEvent::Null)),
+            (b"\x01", key(KeyEvent::Ctrl('a'))),
+            (b"\x1A", key(KeyEvent::Ctrl('z'))),
+            (b"\x1C", key(KeyEvent::Ctrl('\\'))),
+            (b"\x1F", key(KeyEvent::Ctrl('_'))),
+            ("é".as_bytes(), key(KeyEvent::Char('é'))),
+            ("€".as_bytes(), key(KeyEvent::Char('€'))),
+        ];
+
+        for (bytes, expected) in cases {
+            assert_eq!(parse(bytes), expected, "{:?}", bytes);
+        }
+    }
+
+    #[test]
+    fn escape_and_alt_keys() {
+        let cases: Vec<(&[u8], Option<Event>)> = vec![
+            (b"\x1B", key(KeyEvent::Esc)),
+            (b"\x1Ba", key(KeyEvent::Alt('a'))),
+            (b"\x1B[", key(KeyEvent::Alt('['))),
+            (b"\x1BO", key(KeyEvent::Alt('O'))),
+            (b"\x1B\x1B[A", modified(false, true, false, KeyEvent::Up)),
+            (b"\x1B\x7F", modified(false, true, false, KeyEvent::Backspace)),
+        ];
+
+        for (bytes, expected) in cases {
+            assert_eq!(parse(bytes), expected, "{:?}", bytes);
+        }
+    }
+
+    #[test]
+    fn csi_keys() {
+        let cases: Vec<(&[u8], Option<Event>)> = vec![
+            (b"\x1B[A", key(KeyEvent::Up)),
+            (b"\x1B[B", key(KeyEvent::Down)),
+            (b"\x1B[C", key(KeyEvent::Right)),
+            (b"\x1B[D", key(KeyEvent::Left)),
+            (b"\x1B[H", key(KeyEvent::Home)),
+            (b"\x1B[F", key(KeyEvent::End)),
+            (b"\x1B[Z", key(KeyEvent::BackTab)),
+            (b"\x1B[1~", key(KeyEvent::Home)),
+            (b"\x1B[2~", key(KeyEvent::Insert)),
+            (b"\x1B[3~", key(KeyEvent::Delete)),
+            (b"\x1B[4~", key(KeyEvent::End)),
+            (b"\x1B[5~", key(KeyEvent::PageUp)),
+            (b"\x1B[6~", key(KeyEvent::PageDown)),
+            (b"\x1B[7~", key(KeyEvent::Home)),
+            (b"\x1B[8~", key(KeyEvent::End)),
+            (b"\x1B[11~", key(KeyEvent::F(1))),
+            (b"\x1B[15~", key(KeyEvent::F(5))),
+            (b"\x1B[17~", key(KeyEvent::F(6))),
+            (b"\x1B[21~", key(KeyEvent::F(10))),
+            (b"\x1B[23~", key(KeyEvent::F(11))),
+            (b"\x1B[24~", key(KeyEvent::F(12))),
+            (b"\x1B[1;5A", modified(false, false, true, KeyEvent::Up)),
+            (b"\x1B[1;2D", modified(true, false, false, KeyEvent::Left)),
+            (b"\x1B[1;3H", modified(false, true, false, KeyEvent::Home)),
+            // 9 is the meta key, which is reported as alt.
+            (b"\x1B[1;9C", modified(false, true, false, KeyEvent::Right)),
+            (b"\x1B[1;8B", modified(true, true, true, KeyEvent::Down)),
+            (b"\x1B[1;2P", modified(true, false, false, KeyEvent::F(1))),
+            (b"\x1B[3;5~", modified(false, false, true, KeyEvent::Delete)),
+            (b"\x1B[15;2~", modified(true, false, false, KeyEvent::F(5))),
+            (b"\x1B[1;1A", key(KeyEvent::Up)),
+        ];
+
+        for (bytes, expected) in cases {
+            assert_eq!(parse(bytes), expected, "{:?}", bytes);
+        }
+    }
+
+    #[test]
+    fn ss3_keys() {
+        let cases: Vec<(&[u8], Option<Event>)> = vec![
+            (b"\x1BOA", key(KeyEvent::Up)),
+            (b"\x1BOD", key(KeyEvent::Left)),
+            (b"\x1BOH", key(KeyEvent::Home)),
+            (b"\x1BOF", key(KeyEvent::End)),
+            (b"\x1BOP", key(KeyEvent::F(1))),
+            (b"\x1BOS", key(KeyEvent::F(4))),
+            (b"\x1BOM", key(KeyEvent::Enter)),
+            (b"\x1BO5P", modified(false, false, true, KeyEvent::F(1))),
+            (b"\x1BO1;2Q", modified(true, false, false, KeyEvent::F(2))),
+            (b"\x1BOj", key(KeyEvent::Char('*'))),
+            (b"\x1BOk", key(KeyEvent::Char('+'))),
+            (b"\x1BOm", key(KeyEvent::Char('-'))),
+            (b"\x1BOn", key(KeyEvent::Char('.'))),
+            (b"\x1BOo", key(KeyEvent::Char('/'))),
+            (b"\x1BOp", key(KeyEvent::Char('0'))),
+            (b"\x1BOy", key(KeyEvent::Char('9'))),
+        ];
+
+        for (bytes, expected) in cases {
+            assert_eq!(parse(bytes), expected, "{:?}", bytes);
+        }
+    }
+
+    #[test]
+    fn rxvt_and_linux_console_keys() {
+        let cases: Vec<(&[u8], Option<Event>)> = vec![
+            (b"\x1B[2^", modified(false, false, true, KeyEvent::Insert)),
+            (b"\x1B[3$", modified(true, false, false, KeyEvent::Delete)),
+            (b"\x1B[5@", modified(true, false, true, KeyEvent::PageUp)),
+            (b"\x1B[11^", modified(false, false, true, KeyEvent::F(1))),
+            (b"\x1B[a", modified(true, false, false, KeyEvent::Up)),
+            (b"\x1B[d", modified(true, false, false, KeyEvent::Left)),
+            (b"\x1BOb", modified(false, false, true, KeyEvent::Down)),
+            (b"\x1BOc", modified(false, false, true, KeyEvent::Right)),
+            (b"\x1B[[A", key(KeyEvent::F(1))),
+            (b"\x1B[[E", key(KeyEvent::F(5))),
+        ];
+
+        for (bytes, expected) in cases {
+            assert_eq!(parse(bytes), expected, "{:?}", bytes);
+        }
+    }
+
+    #[test]
+    fn incomplete_input_needs_more_bytes() {
+        let cases: Vec<&[u8]> = vec![
+            b"",
+            b"\x1B[1",
+            b"\x1B[1;",
+            b"\x1B[1;5",
+            b"\x1B[15",
+            b"\x1B[[",
+            b"\x1BO5",
+            &[0xC3],
+            &[0xE2, 0x82],
+        ];
+
+        for bytes in cases {
+            assert_eq!(parse_event(bytes, false).unwrap(), None, "{:?}", bytes);
+        }
+    }
+
+    #[test]
+    fn escape_waits_for_more_input() {
+        assert_eq!(parse_event(b"\x1B", true).unwrap(), None);
+        assert_eq!(parse_event(b"\x1B[", true).unwrap(), None);
+        assert_eq!(parse_event(b"\x1BO", true).unwrap(), None);
+    }
+
+    #[test]
+    fn invalid_input_is_an_error() {
+        let cases: Vec<&[u8]> = vec![
+            // Unknown codes and final bytes.
+            b"\x1B[99~",
+            b"\x1B[1;5X",
+            b"\x1B[[x",
+            b"\x1BOz",
+            // Parameters that are not numbers.
+            b"\x1B[1;x~",
+            b"\x1B[99999~",
+            // Invalid UTF-8.
+            &[0xFF],
+            &[0xC3, 0x28],
+        ];
+
+        for bytes in cases {
+            assert!(parse_event(bytes, false).is_err(), "{:?}", bytes);
+        }
+    }
+
+    #[test]
+    fn long_sequence_without_final_byte_is_an_error() {
+        let mut bytes = b"\x1B[".to_vec();
+        bytes.extend(vec![b'1'; 40]);
+
+        assert!(parse_event(&bytes, false).is_err());
+        assert!(parse_event(&bytes, true).is_err());
+    }
+
+    #[test]
+    fn x10_mouse() {
+        use self::MouseButton::*;
+        use self::MouseEventKind::*;
+
+        // The button and the coordinates are offset by 32, the coordinates are 1-based.
+        let cases: Vec<(&[u8], Option<Event>)> = vec![
+            (b"\x1B[M !!", mouse(Press(Left), 0, 0)),
+            (b"\x1B[M!*%", mouse(Press(Middle), 9, 4)),
+            (b"\x1B[M\"!!", mouse(Press(Right), 0, 0)),
+            (b"\x1B[M#!!", mouse(Release(None), 0, 0)),
+            (b"\x1B[M@+,", mouse(Drag(Left), 10, 11)),
+            (b"\x1B[MC!!", mouse(Move, 0, 0)),
+            (b"\x1B[M`!!", mouse(ScrollUp, 0, 0)),
+            (b"\x1B[Ma!!", mouse(ScrollDown, 0, 0)),
+            (
+                b"\x1B[M0!!",
+                mouse_with(Press(Left), 0, 0, KeyModifiers { ctrl: true, ..KeyModifiers::default() }),
+            ),
+            (
+                b"\x1B[M(!!",
+                mouse_with(Press(Left), 0, 0, KeyModifiers { alt: true, ..KeyModifiers::default() }),
+            ),
+            // The largest coordinate X10 can encode.
+            (&[0x1B, b'[', b'M', 32, 255, 255], mouse(Press(Left), 222, 222)),
+        ];
+
+        for (bytes, expected) in cases {
+            assert_eq!(parse(bytes), expected, "{:?}", bytes);
+        }
+
+        assert_eq!(parse(b"\x1B[M"), None);
+        assert_eq!(parse(b"\x1B[M !"), None);
+        // A coordinate of 0 is outside the screen.
+        assert!(parse_event(b"\x1B[M   ", false).is_err());
+    }
+
+    #[test]
+    fn urxvt_mouse() {
+        use self::MouseButton::*;
+        use self::MouseEventKind::*;
+
+        let cases: Vec<(&[u8], Option<Event>)> = vec![
+            (b"\x1B[32;10;5M", mouse(Press(Left), 9, 4)),
+            (b"\x1B[34;1;1M", mouse(Press(Right), 0, 0)),
+            (b"\x1B[35;1;1M", mouse(Release(None), 0, 0)),
+            (b"\x1B[64;300;250M", mouse(Drag(Left), 299, 249)),
+            (b"\x1B[96;1;1M", mouse(ScrollUp, 0, 0)),
+            (b"\x1B[97;1;1M", mouse(ScrollDown, 0, 0)),
+            (
+                b"\x1B[36;1;1M",
+                mouse_with(Press(Left), 0, 0, KeyModifiers { shift: true, ..KeyModifiers::default() }),
+            ),
+        ];
+
+        for (bytes, expected) in cases {
+            assert_eq!(parse(bytes), expected, "{:?}", bytes);
+        }
+
+        assert_eq!(parse(b"\x1B[32;10;5"), None);
+        assert!(parse_event(b"\x1B[32;0;5M", false).is_err());
+    }
+
+    #[test]
+    fn sgr_mouse() {
+        use self::MouseButton::*;
+        use self::MouseEventKind::*;
+
+        let cases: Vec<(&[u8], Option<Event>)> = vec![
+            (b"\x1B[<0;10;5M", mouse(Press(Left), 9, 4)),
+            (b"\x1B[<1;10;5M", mouse(Press(Middle), 9, 4)),
+            (b"\x1B[<2;10;5M", mouse(Press(Right), 9, 4)),
+            // SGR reports which button is released.
+            (b"\x1B[<0;10;5m", mouse(Release(Some(Left)), 9, 4)),
+            (b"\x1B[<2;10;5m", mouse(Release(Some(Right)), 9, 4)),
+            (b"\x1B[<32;3;4M", mouse(Drag(Left), 2, 3)),
+            (b"\x1B[<34;3;4M", mouse(Drag(Right), 2, 3)),
+            (b"\x1B[<35;3;4M", mouse(Move, 2, 3)),
+            (b"\x1B[<64;1;1M", mouse(ScrollUp, 0, 0)),
+            (b"\x1B[<65;1;1M", mouse(ScrollDown, 0, 0)),
+            (b"\x1B[<66;1;1M", mouse(ScrollLeft, 0, 0)),
+            (b"\x1B[<67;1;1M", mouse(ScrollRight, 0, 0)),
+            // Coordinates above 223 can not be encoded by X10, but can by SGR.
+            (b"\x1B[<0;224;224M", mouse(Press(Left), 223, 223)),
+            (b"\x1B[<0;300;1000M", mouse(Press(Left), 299, 999)),
+            (
+                b"\x1B[<20;1;1M",
+                mouse_with(
+                    Press(Left),
+                    0,
+                    0,
+                    KeyModifiers { shift: true, ctrl: true, ..KeyModifiers::default() },
+                ),
+            ),
+            (
+                b"\x1B[<72;1;1M",
+                mouse_with(ScrollUp, 0, 0, KeyModifiers { alt: true, ..KeyModifiers::default() }),
+            ),
+        ];
+
+        for (bytes, expected) in cases {
+            assert_eq!(parse(bytes), expected, "{:?}", bytes);
+        }
+    }
+
+    #[test]
+    fn incomplete_and_invalid_sgr_mouse() {
+        assert_eq!(parse(b"\x1B[<"), None);
+        assert_eq!(parse(b"\x1B[<0;10"), None);
+        assert_eq!(parse(b"\x1B[<0;10;5"), None);
+
+        let cases: Vec<&[u8]> = vec![
+            b"\x1B[<0;10M",
+            b"\x1B[<0;10;5;1M",
+            b"\x1B[<0;0;5M",
+            b"\x1B[<0;x;5M",
+        ];
+
+        for bytes in cases {
+            assert!(parse_event(bytes, false).is_err(), "{:?}", bytes);
+        }
+    }
+
+    #[test]
+    fn bracketed_paste() {
+        assert_eq!(
+            parse(b"\x1B[200~hello\r\nworld\rbye\x1B[201~"),
+            Some(Event::Paste("hello\nworld\nbye".to_string()))
+        );
+        assert_eq!(parse(b"\x1B[200~\x1B[201~"), Some(Event::Paste(String::new())));
+        // Escape sequences in the pasted text are not decoded.
+        assert_eq!(
+            parse(b"\x1B[200~\x1B[A\x1B[201~"),
+            Some(Event::Paste("\x1B[A".to_string()))
+        );
+    }
+
+    #[test]
+    fn paste_split_across_reads() {
+        let paste = "\x1B[200~lorem ipsum dolor\x1B[201~".as_bytes();
+
+        // Every prefix after `ESC [` waits for more bytes, also when no more bytes are available yet.
+        // A lone `ESC` or `ESC [` without more bytes waiting are the keys `Esc` and `Alt+[`.
+        for end in 3..paste.len() {
+            assert_eq!(parse_event(&paste[..end], false).unwrap(), None, "{:?}", &paste[..end]);
+            assert_eq!(parse_event(&paste[..end], true).unwrap(), None, "{:?}", &paste[..end]);
+        }
+
+        assert_eq!(parse(paste), Some(Event::Paste("lorem ipsum dolor".to_string())));
+    }
+
+    #[test]
+    fn paste_end_marker_in_a_later_chunk() {
+        let mut buffer = b"\x1B[200~first chunk".to_vec();
+        assert_eq!(parse(&buffer), None);
+
+        buffer.extend_from_slice(b" second chunk\x1B[20");
+        assert_eq!(parse(&buffer), None);
+
+        buffer.extend_from_slice(b"1~");
+        assert_eq!(
+            parse(&buffer),
+            Some(Event::Paste("first chunk second chunk".to_string()))
+        );
+    }
+
+    #[test]
+    fn paste_is_not_limited_to_the_sequence_length() {
+        let mut buffer = b"\x1B[200~".to_vec();
+        buffer.extend(vec![b'x'; 1000]);
+        assert_eq!(parse(&buffer), None);
+
+        buffer.extend_from_slice(b"\x1B[201~");
+        assert_eq!(parse(&buffer), Some(Event::Paste("x".repeat(1000))));
+    }
+
+    #[test]
+    fn focus_in_and_out() {
+        assert_eq!(parse(b"\x1B[I"), Some(Event::FocusGained));
+        assert_eq!(parse(b"\x1B[O"), Some(Event::FocusLost));
+    }
+}
@@ -0,0 +1,70 @@
+//! This module contains the logic for reporting focus changes.
+//!
+//! When focus reporting is enabled the terminal tells the program when its window gains or loses focus.
+//! These changes are read as `Event::FocusGained` and `Event::FocusLost` with `TerminalInput::read()`.
+//!
+//! Focus reporting is registered as state change at the `Context`,
+//! so it will be disabled when the `FocusReporting` goes out of scope or when the changes of the `Context` are restored.
+//!
+//!     let context = crossterm::Context::new();
+//!     // enable focus reporting, this is disabled again when `focus` goes out of scope.
+//!     let focus = crossterm::input::FocusReporting::from(context.clone()).unwrap();
+
+#[cfg(target_os = "windows")]
+use state::commands::shared_commands::EmptyCommand;
+#[cfg(not(target_os = "windows"))]
+use state::commands::shared_commands::EnableFocusReportingCommand;
+use {CommandManager, Context, Result};
+
+use std::sync::Arc;
+
+/// A handle to focus reporting, while it is alive focus changes are reported to the program.
+pub struct FocusReporting {
+    context: Arc<Context>,
+    command_id: u32,
+}
+
+impl FocusReporting {
+    /// Enable focus reporting for the given context.
+    pub fn from(context: Arc<Context>) -> Result<Self> {
+        let command_id = get_focus_reporting_command(context.clone());
+
+        let focus_reporting = FocusReporting {
+            context: context.clone(),
+            command_id,
+        };
+        focus_reporting.enable()?;
+        Ok(focus_reporting)
+    }
+
+    /// Start reporting focus changes.
+    pub fn enable(&self) -> Result<()> {
+        CommandManager::execute(self.context.clone(), self.command_id)
+    }
+
+    /// Stop reporting focus changes.
+    pub fn disable(&self) -> Result<()> {
+        CommandManager::undo(self.context.clone(), self.command_id)
+    }
+}
+
+impl Drop for FocusReporting {
+    fn drop(&mut self) {
+        let _ = CommandManager::remove(self.context.clone(), self.command_id);
+    }
+}
+
+// Get the command to enable and disable focus reporting based on the current platform.
+fn get_focus_reporting_command(context: Arc<Context>) -> u32 {
+    // Focus reporting is not supported for the windows console.
+    #[cfg(target_os = "windows")]
+    let command_id = {
+        let mut state = context.state_manager.lock().unwrap();
+        state.register_change(Box::from(EmptyCommand))
+    };
+
+    #[cfg(not(target_os = "windows"))]
+    let command_id = EnableFocusReportingCommand::new(&context);
+
+    command_id
+}
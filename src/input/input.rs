@@ -0,0 +1,206 @@
+//! With this module you can perform actions that are input related.
+//! Like reading the keys that are pressed in the terminal.
+
+use super::*;
+use {Context, Result};
+
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Struct that stores an specific platform implementation for input related actions.
+pub struct TerminalInput {
+    context: Arc<Context>,
+    terminal_input: Box<ITerminalInput>,
+}
+
+impl TerminalInput {
+    /// Create new input instance whereon input related actions can be performed.
+    pub fn new(context: Arc<Context>) -> TerminalInput {
+        #[cfg(target_os = "windows")]
+        let input = Box::from(WindowsInput::new(context.clone())) as Box<ITerminalInput>;
+
+        #[cfg(not(target_os = "windows"))]
+        let input = Box::from(UnixInput::new(context.clone())) as Box<ITerminalInput>;
+
+        TerminalInput {
+            terminal_input: input,
+            context,
+        }
+    }
+
+    /// Read the next key that is pressed in the terminal.
+    ///
+    /// This call blocks until a key is pressed. Put the terminal into raw mode first,
+    /// otherwise the terminal will only pass the input through after enter is pressed.
+    ///
+    /// #Example
+    ///
+    /// ```rust,no_run
+    ///
+    /// extern crate crossterm;
+    /// use self::crossterm::Context;
+    /// use self::crossterm::raw::IntoRawMode;
+    /// use self::crossterm::input::{input, KeyEvent};
+    ///
+    /// let context = Context::new();
+    /// let _raw = ::std::io::stdout().into_raw_mode(context.clone()).unwrap();
+    ///
+    /// loop {
+    ///     match input(&context).read_key() {
+    ///         Ok(KeyEvent::Char('q')) | Ok(KeyEvent::Ctrl('c')) => break,
+    ///         Ok(key) => println!("{:?}\r", key),
+    ///         Err(_) => break,
+    ///     }
+    /// }
+    ///
+    /// ```
+    pub fn read_key(&self) -> Result<KeyEvent> {
+        loop {
+            if let Event::Key(key) = self.terminal_input.read()? {
+                return Ok(key);
+            }
+        }
+    }
+
+    /// Wait until an event is available or the timeout expires.
+    ///
+    /// Returns `true` when an event is available, this event can be read with `read()` without blocking.
+    /// This can be used in a game loop that needs to keep running when no key is pressed.
+    ///
+    /// #Example
+    ///
+    /// ```rust,no_run
+    ///
+    /// extern crate crossterm;
+    /// use self::crossterm::Context;
+    /// use self::crossterm::input::{input, Event, KeyEvent};
+    ///
+    /// use std::time::Duration;
+    ///
+    /// let context = Context::new();
+    /// let input = input(&context);
+    ///
+    /// loop {
+    ///     // wait at most 100 milliseconds for an event.
+    ///     if input.poll(Duration::from_millis(100)).unwrap() {
+    ///         if let Ok(Event::Key(KeyEvent::Esc)) = input.read() {
+    ///             break;
+    ///         }
+    ///     }
+    ///
+    ///     // update the game.
+    /// }
+    ///
+    /// ```
+    pub fn poll(&self, timeout: Duration) -> Result<bool> {
+        self.terminal_input.poll(timeout)
+    }
+
+    /// Read the next event, like a key press or a mouse event.
+    ///
+    /// This call blocks until an event is available, use `poll()` to wait with a timeout.
+    /// Mouse events are only reported when the mouse capture is enabled with `enable_mouse_capture()`.
+    ///
+    /// #Example
+    ///
+    /// ```rust,no_run
+    ///
+    /// extern crate crossterm;
+    /// use self::crossterm::Context;
+    /// use self::crossterm::raw::IntoRawMode;
+    /// use self::crossterm::input::{input, Event, KeyEvent, MouseEventKind};
+    ///
+    /// let context = Context::new();
+    /// let _raw = ::std::io::stdout().into_raw_mode(context.clone()).unwrap();
+    ///
+    /// let input = input(&context);
+    /// let _mouse = input.enable_mouse_capture();
+    ///
+    /// loop {
+    ///     match input.read() {
+    ///         Ok(Event::Key(KeyEvent::Char('q'))) => break,
+    ///         Ok(Event::Mouse(mouse)) => {
+    ///             if let MouseEventKind::Press(button) = mouse.kind {
+    ///                 println!("{:?} clicked at {}, {}\r", button, mouse.x, mouse.y);
+    ///             }
+    ///         }
+    ///         Ok(_) => {}
+    ///         Err(_) => break,
+    ///     }
+    /// }
+    ///
+    /// ```
+    pub fn read(&self) -> Result<Event> {
+        self.terminal_input.read()
+    }
+
+    /// Enable the reporting of mouse events, these can be read with `read()`.
+    ///
+    /// The mouse capture will be disabled again when the returned `MouseCapture` goes out of scope.
+    pub fn enable_mouse_capture(&self) -> Result<MouseCapture> {
+        MouseCapture::from(self.context.clone())
+    }
+
+    /// Enable bracketed paste mode, pasted text is then read as a single `Event::Paste` with `read()`.
+    ///
+    /// Bracketed paste mode will be disabled again when the returned `BracketedPaste` goes out of scope.
+    ///
+    /// #Example
+    ///
+    /// ```rust,no_run
+    ///
+    /// extern crate crossterm;
+    /// use self::crossterm::Context;
+    /// use self::crossterm::raw::IntoRawMode;
+    /// use self::crossterm::input::{input, Event, KeyEvent};
+    ///
+    /// let context = Context::new();
+    /// let _raw = ::std::io::stdout().into_raw_mode(context.clone()).unwrap();
+    ///
+    /// let input = input(&context);
+    /// let _paste = input.enable_bracketed_paste().unwrap();
+    ///
+    /// loop {
+    ///     match input.read() {
+    ///         Ok(Event::Key(KeyEvent::Enter)) => break,
+    ///         Ok(Event::Paste(text)) => println!("pasted {} characters\r", text.chars().count()),
+    ///         Ok(_) => {}
+    ///         Err(_) => break,
+    ///     }
+    /// }
+    ///
+    /// ```
+    pub fn enable_bracketed_paste(&self) -> Result<BracketedPaste> {
+        BracketedPaste::from(self.context.clone())
+    }
+
+    /// Enable focus reporting, `read()` then returns `Event::FocusGained` and `Event::FocusLost`
+    /// when the terminal window gains or loses focus.
+    ///
+    /// Focus reporting will be disabled again when the returned `FocusReporting` goes out of scope.
+    pub fn enable_focus_reporting(&self) -> Result<FocusReporting> {
+        FocusReporting::from(self.context.clone())
+    }
+}
+
+/// Get an TerminalInput implementation whereon input related actions can be performed.
+///
+/// Check `/examples/version/input` in the library for more specific examples.
+///
+/// #Example
+///
+/// ```rust,no_run
+///
+/// extern crate crossterm;
+/// use self::crossterm::Context;
+/// use self::crossterm::input;
+///
+/// let context = Context::new();
+///
+/// // Get input and wait for a key.
+/// let key = input::input(&context).read_key();
+///
+/// ```
+pub fn input(context: &Arc<Context>) -> Box<TerminalInput> {
+    Box::from(TerminalInput::new(context.clone()))
+}
@@ -0,0 +1,181 @@
+//! With this module you can perform actions that are input related.
+//! Like reading the keys that are pressed by the user of the terminal and the mouse events.
+//!
+//! Note that the terminal should be in raw mode (see `IntoRawMode`) for reading keys,
+//! otherwise the terminal will buffer the input until the user presses enter.
+//! Mouse events are only reported after enabling the mouse capture (see `MouseCapture`)
+//! and pasted text is only reported as a whole after enabling bracketed paste mode (see `BracketedPaste`).
+//! Focus changes of the terminal window are reported after enabling focus reporting (see `FocusReporting`).
+//!
+//! With `poll` you can wait for an event for a limited time, so that an game loop or dashboard
+//! can keep running when the user does not press anything:
+//!
+//! ```rust,no_run
+//! extern crate crossterm;
+//! use crossterm::input::{input, Event, KeyEvent};
+//! use crossterm::Context;
+//!
+//! use std::time::Duration;
+//!
+//! let context = Context::new();
+//! let input = input(&context);
+//!
+//! loop {
+//!     if input.poll(Duration::from_millis(100)).unwrap() {
+//!         match input.read().unwrap() {
+//!             Event::Key(KeyEvent::Esc) => break,
+//!             event => println!("{:?}", event),
+//!         }
+//!     }
+//!     // update the screen.
+//! }
+//! ```
+
+pub mod focus;
+pub mod input;
+pub mod mouse;
+pub mod paste;
+#[cfg(not(target_os = "windows"))]
+pub mod query;
+
+mod ansi_parser;
+#[cfg(not(target_os = "windows"))]
+mod unix_input;
+#[cfg(target_os = "windows")]
+mod windows_input;
+
+#[cfg(not(target_os = "windows"))]
+use self::unix_input::UnixInput;
+#[cfg(target_os = "windows")]
+use self::windows_input::WindowsInput;
+
+pub use self::focus::FocusReporting;
+pub use self::input::{input, TerminalInput};
+pub use self::mouse::MouseCapture;
+pub use self::paste::BracketedPaste;
+
+use Result;
+
+use std::io;
+use std::time::Duration;
+
+/// Enum that represents the keys that can be read from the terminal.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum KeyEvent {
+    /// A printable character, shifted characters like `A` are reported as is.
+    Char(char),
+    /// A character that is pressed together with the control key, like `Ctrl+c`.
+    Ctrl(char),
+    /// A character that is pressed together with the alt key, like `Alt+x`.
+    Alt(char),
+    Enter,
+    Tab,
+    /// Shift + tab
+    BackTab,
+    Backspace,
+    Esc,
+    Left,
+    Right,
+    Up,
+    Down,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    Insert,
+    Delete,
+    /// Function key `F1` till `F12`.
+    F(u8),
+    /// Ctrl + space or Ctrl + @.
+    Null,
+    /// A key pressed together with one or more modifier keys, like `Ctrl+Left` or `Shift+F5`.
+    Modified(KeyModifiers, Box<KeyEvent>),
+}
+
+/// The modifier keys that were held down while a key was pressed.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct KeyModifiers {
+    pub shift: bool,
+    pub alt: bool,
+    pub ctrl: bool,
+}
+
+impl KeyModifiers {
+    /// Check whether no modifier key is held down.
+    pub fn is_empty(&self) -> bool {
+        !(self.shift || self.alt || self.ctrl)
+    }
+}
+
+/// Enum that represents the events that can be read from the terminal.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Event {
+    /// A key is pressed.
+    Key(KeyEvent),
+    /// The mouse is used, this is only reported when the mouse capture is enabled.
+    Mouse(MouseEvent),
+    /// The terminal is resized, contains the new size in the same form as `Terminal::terminal_size()` returns it.
+    ///
+    /// The windows console only reports a resize when the `ENABLE_WINDOW_INPUT` console input mode is set.
+    Resize(u16, u16),
+    /// Text is pasted, this is only reported when bracketed paste mode is enabled.
+    /// Newlines in the pasted text are always `\n`.
+    Paste(String),
+    /// The terminal window gained focus, this is only reported when focus reporting is enabled.
+    ///
+    /// The windows console always reports focus changes.
+    FocusGained,
+    /// The terminal window lost focus, this is only reported when focus reporting is enabled.
+    FocusLost,
+    /// Bytes that are not a known event, like an escape sequence of a key crossterm does not know.
+    Unknown(Vec<u8>),
+}
+
+/// A mouse event, the position is 0-based just like the position used by `TerminalCursor::goto`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct MouseEvent {
+    pub kind: MouseEventKind,
+    pub x: u16,
+    pub y: u16,
+    /// The modifier keys that were held down, note that most terminals use shift + mouse for their own text selection.
+    pub modifiers: KeyModifiers,
+}
+
+/// Enum that represents what happened with the mouse.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum MouseEventKind {
+    Press(MouseButton),
+    /// A button is released, not all terminals report which button was released.
+    Release(Option<MouseButton>),
+    /// The mouse is moved while a button is held down.
+    Drag(MouseButton),
+    /// The mouse is moved without holding a button.
+    Move,
+    ScrollUp,
+    ScrollDown,
+    ScrollLeft,
+    ScrollRight,
+}
+
+/// Enum that represents the mouse buttons.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum MouseButton {
+    Left,
+    Right,
+    Middle,
+}
+
+///! This trait defines the actions that can be preformed with the terminal input.
+///! This trait can be implemented so that an concrete implementation of the ITerminalInput can forfill
+///! the wishes to work on an specific platform.
+///!
+///! ## For example:
+///!
+///! This trait is implemented for `WINAPI` (Windows specific) and `ANSI` (Unix specific),
+///! so that input related actions can be preformed on both unix and windows systems.
+pub trait ITerminalInput: Send + Sync {
+    /// Wait until an event is available or the timeout expires, returns whether an event is available.
+    fn poll(&self, timeout: Duration) -> Result<bool>;
+    /// Read the next event, this call blocks until an event is available.
+    fn read(&self) -> Result<Event>;
+}
@@ -0,0 +1,68 @@
+//! This module contains the logic for capturing the mouse.
+//!
+//! When the mouse is captured the terminal reports the mouse events to the program instead of using them for text selection.
+//! These events can be read with `TerminalInput::read()`.
+//!
+//! The mouse capture is registered as state change at the `Context`,
+//! so it will be disabled when the `MouseCapture` goes out of scope or when the changes of the `Context` are restored.
+//!
+//!     let context = crossterm::Context::new();
+//!     // enable mouse capture, this is disabled again when `mouse` goes out of scope.
+//!     let mouse = crossterm::input::MouseCapture::from(context.clone()).unwrap();
+
+#[cfg(target_os = "windows")]
+use shared::functions;
+use state::commands::*;
+use {CommandManager, Context, Result};
+
+use std::sync::Arc;
+
+/// A handle to the mouse capture, while it is alive the mouse events are reported to the program.
+pub struct MouseCapture {
+    context: Arc<Context>,
+    command_id: u32,
+}
+
+impl MouseCapture {
+    /// Enable the mouse capture for the given context.
+    pub fn from(context: Arc<Context>) -> Result<Self> {
+        let command_id = get_mouse_capture_command(context.clone());
+
+        let mouse_capture = MouseCapture {
+            context: context.clone(),
+            command_id,
+        };
+        mouse_capture.enable()?;
+        Ok(mouse_capture)
+    }
+
+    /// Start reporting mouse events.
+    pub fn enable(&self) -> Result<()> {
+        CommandManager::execute(self.context.clone(), self.command_id)
+    }
+
+    /// Stop reporting mouse events.
+    pub fn disable(&self) -> Result<()> {
+        CommandManager::undo(self.context.clone(), self.command_id)
+    }
+}
+
+impl Drop for MouseCapture {
+    fn drop(&mut self) {
+        let _ = CommandManager::remove(self.context.clone(), self.command_id);
+    }
+}
+
+// Get the mouse capture command to enable and disable the mouse events based on the current platform
+fn get_mouse_capture_command(context: Arc<Context>) -> u32 {
+    #[cfg(target_os = "windows")]
+    let command_id = functions::get_module::<u32>(
+        win_commands::EnableMouseCaptureCommand::new(&context),
+        shared_commands::EnableMouseCaptureCommand::new(&context),
+    ).unwrap();
+
+    #[cfg(not(target_os = "windows"))]
+    let command_id = shared_commands::EnableMouseCaptureCommand::new(&context);
+
+    command_id
+}
@@ -0,0 +1,72 @@
+//! This module contains the logic for bracketed paste mode.
+//!
+//! Without bracketed paste mode the terminal sends pasted text as if it was typed,
+//! so every newline in the pasted text is read as a press of enter.
+//! In bracketed paste mode the terminal marks the start and the end of the pasted text,
+//! the pasted text is then read as a single `Event::Paste` with `TerminalInput::read()`.
+//!
+//! Bracketed paste mode is registered as state change at the `Context`,
+//! so it will be disabled when the `BracketedPaste` goes out of scope or when the changes of the `Context` are restored.
+//!
+//!     let context = crossterm::Context::new();
+//!     // enable bracketed paste mode, this is disabled again when `paste` goes out of scope.
+//!     let paste = crossterm::input::BracketedPaste::from(context.clone()).unwrap();
+
+#[cfg(target_os = "windows")]
+use state::commands::shared_commands::EmptyCommand;
+#[cfg(not(target_os = "windows"))]
+use state::commands::shared_commands::EnableBracketedPasteCommand;
+use {CommandManager, Context, Result};
+
+use std::sync::Arc;
+
+/// A handle to bracketed paste mode, while it is alive pasted text is reported as `Event::Paste`.
+pub struct BracketedPaste {
+    context: Arc<Context>,
+    command_id: u32,
+}
+
+impl BracketedPaste {
+    /// Enable bracketed paste mode for the given context.
+    pub fn from(context: Arc<Context>) -> Result<Self> {
+        let command_id = get_bracketed_paste_command(context.clone());
+
+        let bracketed_paste = BracketedPaste {
+            context: context.clone(),
+            command_id,
+        };
+        bracketed_paste.enable()?;
+        Ok(bracketed_paste)
+    }
+
+    /// Start reporting pasted text as `Event::Paste`.
+    pub fn enable(&self) -> Result<()> {
+        CommandManager::execute(self.context.clone(), self.command_id)
+    }
+
+    /// Stop reporting pasted text as `Event::Paste`, pasted text is read as typed keys again.
+    pub fn disable(&self) -> Result<()> {
+        CommandManager::undo(self.context.clone(), self.command_id)
+    }
+}
+
+impl Drop for BracketedPaste {
+    fn drop(&mut self) {
+        let _ = CommandManager::remove(self.context.clone(), self.command_id);
+    }
+}
+
+// Get the command to enable and disable bracketed paste mode based on the current platform.
+fn get_bracketed_paste_command(context: Arc<Context>) -> u32 {
+    // The windows console does not mark pasted text, so there is nothing to enable.
+    #[cfg(target_os = "windows")]
+    let command_id = {
+        let mut state = context.state_manager.lock().unwrap();
+        state.register_change(Box::from(EmptyCommand))
+    };
+
+    #[cfg(not(target_os = "windows"))]
+    let command_id = EnableBracketedPasteCommand::new(&context);
+
+    command_id
+}
@@ -0,0 +1,196 @@
+//! This module contains the logic for querying the terminal.
+//!
+//! Some information, like the cursor position, can only be asked by writing an escape sequence to the terminal,
+//! the terminal then answers with an other escape sequence on the input.
+//! The answer could arrive between keys the user was typing, the keys that are read while waiting for the answer
+//! are decoded and queued so that they are still returned by `TerminalInput::read()`.
+//!
+//! Note that the answer is read from the same input as the events,
+//! it can not be received while an other thread is reading the input with `TerminalInput`.
+
+use super::ansi_parser;
+use super::Event;
+use kernel::unix_kernel::input as kernel_input;
+use kernel::unix_kernel::terminal;
+use state::commands::NoncanonicalModeCommand;
+use {CommandManager, Context, Error, ErrorKind, Result};
+
+use std::mem;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+const ESC: u8 = 0x1B;
+
+/// Tells whether the bytes that are read so far are the answer to a query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Response<T> {
+    /// The bytes are the complete answer.
+    Complete(T),
+    /// The bytes are the start of the answer, more bytes are needed.
+    Incomplete,
+    /// The bytes are not the answer, they are input of the user.
+    NoMatch,
+}
+
+/// Write the given request to the terminal and wait at most `timeout` for the answer.
+///
+/// `parse` is called with the bytes that are read so far and decides whether these are the answer.
+/// The input that is not the answer is decoded into events that are queued for `TerminalInput::read()`.
+/// The terminal is put in noncanonical mode while waiting, so that the answer does not wait for enter and is not printed.
+///
+/// Returns an error of the kind `ErrorKind::Timeout` when the terminal did not answer in time,
+/// for example because the terminal does not support the request.
+///
+/// #Example
+///
+/// ```rust,no_run
+///
+/// extern crate crossterm;
+/// use crossterm::input::query::{query, Response};
+/// use crossterm::Context;
+///
+/// use std::time::Duration;
+///
+/// let context = Context::new();
+///
+/// // Ask whether the terminal is ready, it answers with `ESC [ 0 n`.
+/// let ready = query(&context, "\x1B[5n", Duration::from_millis(500), |bytes| {
+///     let answer = b"\x1B[0n";
+///
+///     if bytes == answer {
+///         Response::Complete(true)
+///     } else if answer.starts_with(bytes) {
+///         Response::Incomplete
+///     } else {
+///         Response::NoMatch
+///     }
+/// });
+///
+/// ```
+pub fn query<T, F>(context: &Arc<Context>, request: &str, timeout: Duration, parse: F) -> Result<T>
+where
+    F: FnMut(&[u8]) -> Response<T>,
+{
+    let fd = terminal::input_fd(context);
+    let command_id = NoncanonicalModeCommand::new(&context.state_manager, fd);
+
+    // The terminal mode is restored on all paths, also when the answer did not arrive.
+    let result = CommandManager::execute(context.clone(), command_id)
+        .and_then(|_| send_and_wait(context, request, timeout, parse));
+    let removed = CommandManager::remove(context.clone(), command_id);
+
+    let answer = result?;
+    removed?;
+    Ok(answer)
+}
+
+fn send_and_wait<T, F>(context: &Context, request: &str, timeout: Duration, mut parse: F) -> Result<T>
+where
+    F: FnMut(&[u8]) -> Response<T>,
+{
+    use std::io::Write;
+
+    {
+        let mut screen_manager = context.screen_manager.lock().unwrap();
+        // The queued output is written first, otherwise the answer is not up to date.
+        screen_manager.write_str(request)?;
+        screen_manager.flush()?;
+    }
+
+    let fd = terminal::input_fd(context);
+    let deadline = Instant::now() + timeout;
+
+    // The bytes that could be the answer and the bytes of an event the user typed.
+    let mut pending = Vec::new();
+    let mut event_bytes = Vec::new();
+
+    loop {
+        let now = Instant::now();
+        if now >= deadline {
+            // The bytes that are read are not the answer, they are kept for `TerminalInput::read()`.
+            if !event_bytes.is_empty() {
+                queue_incomplete_event(context, &mut event_bytes);
+            }
+            if !pending.is_empty() {
+                queue_incomplete_event(context, &mut pending);
+            }
+
+            return Err(Error::new(ErrorKind::Timeout, "the terminal did not answer in time"));
+        }
+
+        if !kernel_input::poll_input(fd, Some(deadline - now))? {
+            continue;
+        }
+
+        let byte = kernel_input::read_byte(fd)?;
+
+        // The answer could arrive while the bytes of an incomplete event are kept, the answer starts with `ESC`.
+        // Pasted text is not cut off since it can contain escape sequences itself.
+        if byte == ESC && !event_bytes.is_empty() && !event_bytes.starts_with(ansi_parser::PASTE_START) {
+            queue_incomplete_event(context, &mut event_bytes);
+        }
+
+        if !event_bytes.is_empty() {
+            event_bytes.push(byte);
+        } else {
+            pending.push(byte);
+
+            match parse(&pending) {
+                Response::Complete(answer) => return Ok(answer),
+                Response::Incomplete => continue,
+                Response::NoMatch => {
+                    event_bytes = mem::take(&mut pending);
+
+                    // The last byte could be the start of the answer, like the `ESC` after a lone `Esc` key.
+                    if event_bytes.len() > 1 {
+                        let last = event_bytes.len() - 1;
+
+                        match parse(&event_bytes[last..]) {
+                            Response::NoMatch => {}
+                            response => {
+                                pending.push(event_bytes.pop().unwrap());
+                                queue_event(context, &mut event_bytes, false);
+                                event_bytes.clear();
+
+                                match response {
+                                    Response::Complete(answer) => return Ok(answer),
+                                    _ => continue,
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        let more_input = kernel_input::input_available(fd)?;
+        queue_event(context, &mut event_bytes, more_input);
+    }
+}
+
+/// Queue the bytes of an event that is not complete, as the event they are without more input or as they are.
+fn queue_incomplete_event(context: &Context, bytes: &mut Vec<u8>) {
+    let event = match ansi_parser::parse_event(bytes, false) {
+        Ok(Some(event)) => event,
+        _ => Event::Unknown(bytes.clone()),
+    };
+
+    context.event_queue.lock().unwrap().push_back(event);
+    bytes.clear();
+}
+
+/// Decode the given bytes and queue the event, the bytes are kept when they are the start of an event.
+fn queue_event(context: &Context, bytes: &mut Vec<u8>, more_input: bool) {
+    match ansi_parser::parse_event(bytes, more_input) {
+        Ok(Some(event)) => {
+            context.event_queue.lock().unwrap().push_back(event);
+            bytes.clear();
+        }
+        Ok(None) => {}
+        // Bytes that are not a known event are queued as they are, like `TerminalInput::read()` does.
+        Err(_) => {
+            let bytes = mem::take(bytes);
+            context.event_queue.lock().unwrap().push_back(Event::Unknown(bytes));
+        }
+    }
+}
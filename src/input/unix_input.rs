@@ -0,0 +1,208 @@
+//! This is an UNIX specific implementation for input related action.
+//! The bytes send by the terminal are decoded with the ANSI parser.
+
+use super::ansi_parser;
+use super::*;
+use kernel::unix_kernel::input as kernel_input;
+use kernel::unix_kernel::signal::ResizeListener;
+use kernel::unix_kernel::terminal;
+use Context;
+
+
+use std::os::unix::io::RawFd;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// This struct is an unix implementation for input related actions.
+pub struct UnixInput {
+    context: Arc<Context>,
+}
+
+impl UnixInput {
+    pub fn new(context: Arc<Context>) -> UnixInput {
+        UnixInput { context }
+    }
+}
+
+impl ITerminalInput for UnixInput {
+    fn poll(&self, timeout: Duration) -> Result<bool> {
+        if !self.context.event_queue.lock().unwrap().is_empty() {
+            return Ok(true);
+        }
+
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            if self.wait_for_events(Some(deadline))? {
+                return Ok(true);
+            }
+
+            if Instant::now() >= deadline {
+                return Ok(false);
+            }
+        }
+    }
+
+    fn read(&self) -> Result<Event> {
+        loop {
+            if let Some(event) = self.context.event_queue.lock().unwrap().pop_front() {
+                return Ok(event);
+            }
+
+            self.wait_for_events(None)?;
+        }
+    }
+}
+
+impl UnixInput {
+    /// Wait until the terminal sends input or is resized and queue the events,
+    /// returns `false` if the deadline passed or the wait was interrupted by a signal.
+    fn wait_for_events(&self, deadline: Option<Instant>) -> io::Result<bool> {
+        // The lock is released before waiting, so that other threads are not blocked while this thread waits for input.
+        let resize_listener = {
+            let mut resize_listener = self.context.resize_listener.lock().unwrap();
+
+            if resize_listener.is_none() {
+                *resize_listener = Some(Arc::new(ResizeListener::new()?));
+            }
+
+            resize_listener.as_ref().unwrap().clone()
+        };
+
+        let input_fd = terminal::input_fd(&self.context);
+        let ready = kernel_input::poll_fds(&[input_fd, resize_listener.fd()], remaining(deadline))?;
+
+        if ready[1] && resize_listener.take_resized() {
+            let (columns, rows) = terminal::terminal_size(terminal::size_fd(&self.context))?;
+            self.context.event_queue.lock().unwrap().push_back(Event::Resize(columns, rows));
+            return Ok(true);
+        }
+
+        if ready[0] {
+            let events = read_events(input_fd, deadline)?;
+            self.context.event_queue.lock().unwrap().extend(events);
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+}
+
+/// Read the bytes that are waiting and decode them into events, bytes that are not a known event become `Event::Unknown`.
+///
+/// When the last event is not complete this waits for its remaining bytes until the deadline,
+/// the bytes that did not arrive in time are returned as `Event::Unknown`.
+fn read_events(fd: RawFd, deadline: Option<Instant>) -> io::Result<Vec<Event>> {
+    let mut events = Vec::new();
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 1024];
+
+    loop {
+        let count = kernel_input::read_available(fd, &mut chunk)?;
+        buffer.extend_from_slice(&chunk[..count]);
+
+        let more_input = kernel_input::input_available(fd)?;
+        let parsed = parse_events(&buffer, more_input, &mut events);
+        buffer.drain(..parsed);
+
+        if buffer.is_empty() {
+            return Ok(events);
+        }
+
+        if !wait_for_input(fd, deadline)? {
+            events.push(Event::Unknown(buffer));
+            return Ok(events);
+        }
+    }
+}
+
+/// Decode the events at the start of the given bytes, returns the number of bytes that are decoded.
+///
+/// The bytes are tried one more at a time, so that the bytes of the next event are not taken into the current one.
+fn parse_events(buffer: &[u8], more_input: bool, events: &mut Vec<Event>) -> usize {
+    let mut start = 0;
+    let mut end = start + 1;
+
+    while end <= buffer.len() {
+        // Only after the last byte it depends on the terminal whether more bytes are waiting.
+        let more_input = more_input || end < buffer.len();
+
+        match ansi_parser::parse_event(&buffer[start..end], more_input) {
+            Ok(Some(event)) => events.push(event),
+            Ok(None) => {
+                end += 1;
+                continue;
+            }
+            Err(_) => events.push(Event::Unknown(buffer[start..end].to_vec())),
+        }
+
+        start = end;
+        end = start + 1;
+    }
+
+    start
+}
+
+/// Wait until there is input to be read or the deadline passed, `None` waits without a deadline.
+fn wait_for_input(fd: RawFd, deadline: Option<Instant>) -> io::Result<bool> {
+    loop {
+        if kernel_input::poll_input(fd, remaining(deadline))? {
+            return Ok(true);
+        }
+
+        if let Some(deadline) = deadline {
+            if Instant::now() >= deadline {
+                return Ok(false);
+            }
+        }
+    }
+}
+
+/// The time that is left until the deadline, `None` means no deadline.
+fn remaining(deadline: Option<Instant>) -> Option<Duration> {
+    deadline.map(|deadline| {
+        let now = Instant::now();
+        if deadline > now {
+            deadline - now
+        } else {
+            Duration::from_millis(0)
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::read_events;
+    use input::{Event, KeyEvent};
+    use libc;
+
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn events_are_read_at_once_and_an_incomplete_event_waits_until_the_deadline() {
+        let mut fds = [0; 2];
+        assert_eq!(unsafe { libc::pipe(fds.as_mut_ptr()) }, 0);
+
+        let bytes = b"a\x1B[A\x1B[1;";
+        let written = unsafe { libc::write(fds[1], bytes.as_ptr() as *const libc::c_void, bytes.len()) };
+        assert_eq!(written, bytes.len() as isize);
+
+        let start = Instant::now();
+        let events = read_events(fds[0], Some(start + Duration::from_millis(50))).unwrap();
+
+        assert_eq!(
+            events,
+            vec![
+                Event::Key(KeyEvent::Char('a')),
+                Event::Key(KeyEvent::Up),
+                Event::Unknown(b"\x1B[1;".to_vec()),
+            ]
+        );
+        assert!(start.elapsed() >= Duration::from_millis(50));
+
+        unsafe {
+            libc::close(fds[0]);
+            libc::close(fds[1]);
+        }
+    }
+}
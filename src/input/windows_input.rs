@@ -0,0 +1,204 @@
+//! This is an WINAPI specific implementation for input related action.
+//! The console input events are read with `ReadConsoleInputW`, key events are translated with the virtual key code.
+
+use super::*;
+use kernel::windows_kernel::input::{self as kernel_input, InputRecord};
+use kernel::windows_kernel::kernel;
+
+use winapi::shared::minwindef::DWORD;
+use winapi::um::wincon::{
+    KEY_EVENT_RECORD, LEFT_ALT_PRESSED, LEFT_CTRL_PRESSED, MOUSE_EVENT_RECORD, RIGHT_ALT_PRESSED,
+    RIGHT_CTRL_PRESSED, SHIFT_PRESSED,
+};
+use winapi::um::winuser;
+use Context;
+
+use std::char;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+// Values of `MOUSE_EVENT_RECORD`, see https://docs.microsoft.com/en-us/windows/console/mouse-event-record-str.
+const FROM_LEFT_1ST_BUTTON_PRESSED: DWORD = 0x0001;
+const RIGHTMOST_BUTTON_PRESSED: DWORD = 0x0002;
+const FROM_LEFT_2ND_BUTTON_PRESSED: DWORD = 0x0004;
+const MOUSE_MOVED: DWORD = 0x0001;
+const MOUSE_WHEELED: DWORD = 0x0004;
+const MOUSE_HWHEELED: DWORD = 0x0008;
+
+/// This struct is an windows implementation for input related actions.
+pub struct WindowsInput {
+    context: Arc<Context>,
+}
+
+impl WindowsInput {
+    pub fn new(context: Arc<Context>) -> WindowsInput {
+        WindowsInput { context }
+    }
+}
+
+impl ITerminalInput for WindowsInput {
+    fn poll(&self, timeout: Duration) -> Result<bool> {
+        if !self.context.event_queue.lock().unwrap().is_empty() {
+            return Ok(true);
+        }
+
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            let now = Instant::now();
+            let remaining = if deadline > now { deadline - now } else { Duration::from_millis(0) };
+
+            // The input handle is signaled for every console event, so reading one does not block.
+            if kernel_input::wait_for_input(Some(remaining))? {
+                if let Some(event) = read_event()? {
+                    self.context.event_queue.lock().unwrap().push_back(event);
+                    return Ok(true);
+                }
+            }
+
+            if Instant::now() >= deadline {
+                return Ok(false);
+            }
+        }
+    }
+
+    fn read(&self) -> Result<Event> {
+        if let Some(event) = self.context.event_queue.lock().unwrap().pop_front() {
+            return Ok(event);
+        }
+
+        loop {
+            if let Some(event) = read_event()? {
+                return Ok(event);
+            }
+        }
+    }
+}
+
+/// Read a single event from the console, returns `None` for events that crossterm does not report.
+fn read_event() -> io::Result<Option<Event>> {
+    Ok(match kernel_input::read_single_input_record()? {
+        Some(InputRecord::Key(key_event)) => parse_key_event(&key_event).map(Event::Key),
+        Some(InputRecord::Mouse(mouse_event)) => Some(Event::Mouse(parse_mouse_event(&mouse_event))),
+        Some(InputRecord::Resize) => {
+            // The record contains the size of the screen buffer, the size of the visible window is reported instead.
+            let window = kernel::get_std_console_screen_buffer_info().srWindow;
+            Some(Event::Resize(
+                (window.Right - window.Left) as u16,
+                (window.Bottom - window.Top) as u16,
+            ))
+        }
+        Some(InputRecord::Focus(true)) => Some(Event::FocusGained),
+        Some(InputRecord::Focus(false)) => Some(Event::FocusLost),
+        None => None,
+    })
+}
+
+/// Get the modifier keys from the control key state of an input record.
+fn parse_modifiers(state: DWORD) -> KeyModifiers {
+    KeyModifiers {
+        shift: state & SHIFT_PRESSED != 0,
+        alt: state & (LEFT_ALT_PRESSED | RIGHT_ALT_PRESSED) != 0,
+        ctrl: state & (LEFT_CTRL_PRESSED | RIGHT_CTRL_PRESSED) != 0,
+    }
+}
+
+/// Translate a key event record, keys that do not produce a character on their own like shift return `None`.
+fn parse_key_event(key_event: &KEY_EVENT_RECORD) -> Option<KeyEvent> {
+    let modifiers = parse_modifiers(key_event.dwControlKeyState);
+
+    let key = match key_event.wVirtualKeyCode as i32 {
+        winuser::VK_BACK => KeyEvent::Backspace,
+        winuser::VK_RETURN => KeyEvent::Enter,
+        winuser::VK_TAB if modifiers.shift => return Some(KeyEvent::BackTab),
+        winuser::VK_TAB => KeyEvent::Tab,
+        winuser::VK_ESCAPE => KeyEvent::Esc,
+        winuser::VK_LEFT => KeyEvent::Left,
+        winuser::VK_RIGHT => KeyEvent::Right,
+        winuser::VK_UP => KeyEvent::Up,
+        winuser::VK_DOWN => KeyEvent::Down,
+        winuser::VK_HOME => KeyEvent::Home,
+        winuser::VK_END => KeyEvent::End,
+        winuser::VK_PRIOR => KeyEvent::PageUp,
+        winuser::VK_NEXT => KeyEvent::PageDown,
+        winuser::VK_INSERT => KeyEvent::Insert,
+        winuser::VK_DELETE => KeyEvent::Delete,
+        code if code >= winuser::VK_F1 && code <= winuser::VK_F12 => {
+            KeyEvent::F((code - winuser::VK_F1 + 1) as u8)
+        }
+        _ => {
+            let character = unsafe { *key_event.uChar.UnicodeChar() };
+
+            // Keys like shift and ctrl on their own do not produce a character.
+            if character == 0 {
+                return None;
+            }
+
+            // When ctrl is held the console reports the control character, `Ctrl+a` is `0x01`.
+            if character < 0x20 {
+                return Some(match character {
+                    0x01..=0x1A => KeyEvent::Ctrl((character as u8 + 0x60) as char),
+                    _ => KeyEvent::Ctrl((character as u8 + 0x40) as char),
+                });
+            }
+
+            let character = char::from_u32(u32::from(character))?;
+
+            if modifiers.alt && !modifiers.ctrl {
+                return Some(KeyEvent::Alt(character));
+            }
+
+            return Some(KeyEvent::Char(character));
+        }
+    };
+
+    if modifiers.is_empty() {
+        Some(key)
+    } else {
+        Some(KeyEvent::Modified(modifiers, Box::new(key)))
+    }
+}
+
+/// Translate a mouse event record.
+fn parse_mouse_event(mouse_event: &MOUSE_EVENT_RECORD) -> MouseEvent {
+    let buttons = mouse_event.dwButtonState;
+
+    let button = if buttons & FROM_LEFT_1ST_BUTTON_PRESSED != 0 {
+        Some(MouseButton::Left)
+    } else if buttons & RIGHTMOST_BUTTON_PRESSED != 0 {
+        Some(MouseButton::Right)
+    } else if buttons & FROM_LEFT_2ND_BUTTON_PRESSED != 0 {
+        Some(MouseButton::Middle)
+    } else {
+        None
+    };
+
+    // The high word of the button state is the signed wheel delta.
+    let wheel_delta = (buttons >> 16) as i16;
+
+    let kind = match mouse_event.dwEventFlags {
+        MOUSE_WHEELED if wheel_delta > 0 => MouseEventKind::ScrollUp,
+        MOUSE_WHEELED => MouseEventKind::ScrollDown,
+        MOUSE_HWHEELED if wheel_delta > 0 => MouseEventKind::ScrollRight,
+        MOUSE_HWHEELED => MouseEventKind::ScrollLeft,
+        MOUSE_MOVED => match button {
+            Some(button) => MouseEventKind::Drag(button),
+            None => MouseEventKind::Move,
+        },
+        _ => match button {
+            Some(button) => MouseEventKind::Press(button),
+            None => MouseEventKind::Release(None),
+        },
+    };
+
+    // The console reports the position in the screen buffer, crossterm uses the position in the visible window.
+    let window = kernel::get_std_console_screen_buffer_info().srWindow;
+    let position = mouse_event.dwMousePosition;
+
+    MouseEvent {
+        kind,
+        x: (position.X - window.Left).max(0) as u16,
+        y: (position.Y - window.Top).max(0) as u16,
+        modifiers: parse_modifiers(mouse_event.dwControlKeyState),
+    }
+}
@@ -0,0 +1,105 @@
+//! This module contains all `unix` specific input related logic.
+//!
+//! Note that the input is read directly from the file descriptor (stdin by default) and not with `std::io::stdin()`.
+//! The standard library buffers stdin, bytes that end up in that buffer can not be seen by `poll`.
+
+use libc::{self, c_int, c_void, pollfd, POLLERR, POLLHUP, POLLIN, POLLNVAL};
+
+use std::io;
+use std::os::unix::io::RawFd;
+use std::time::Duration;
+
+/// Read a single byte from the given terminal input, this call blocks until a byte is available.
+pub fn read_byte(fd: RawFd) -> io::Result<u8> {
+    let mut buf = [0u8; 1];
+    read_available(fd, &mut buf)?;
+    Ok(buf[0])
+}
+
+/// Read the bytes that are waiting to be read from the given terminal input, at most as many as fit in `buf`.
+///
+/// Returns the number of bytes that are read, this call blocks until at least one byte is available.
+pub fn read_available(fd: RawFd, buf: &mut [u8]) -> io::Result<usize> {
+    loop {
+        let result = unsafe { libc::read(fd, buf.as_mut_ptr() as *mut c_void, buf.len()) };
+
+        match result {
+            0 => {
+                return Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "The terminal input has been closed",
+                ))
+            }
+            count if count > 0 => return Ok(count as usize),
+            _ => {
+                let error = io::Error::last_os_error();
+                if error.kind() != io::ErrorKind::Interrupted {
+                    return Err(error);
+                }
+            }
+        }
+    }
+}
+
+/// Check whether there is input waiting to be read, this call does not block.
+pub fn input_available(fd: RawFd) -> io::Result<bool> {
+    poll_input(fd, Some(Duration::from_millis(0)))
+}
+
+/// Wait until there is input to be read or the timeout expires, `None` waits without a timeout.
+///
+/// Returns `false` when the timeout expired or the wait was interrupted by a signal.
+pub fn poll_input(fd: RawFd, timeout: Option<Duration>) -> io::Result<bool> {
+    Ok(poll_fds(&[fd], timeout)?[0])
+}
+
+/// Wait until one of the given file descriptors can be read or the timeout expires, `None` waits without a timeout.
+///
+/// Returns for every file descriptor whether it can be read, all are `false` when the timeout expired
+/// or the wait was interrupted by a signal.
+/// A file descriptor that is hung up or has an error, like a closed terminal, can be read as well,
+/// reading it returns the end of the input or the error instead of blocking.
+pub fn poll_fds(fds: &[RawFd], timeout: Option<Duration>) -> io::Result<Vec<bool>> {
+    let mut poll_fds: Vec<pollfd> = fds
+        .iter()
+        .map(|fd| pollfd {
+            fd: *fd,
+            events: POLLIN,
+            revents: 0,
+        })
+        .collect();
+
+    let timeout = match timeout {
+        Some(timeout) => {
+            // Round up, otherwise a timeout below a millisecond becomes 0 and the caller keeps polling until the deadline.
+            let nanos = u64::from(timeout.subsec_nanos());
+            let millis = timeout.as_secs().saturating_mul(1000).saturating_add(nanos.div_ceil(1_000_000));
+            millis.min(c_int::MAX as u64) as c_int
+        }
+        None => -1,
+    };
+
+    let result = unsafe { libc::poll(poll_fds.as_mut_ptr(), poll_fds.len() as libc::nfds_t, timeout) };
+
+    match result {
+        -1 => {
+            let error = io::Error::last_os_error();
+            if error.kind() == io::ErrorKind::Interrupted {
+                Ok(vec![false; fds.len()])
+            } else {
+                Err(error)
+            }
+        }
+        0 => Ok(vec![false; fds.len()]),
+        _ => {
+            if poll_fds.iter().any(|fd| fd.revents & POLLNVAL != 0) {
+                return Err(io::Error::from_raw_os_error(libc::EBADF));
+            }
+
+            Ok(poll_fds
+                .iter()
+                .map(|fd| fd.revents & (POLLIN | POLLHUP | POLLERR) != 0)
+                .collect())
+        }
+    }
+}
@@ -0,0 +1,336 @@
+//! This module contains all `unix` specific signal handling logic.
+//!
+//! A signal handler may only call async-signal-safe functions, so the handlers in this module do nothing more
+//! than writing a byte into a pipe (the self-pipe trick). The read end of such a pipe can be watched with `poll`
+//! together with the terminal input, so that the signal can be handled outside of the handler.
+
+use super::input;
+use libc::{self, c_int, c_void, sigaction, sighandler_t, siginfo_t, SIGHUP, SIGINT, SIGTERM, SIGTSTP, SIGWINCH};
+
+use std::io;
+use std::mem;
+use std::os::unix::io::RawFd;
+use std::ptr;
+use std::sync::atomic::{AtomicIsize, Ordering};
+use std::sync::Once;
+
+/// The maximum number of listeners that can wait for a resize at the same time.
+const MAX_LISTENERS: usize = 16;
+
+/// The write ends of the pipes of the resize listeners, `-1` is a free slot.
+static RESIZE_LISTENERS: [AtomicIsize; MAX_LISTENERS] = [
+    AtomicIsize::new(-1), AtomicIsize::new(-1), AtomicIsize::new(-1), AtomicIsize::new(-1),
+    AtomicIsize::new(-1), AtomicIsize::new(-1), AtomicIsize::new(-1), AtomicIsize::new(-1),
+    AtomicIsize::new(-1), AtomicIsize::new(-1), AtomicIsize::new(-1), AtomicIsize::new(-1),
+    AtomicIsize::new(-1), AtomicIsize::new(-1), AtomicIsize::new(-1), AtomicIsize::new(-1),
+];
+
+static INSTALL_RESIZE_HANDLER: Once = Once::new();
+/// The handler that was installed before crossterm installed its own, it is called after crossterm's handler.
+static mut PREVIOUS_RESIZE_HANDLER: Option<sigaction> = None;
+
+/// The signals that are written into the pipe of `install_handlers`,
+/// the signals that terminate the process and after which the terminal should be restored and `SIGTSTP` that stops it.
+const HANDLED_SIGNALS: [c_int; 4] = [SIGINT, SIGTERM, SIGHUP, SIGTSTP];
+
+/// The write end of the pipe that receives the handled signals, `-1` when the handlers are not installed.
+static SIGNAL_PIPE: AtomicIsize = AtomicIsize::new(-1);
+/// The handlers of the handled signals that were installed before crossterm installed its own.
+static mut PREVIOUS_HANDLERS: [Option<sigaction>; 4] = [None, None, None, None];
+
+/// A pipe that receives a byte every time the terminal is resized.
+pub struct ResizeListener {
+    read_fd: RawFd,
+    write_fd: RawFd,
+    slot: usize,
+}
+
+impl ResizeListener {
+    /// Create a new listener, the `SIGWINCH` handler is installed the first time this is called.
+    pub fn new() -> io::Result<ResizeListener> {
+        install_resize_handler()?;
+
+        let (read_fd, write_fd) = create_pipe()?;
+
+        let slot = RESIZE_LISTENERS.iter().position(|listener| {
+            listener
+                .compare_exchange(-1, write_fd as isize, Ordering::SeqCst, Ordering::SeqCst)
+                .is_ok()
+        });
+
+        let slot = match slot {
+            Some(slot) => slot,
+            None => {
+                close_pipe(read_fd, write_fd);
+                return Err(io::Error::other("Too many resize listeners"));
+            }
+        };
+
+        Ok(ResizeListener {
+            read_fd,
+            write_fd,
+            slot,
+        })
+    }
+
+    /// The file descriptor that becomes readable when the terminal is resized.
+    pub fn fd(&self) -> RawFd {
+        self.read_fd
+    }
+
+    /// Make the file descriptor readable without a resize, so that a thread that waits for it wakes up.
+    pub fn wake(&self) {
+        unsafe {
+            libc::write(self.write_fd, b"\x01".as_ptr() as *const c_void, 1);
+        }
+    }
+
+    /// Empty the pipe, returns whether the terminal has been resized since the last call.
+    pub fn take_resized(&self) -> bool {
+        let mut buf = [0u8; 32];
+        let mut resized = false;
+
+        loop {
+            let result =
+                unsafe { libc::read(self.read_fd, buf.as_mut_ptr() as *mut c_void, buf.len()) };
+
+            if result <= 0 {
+                return resized;
+            }
+
+            resized = true;
+        }
+    }
+}
+
+impl Drop for ResizeListener {
+    fn drop(&mut self) {
+        RESIZE_LISTENERS[self.slot].store(-1, Ordering::SeqCst);
+
+        close_pipe(self.read_fd, self.write_fd);
+    }
+}
+
+/// Install the `SIGWINCH` handler, this is only done once.
+fn install_resize_handler() -> io::Result<()> {
+    let mut result = Ok(());
+
+    INSTALL_RESIZE_HANDLER.call_once(|| unsafe {
+        let mut action: sigaction = mem::zeroed();
+        // The handler gets the signal information, so that it can pass it on to a previous `SA_SIGINFO` handler.
+        action.sa_sigaction = on_resize as extern "C" fn(c_int, *mut siginfo_t, *mut c_void) as sighandler_t;
+        action.sa_flags = libc::SA_RESTART | libc::SA_SIGINFO;
+        libc::sigemptyset(&mut action.sa_mask);
+
+        let mut previous: sigaction = mem::zeroed();
+
+        if libc::sigaction(SIGWINCH, &action, &mut previous) == -1 {
+            result = Err(io::Error::last_os_error());
+        } else {
+            PREVIOUS_RESIZE_HANDLER = Some(previous);
+        }
+    });
+
+    result
+}
+
+/// The `SIGWINCH` handler, writes a byte to the pipe of every listener.
+extern "C" fn on_resize(signal: c_int, info: *mut siginfo_t, context: *mut c_void) {
+    unsafe {
+        let saved_errno = *errno();
+
+        for listener in RESIZE_LISTENERS.iter() {
+            let fd = listener.load(Ordering::SeqCst);
+
+            if fd != -1 {
+                // The pipe is non-blocking, when it is full the listener already knows about the resize.
+                libc::write(fd as RawFd, b"\x01".as_ptr() as *const c_void, 1);
+            }
+        }
+
+        if let Some(ref previous) = PREVIOUS_RESIZE_HANDLER {
+            let handler = previous.sa_sigaction;
+
+            if handler != libc::SIG_DFL && handler != libc::SIG_IGN {
+                if previous.sa_flags & libc::SA_SIGINFO != 0 {
+                    let handler: extern "C" fn(c_int, *mut siginfo_t, *mut c_void) = mem::transmute(handler);
+                    handler(signal, info, context);
+                } else {
+                    let handler: extern "C" fn(c_int) = mem::transmute(handler);
+                    handler(signal);
+                }
+            }
+        }
+
+        *errno() = saved_errno;
+    }
+}
+
+/// Install handlers for `SIGINT`, `SIGTERM`, `SIGHUP` and `SIGTSTP` that write the number of the received signal into a pipe,
+/// returns the read end of that pipe.
+///
+/// Signals that are ignored, for example `SIGHUP` when the program is started with `nohup`, stay ignored.
+/// This should only be called once.
+pub fn install_handlers() -> io::Result<RawFd> {
+    let (read_fd, write_fd) = create_pipe()?;
+    SIGNAL_PIPE.store(write_fd as isize, Ordering::SeqCst);
+
+    for (index, signal) in HANDLED_SIGNALS.iter().enumerate() {
+        unsafe {
+            let mut action: sigaction = mem::zeroed();
+            action.sa_sigaction = on_signal as extern "C" fn(c_int) as sighandler_t;
+            action.sa_flags = libc::SA_RESTART;
+            libc::sigemptyset(&mut action.sa_mask);
+
+            let mut previous: sigaction = mem::zeroed();
+
+            if libc::sigaction(*signal, &action, &mut previous) == -1 {
+                return Err(io::Error::last_os_error());
+            }
+
+            if previous.sa_sigaction == libc::SIG_IGN {
+                libc::sigaction(*signal, &previous, ptr::null_mut());
+            } else {
+                PREVIOUS_HANDLERS[index] = Some(previous);
+            }
+        }
+    }
+
+    Ok(read_fd)
+}
+
+/// Put the handler back that was installed before crossterm's handler and raise the signal again,
+/// so that the behaviour the program had without crossterm continues, by default the process is terminated.
+///
+/// When the process is not terminated, crossterm's handler is installed again so that the next signal is handled as well.
+pub fn raise_with_previous_handler(signal: c_int) {
+    unsafe {
+        let index = HANDLED_SIGNALS.iter().position(|s| *s == signal);
+
+        let previous: sigaction = match index.and_then(|index| PREVIOUS_HANDLERS[index]) {
+            Some(previous) => previous,
+            None => {
+                let mut action: sigaction = mem::zeroed();
+                action.sa_sigaction = libc::SIG_DFL;
+                libc::sigemptyset(&mut action.sa_mask);
+                action
+            }
+        };
+
+        let mut own: sigaction = mem::zeroed();
+
+        if libc::sigaction(signal, &previous, &mut own) == -1 {
+            return;
+        }
+
+        // The signal is delivered before `raise` returns, the previous handler has run when it returns.
+        libc::raise(signal);
+
+        libc::sigaction(signal, &own, ptr::null_mut());
+    }
+}
+
+/// Wait until a signal is written into the pipe of `install_handlers`, returns the number of the received signal.
+pub fn wait_for_signal(fd: RawFd) -> io::Result<c_int> {
+    loop {
+        input::poll_fds(&[fd], None)?;
+
+        let mut byte = 0u8;
+        let result = unsafe { libc::read(fd, &mut byte as *mut u8 as *mut c_void, 1) };
+
+        if result == 1 {
+            return Ok(byte as c_int);
+        }
+    }
+}
+
+/// Stop the process like `Ctrl-Z` does in a shell, this returns when the process is continued with `SIGCONT`.
+///
+/// The default `SIGTSTP` action is used while stopping, the handler that is installed by the program is put back afterwards.
+pub fn stop_process() -> io::Result<()> {
+    unsafe {
+        let mut action: sigaction = mem::zeroed();
+        action.sa_sigaction = libc::SIG_DFL;
+        libc::sigemptyset(&mut action.sa_mask);
+
+        let mut previous: sigaction = mem::zeroed();
+
+        if libc::sigaction(SIGTSTP, &action, &mut previous) == -1 {
+            return Err(io::Error::last_os_error());
+        }
+
+        let result = if libc::raise(SIGTSTP) != 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(())
+        };
+
+        libc::sigaction(SIGTSTP, &previous, ptr::null_mut());
+
+        result
+    }
+}
+
+/// Let every resize listener know that the terminal is resized, so that programs draw their screen again.
+pub fn raise_resize() {
+    unsafe {
+        libc::raise(SIGWINCH);
+    }
+}
+
+/// The handler of the handled signals, writes the signal number to the signal pipe.
+extern "C" fn on_signal(signal: c_int) {
+    unsafe {
+        let saved_errno = *errno();
+
+        let fd = SIGNAL_PIPE.load(Ordering::SeqCst);
+
+        if fd != -1 {
+            let byte = signal as u8;
+            libc::write(fd as RawFd, &byte as *const u8 as *const c_void, 1);
+        }
+
+        *errno() = saved_errno;
+    }
+}
+
+/// Create a non-blocking pipe that is closed on exec.
+fn create_pipe() -> io::Result<(RawFd, RawFd)> {
+    let mut fds = [0 as c_int; 2];
+
+    if unsafe { libc::pipe(fds.as_mut_ptr()) } == -1 {
+        return Err(io::Error::last_os_error());
+    }
+
+    for fd in fds.iter() {
+        unsafe {
+            let flags = libc::fcntl(*fd, libc::F_GETFL);
+            libc::fcntl(*fd, libc::F_SETFL, flags | libc::O_NONBLOCK);
+            libc::fcntl(*fd, libc::F_SETFD, libc::FD_CLOEXEC);
+        }
+    }
+
+    Ok((fds[0], fds[1]))
+}
+
+fn close_pipe(read_fd: RawFd, write_fd: RawFd) {
+    unsafe {
+        libc::close(read_fd);
+        libc::close(write_fd);
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+unsafe fn errno() -> *mut c_int {
+    libc::__errno_location()
+}
+
+#[cfg(any(target_os = "macos", target_os = "ios", target_os = "freebsd"))]
+unsafe fn errno() -> *mut c_int {
+    libc::__error()
+}
+
+#[cfg(any(target_os = "openbsd", target_os = "netbsd"))]
+unsafe fn errno() -> *mut c_int {
+    libc::__errno()
+}
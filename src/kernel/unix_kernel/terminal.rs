@@ -1,13 +1,15 @@
 //! This module contains all `unix` specific terminal related logic.
 
 pub use self::libc::termios;
-use self::libc::{c_int, c_ushort, ioctl, STDOUT_FILENO, TIOCGWINSZ};
-use state::commands::{IStateCommand, NoncanonicalModeCommand};
+use self::libc::{c_int, c_ushort, ioctl, STDIN_FILENO, STDOUT_FILENO, TIOCGWINSZ};
+use input::query::{self, Response};
 use termios::Termios;
-use {libc, CommandManager, Context, StateManager};
+use {libc, Context, Result};
 
-use std::io::Error;
-use std::rc::Rc;
+use std::fs::{File, OpenOptions};
+use std::os::unix::io::RawFd;
+use std::sync::Arc;
+use std::time::Duration;
 use std::{io, mem};
 
 /// A representation of the size of the current terminal.
@@ -22,8 +24,28 @@ pub struct UnixSize {
     y: c_ushort,
 }
 
-/// Get the current terminal size.
-pub fn terminal_size() -> (u16, u16) {
+/// Check whether the given file descriptor belongs to a terminal.
+pub fn is_tty(fd: RawFd) -> bool {
+    unsafe { libc::isatty(fd) == 1 }
+}
+
+/// Open the controlling terminal of this process, this can be used when stdin or stdout is not a terminal.
+pub fn open_tty() -> io::Result<File> {
+    OpenOptions::new().read(true).write(true).open("/dev/tty")
+}
+
+/// Get the file descriptor the input of the given context is read from and whereof the mode is changed.
+pub fn input_fd(context: &Context) -> RawFd {
+    context.input_fd.unwrap_or(STDIN_FILENO)
+}
+
+/// Get the file descriptor of the terminal whereof the size of the given context is read.
+pub fn size_fd(context: &Context) -> RawFd {
+    context.input_fd.unwrap_or(STDOUT_FILENO)
+}
+
+/// Get the current size of the terminal the given file descriptor belongs to.
+pub fn terminal_size(fd: RawFd) -> io::Result<(u16, u16)> {
     // http://rosettacode.org/wiki/Terminal_control/Dimensions#Library:_BSD_libc
     let us = UnixSize {
         rows: 0,
@@ -31,86 +53,130 @@ pub fn terminal_size() -> (u16, u16) {
         x: 0,
         y: 0,
     };
-    let r = unsafe { ioctl(STDOUT_FILENO, TIOCGWINSZ, &us) };
+    let r = unsafe { ioctl(fd, TIOCGWINSZ, &us) };
     if r == 0 {
         // because crossterm works starts counting at 0 and unix terminal starts at cell 1 you have subtract one to get 0-based results.
-        (us.cols - 1, us.rows - 1)
+        Ok((us.cols.saturating_sub(1), us.rows.saturating_sub(1)))
     } else {
-        (0, 0)
+        Err(io::Error::last_os_error())
     }
 }
 
-/// Get the current cursor position.
-pub fn pos(context: Rc<Context>) -> (u16, u16) {
-    use std::io::{Read, Write};
-
-    let mut command_id = NoncanonicalModeCommand::new(&context.state_manager);
-
-    CommandManager::execute(context.clone(), command_id);
+/// Get the current cursor position, waits at most `timeout` for the terminal to report it.
+pub fn pos(context: &Arc<Context>, timeout: Duration) -> Result<(u16, u16)> {
+    query::query(context, csi!("6n"), timeout, parse_position)
+}
 
-    // This code is original written by term_cursor credits to them.
-    use std::io;
-    let mut std = io::stdout();
-    // Write command
-    std.write(b"\x1B[6n");
-    std.flush();
+/// Parse the cursor position report `ESC [ row ; column R`, the position is returned 0-based as (column, row).
+///
+/// A key with modifiers, like `ESC [ 1 ; 5 R` for F3 with Ctrl, looks the same as a report for the first row.
+/// Such a key that is pressed while waiting is taken as the position, the report of the terminal is then read as input afterwards.
+fn parse_position(bytes: &[u8]) -> Response<(u16, u16)> {
+    // The bytes are checked every time a byte is read, so only the last byte can complete or break the report.
+    if bytes.len() <= 2 {
+        return if b"\x1B[".starts_with(bytes) {
+            Response::Incomplete
+        } else {
+            Response::NoMatch
+        };
+    }
 
-    // Read back result
-    let mut buf = [0u8; 2];
-    // Expect `ESC[`
-    io::stdin().read_exact(&mut buf);
-    if buf[0] != 0x1B || buf[1] as char != '[' {
-        return (0, 0);
+    if !bytes.starts_with(b"\x1B[") {
+        return Response::NoMatch;
     }
 
-    // Read rows and cols through a ad-hoc integer parsing function
-    let read_num = || -> (i32, char) {
-        let mut num = 0;
-        let mut c;
+    let params = &bytes[2..];
+    let (last, numbers) = params.split_last().unwrap();
 
-        loop {
-            let mut buf = [0u8; 1];
-            io::stdin().read_exact(&mut buf);
-            c = buf[0] as char;
-            if let Some(d) = c.to_digit(10) {
-                num = if num == 0 { 0 } else { num * 10 };
-                num += d as i32;
+    match *last {
+        b'0'..=b'9' | b';' if numbers.len() < 11 => {
+            if params.iter().filter(|byte| **byte == b';').count() > 1 {
+                Response::NoMatch
             } else {
-                break;
+                Response::Incomplete
             }
         }
+        b'R' => {
+            let mut values = numbers.split(|byte| *byte == b';').map(parse_number);
 
-        (num, c)
-    };
+            match (values.next(), values.next(), values.next()) {
+                (Some(Some(row)), Some(Some(column)), None) => {
+                    Response::Complete((column.saturating_sub(1), row.saturating_sub(1)))
+                }
+                _ => Response::NoMatch,
+            }
+        }
+        _ => Response::NoMatch,
+    }
+}
+
+/// Read the given selection of the clipboard, waits at most `timeout` for the terminal to answer.
+/// The content is returned base64 encoded like the terminal sends it.
+pub fn clipboard(context: &Arc<Context>, selection: char, timeout: Duration) -> Result<Vec<u8>> {
+    let request = format!(osc!("52;{};?"), selection);
+    query::query(context, &request, timeout, parse_clipboard)
+}
 
-    // Read rows and expect `;`
-    let (rows, c) = read_num();
+/// Parse the clipboard report `ESC ] 52 ; selection ; base64`, that is ended with `BEL` or `ESC \`.
+fn parse_clipboard(bytes: &[u8]) -> Response<Vec<u8>> {
+    const PREFIX: &[u8] = b"\x1B]52;";
 
-    if c != ';' {
-        return (0, 0);
+    if bytes.len() <= PREFIX.len() {
+        return if PREFIX.starts_with(bytes) {
+            Response::Incomplete
+        } else {
+            Response::NoMatch
+        };
     }
 
-    // Read cols
-    let (cols, c) = read_num();
+    if !bytes.starts_with(PREFIX) {
+        return Response::NoMatch;
+    }
 
-    // Expect `R`
-    let res = if c == 'R' {
-        (cols as u16, rows as u16)
-    } else {
-        return (0, 0);
+    // The selection in the report could differ from the requested selection, like `s` for the configured selection.
+    let params = &bytes[PREFIX.len()..];
+    let content = match params.iter().position(|byte| *byte == b';') {
+        Some(index) => &params[index + 1..],
+        None => {
+            return if params.iter().all(|byte| byte.is_ascii_alphanumeric()) {
+                Response::Incomplete
+            } else {
+                Response::NoMatch
+            };
+        }
     };
 
-    CommandManager::undo(context.clone(), command_id);
+    let length = content
+        .iter()
+        .take_while(|byte| byte.is_ascii_alphanumeric() || **byte == b'+' || **byte == b'/' || **byte == b'=')
+        .count();
+    let (base64, terminator) = content.split_at(length);
+
+    match terminator {
+        b"" | b"\x1B" => Response::Incomplete,
+        b"\x07" | b"\x1B\\" => Response::Complete(base64.to_vec()),
+        _ => Response::NoMatch,
+    }
+}
+
+/// Parse a decimal number of a report, returns `None` when it is empty or does not fit.
+fn parse_number(digits: &[u8]) -> Option<u16> {
+    if digits.is_empty() {
+        return None;
+    }
 
-    res
+    digits.iter().try_fold(0u16, |number, digit| {
+        let digit = (*digit as char).to_digit(10)? as u16;
+        number.checked_mul(10)?.checked_add(digit)
+    })
 }
 
-/// Set the terminal mode to the given mode.
-pub fn set_terminal_mode(termios: &Termios) -> io::Result<()> {
+/// Set the mode of the terminal the given file descriptor belongs to.
+pub fn set_terminal_mode(fd: RawFd, termios: &Termios) -> io::Result<()> {
     extern "C" {
         pub fn tcsetattr(fd: c_int, opt: c_int, termptr: *const Termios) -> c_int;
     }
-    is_true(unsafe { tcsetattr(0, 0, termios) }).and(Ok(()))
+    is_true(unsafe { tcsetattr(fd, 0, termios) }).and(Ok(()))
 }
 
 /// Transform the given mode into an raw mode (non-canonical) mode.
@@ -121,14 +187,14 @@ pub fn make_raw(termios: &mut Termios) {
     unsafe { cfmakeraw(termios) }
 }
 
-/// Get the current terminal mode.
-pub fn get_terminal_mode() -> io::Result<Termios> {
+/// Get the current mode of the terminal the given file descriptor belongs to.
+pub fn get_terminal_mode(fd: RawFd) -> io::Result<Termios> {
     extern "C" {
         pub fn tcgetattr(fd: c_int, termptr: *mut Termios) -> c_int;
     }
     unsafe {
         let mut termios = mem::zeroed();
-        is_true(tcgetattr(0, &mut termios))?;
+        is_true(tcgetattr(fd, &mut termios))?;
         Ok(termios)
     }
 }
@@ -138,10 +204,85 @@ pub fn exit() {
 }
 
 /// Is the return value true?
-fn is_true(value: i32) -> Result<(), Error> {
+fn is_true(value: i32) -> io::Result<()> {
     match value {
         -1 => Err(io::Error::last_os_error()),
         0 => Ok(()),
         _ => Err(io::Error::last_os_error()),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_clipboard, parse_position};
+    use input::query::Response;
+
+    #[test]
+    fn position_is_0_based_column_and_row() {
+        assert_eq!(parse_position(b"\x1B[5;10R"), Response::Complete((9, 4)));
+        assert_eq!(parse_position(b"\x1B[1;1R"), Response::Complete((0, 0)));
+        assert_eq!(parse_position(b"\x1B[0;0R"), Response::Complete((0, 0)));
+    }
+
+    #[test]
+    fn position_prefixes_are_incomplete() {
+        let report = b"\x1B[12;345R";
+
+        for length in 0..report.len() {
+            assert_eq!(parse_position(&report[..length]), Response::Incomplete, "{:?}", &report[..length]);
+        }
+    }
+
+    #[test]
+    fn other_input_is_no_position() {
+        let cases: [&[u8]; 10] = [
+            b"a",
+            b"ab5;1R",
+            b"\x1BO",
+            b"\x1B[A",
+            b"\x1B[1;5A",
+            b"\x1B[5;10;1",
+            b"\x1B[5R",
+            b"\x1B[;10R",
+            b"\x1B[99999;1R",
+            b"\x1B[111111111111",
+        ];
+
+        for bytes in cases.iter() {
+            assert_eq!(parse_position(bytes), Response::NoMatch, "{:?}", bytes);
+        }
+    }
+
+    #[test]
+    fn clipboard_content_is_returned_encoded() {
+        assert_eq!(parse_clipboard(b"\x1B]52;c;Zm9v\x07"), Response::Complete(b"Zm9v".to_vec()));
+        assert_eq!(parse_clipboard(b"\x1B]52;c;Zm9v\x1B\\"), Response::Complete(b"Zm9v".to_vec()));
+        assert_eq!(parse_clipboard(b"\x1B]52;s;Zg==\x07"), Response::Complete(b"Zg==".to_vec()));
+        assert_eq!(parse_clipboard(b"\x1B]52;p;\x07"), Response::Complete(Vec::new()));
+    }
+
+    #[test]
+    fn clipboard_prefixes_are_incomplete() {
+        let report = b"\x1B]52;c;Zm9v+/==\x1B\\";
+
+        for length in 0..report.len() {
+            assert_eq!(parse_clipboard(&report[..length]), Response::Incomplete, "{:?}", &report[..length]);
+        }
+    }
+
+    #[test]
+    fn other_input_is_no_clipboard() {
+        let cases: [&[u8]; 6] = [
+            b"a",
+            b"\x1B[5;10R",
+            b"\x1B]53;c;Zm9v\x07",
+            b"\x1B]52;c!",
+            b"\x1B]52;c;Zm9v!",
+            b"\x1B]52;c;Zm9v\x1Bx",
+        ];
+
+        for bytes in cases.iter() {
+            assert_eq!(parse_clipboard(bytes), Response::NoMatch, "{:?}", bytes);
+        }
+    }
+}
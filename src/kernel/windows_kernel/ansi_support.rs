@@ -13,7 +13,7 @@ pub fn try_enable_ansi_support() -> bool {
     START.call_once(|| {
         use state::commands::win_commands::EnableAnsiCommand;
         let mut command = EnableAnsiCommand::new();
-        let success = command.execute();
+        let success = command.execute().is_ok();
 
         set_is_windows_ansi_supportable(success);
         set_ansi_enabled(success);
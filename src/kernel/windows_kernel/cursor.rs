@@ -3,14 +3,13 @@
 use super::super::super::manager::{ScreenManager, WinApiScreenManager};
 use super::kernel;
 
-use std::rc::Rc;
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
 
 /// This stores the cursor pos, at program level. So it can be recalled later.
 static mut SAVED_CURSOR_POS: (u16, u16) = (0, 0);
 
 /// Reset to saved cursor position
-pub fn reset_to_saved_position(screen_manager: &Rc<Mutex<ScreenManager>>) {
+pub fn reset_to_saved_position(screen_manager: &Arc<Mutex<ScreenManager>>) {
     unsafe {
         kernel::set_console_cursor_position(
             SAVED_CURSOR_POS.0 as i16,
@@ -21,7 +20,7 @@ pub fn reset_to_saved_position(screen_manager: &Rc<Mutex<ScreenManager>>) {
 }
 
 /// Save current cursor position to recall later.
-pub fn save_cursor_pos(screen_manager: &Rc<Mutex<ScreenManager>>) {
+pub fn save_cursor_pos(screen_manager: &Arc<Mutex<ScreenManager>>) {
     let position = pos(screen_manager);
 
     unsafe {
@@ -30,7 +29,7 @@ pub fn save_cursor_pos(screen_manager: &Rc<Mutex<ScreenManager>>) {
 }
 
 /// get the current cursor position.
-pub fn pos(screen_manager: &Rc<Mutex<ScreenManager>>) -> (u16, u16) {
+pub fn pos(screen_manager: &Arc<Mutex<ScreenManager>>) -> (u16, u16) {
     let csbi = kernel::get_console_screen_buffer_info(screen_manager);
     (
         csbi.dwCursorPosition.X as u16,
@@ -0,0 +1,79 @@
+//! This module contains all `windows` specific input related logic.
+
+use super::kernel;
+
+use winapi::shared::minwindef::DWORD;
+use winapi::um::consoleapi::ReadConsoleInputW;
+use winapi::um::synchapi::WaitForSingleObject;
+use winapi::um::winbase::{INFINITE, WAIT_FAILED, WAIT_OBJECT_0};
+use winapi::um::wincon::{
+    FOCUS_EVENT, INPUT_RECORD, KEY_EVENT, KEY_EVENT_RECORD, MOUSE_EVENT, MOUSE_EVENT_RECORD,
+    WINDOW_BUFFER_SIZE_EVENT,
+};
+
+use std::time::Duration;
+use std::{io, mem};
+
+/// The console input events crossterm is interested in.
+pub enum InputRecord {
+    Key(KEY_EVENT_RECORD),
+    Mouse(MOUSE_EVENT_RECORD),
+    /// The size of the screen buffer changed, this is only reported when `ENABLE_WINDOW_INPUT` is set.
+    Resize,
+    /// The console window gained (`true`) or lost (`false`) focus.
+    Focus(bool),
+}
+
+/// Wait until the console input has events or the timeout expires, `None` waits without a timeout.
+pub fn wait_for_input(timeout: Option<Duration>) -> io::Result<bool> {
+    let timeout = match timeout {
+        Some(timeout) => {
+            let millis = timeout.as_secs() * 1000 + u64::from(timeout.subsec_nanos() / 1_000_000);
+            millis.min(u64::from(INFINITE - 1)) as DWORD
+        }
+        None => INFINITE,
+    };
+
+    match unsafe { WaitForSingleObject(kernel::get_input_handle(), timeout) } {
+        WAIT_OBJECT_0 => Ok(true),
+        WAIT_FAILED => Err(io::Error::last_os_error()),
+        _ => Ok(false),
+    }
+}
+
+/// Read one event from the console input, this call blocks when there are no events.
+///
+/// Returns `None` for events crossterm is not interested in, like releasing a key.
+pub fn read_single_input_record() -> io::Result<Option<InputRecord>> {
+    let mut record: INPUT_RECORD = unsafe { mem::zeroed() };
+    let mut events_read: DWORD = 0;
+
+    let success = unsafe {
+        ReadConsoleInputW(kernel::get_input_handle(), &mut record, 1, &mut events_read)
+    };
+
+    if success == 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    if events_read != 1 {
+        return Ok(None);
+    }
+
+    Ok(match record.EventType {
+        KEY_EVENT => {
+            let key_event = unsafe { *record.Event.KeyEvent() };
+
+            // Only key presses are reported, releasing a key is ignored.
+            if key_event.bKeyDown != 0 {
+                Some(InputRecord::Key(key_event))
+            } else {
+                None
+            }
+        }
+        MOUSE_EVENT => Some(InputRecord::Mouse(unsafe { *record.Event.MouseEvent() })),
+        WINDOW_BUFFER_SIZE_EVENT => Some(InputRecord::Resize),
+        FOCUS_EVENT => Some(InputRecord::Focus(unsafe { record.Event.FocusEvent().bSetFocus } != 0)),
+        _ => None,
+    })
+}
@@ -1,7 +1,7 @@
 //! This module is the core of all the `WINAPI` actions. All unsafe `WINAPI` function call are done here.
 //! I am planing to refactor this a little since a lot of code could be handled safer.
 
-use std::rc::Rc;
+use std::sync::Arc;
 use Context;
 
 use winapi::shared::minwindef::{FALSE, TRUE};
@@ -31,7 +31,7 @@ use super::super::super::manager::{ScreenManager, WinApiScreenManager};
 use std::sync::Mutex;
 
 /// Get the global stored handle.
-pub fn get_current_handle(screen_manager: &Rc<Mutex<ScreenManager>>) -> HANDLE {
+pub fn get_current_handle(screen_manager: &Arc<Mutex<ScreenManager>>) -> HANDLE {
     let mut mx_guard = screen_manager;
 
     let handle: HANDLE;
@@ -98,19 +98,27 @@ fn is_valid_handle(handle: &HANDLE) -> bool {
 }
 /// Create a new console screen buffer info struct.
 pub fn get_console_screen_buffer_info(
-    screen_manager: &Rc<Mutex<ScreenManager>>,
+    screen_manager: &Arc<Mutex<ScreenManager>>,
 ) -> CONSOLE_SCREEN_BUFFER_INFO {
+    match try_get_console_screen_buffer_info(screen_manager) {
+        Ok(csbi) => csbi,
+        Err(_) => panic!("Cannot get console screen buffer info"),
+    }
+}
 
+/// Create a new console screen buffer info struct, returns the error instead of panicking when that fails.
+pub fn try_get_console_screen_buffer_info(
+    screen_manager: &Arc<Mutex<ScreenManager>>,
+) -> ::std::io::Result<CONSOLE_SCREEN_BUFFER_INFO> {
     let mut csbi = CONSOLE_SCREEN_BUFFER_INFO::empty();
-    let success;
-
-    unsafe { success = GetConsoleScreenBufferInfo(get_current_handle(screen_manager), &mut csbi) }
 
-    if success == 0 {
-        panic!("Cannot get console screen buffer info");
+    unsafe {
+        if !is_true(GetConsoleScreenBufferInfo(get_current_handle(screen_manager), &mut csbi)) {
+            return Err(::std::io::Error::last_os_error());
+        }
     }
 
-    csbi
+    Ok(csbi)
 }
 
 /// Create a new console screen buffer info struct.
@@ -129,7 +137,7 @@ pub fn get_std_console_screen_buffer_info() -> CONSOLE_SCREEN_BUFFER_INFO {
 }
 
 /// Get buffer info and handle of the current screen.
-pub fn get_buffer_info_and_hande(screen_manager: &Rc<Mutex<ScreenManager>>) -> (CONSOLE_SCREEN_BUFFER_INFO, HANDLE)
+pub fn get_buffer_info_and_hande(screen_manager: &Arc<Mutex<ScreenManager>>) -> (CONSOLE_SCREEN_BUFFER_INFO, HANDLE)
 {
    let handle = get_current_handle(screen_manager);
     let csbi = get_console_screen_buffer_info_from_handle(&handle);
@@ -159,7 +167,7 @@ pub fn get_largest_console_window_size() -> COORD {
 }
 
 /// Get the original color of the terminal.
-pub fn get_original_console_color(screen_manager: &Rc<Mutex<ScreenManager>>) -> u16 {
+pub fn get_original_console_color(screen_manager: &Arc<Mutex<ScreenManager>>) -> u16 {
     let console_buffer_info = get_console_screen_buffer_info(screen_manager);
     console_buffer_info.wAttributes as u16
 }
@@ -181,7 +189,7 @@ pub fn get_console_mode(handle: &HANDLE, current_mode: &mut u32) -> bool {
 }
 
 /// Set the cursor position to the given x and y. Note that this is 0 based.
-pub fn set_console_cursor_position(x: i16, y: i16, screen_manager: &Rc<Mutex<ScreenManager>>) {
+pub fn set_console_cursor_position(x: i16, y: i16, screen_manager: &Arc<Mutex<ScreenManager>>) {
     if x < 0 || x >= <i16>::max_value() {
         panic!("X: {}, Argument Out of Range Exception", x);
     }
@@ -204,7 +212,7 @@ pub fn set_console_cursor_position(x: i16, y: i16, screen_manager: &Rc<Mutex<Scr
 }
 
 /// change the cursor visibility.
-pub fn cursor_visibility(visable: bool, screen_manager: &Rc<Mutex<ScreenManager>>) {
+pub fn cursor_visibility(visable: bool, screen_manager: &Arc<Mutex<ScreenManager>>) {
     let handle = get_current_handle(screen_manager);
 
     let cursor_info = CONSOLE_CURSOR_INFO {
@@ -218,19 +226,26 @@ pub fn cursor_visibility(visable: bool, screen_manager: &Rc<Mutex<ScreenManager>
 }
 
 /// Change the console text attribute.
-pub fn set_console_text_attribute(value: u16, screen_manager: &Rc<Mutex<ScreenManager>>) {
+pub fn set_console_text_attribute(
+    value: u16,
+    screen_manager: &Arc<Mutex<ScreenManager>>,
+) -> ::std::io::Result<()> {
     let handle = get_current_handle(screen_manager);
 
     unsafe {
-        SetConsoleTextAttribute(handle, value);
+        if !is_true(SetConsoleTextAttribute(handle, value)) {
+            return Err(::std::io::Error::last_os_error());
+        }
     }
+
+    Ok(())
 }
 
 /// Change console info.
 pub fn set_console_info(
     absolute: bool,
     rect: &SMALL_RECT,
-    screen_manager: &Rc<Mutex<ScreenManager>>,
+    screen_manager: &Arc<Mutex<ScreenManager>>,
 ) -> bool {
     let handle = get_current_handle(screen_manager);
 
@@ -247,7 +262,7 @@ pub fn set_console_info(
 /// Set the console screen buffer size
 pub fn set_console_screen_buffer_size(
     size: COORD,
-    screen_manager: &Rc<Mutex<ScreenManager>>,
+    screen_manager: &Arc<Mutex<ScreenManager>>,
 ) -> bool {
 
     let handle = get_current_handle(screen_manager);
@@ -263,7 +278,7 @@ pub fn fill_console_output_character(
     cells_written: &mut u32,
     start_location: COORD,
     cells_to_write: u32,
-    screen_manager: &Rc<Mutex<ScreenManager>>,
+    screen_manager: &Arc<Mutex<ScreenManager>>,
 ) -> bool {
 
     let handle = get_current_handle(screen_manager);
@@ -286,7 +301,7 @@ pub fn fill_console_output_attribute(
     cells_written: &mut u32,
     start_location: COORD,
     cells_to_write: u32,
-    screen_manager: &Rc<Mutex<ScreenManager>>,
+    screen_manager: &Arc<Mutex<ScreenManager>>,
 ) -> bool {
     // Get the position of the current console window
 
@@ -393,6 +408,44 @@ pub fn write_console_output(
     }
 }
 
+/// Get the title of the console window as utf16 characters.
+pub fn get_console_title() -> ::std::io::Result<Vec<u16>> {
+    use self::wincon::GetConsoleTitleW;
+
+    // The title of a console window can be at most 64K characters long.
+    let mut title: Vec<u16> = vec![0; 64 * 1024];
+
+    let length = unsafe { GetConsoleTitleW(title.as_mut_ptr(), title.len() as u32) };
+
+    if length == 0 {
+        let error = ::std::io::Error::last_os_error();
+
+        // An empty title is not an error.
+        if error.raw_os_error() != Some(0) {
+            return Err(error);
+        }
+    }
+
+    title.truncate(length as usize);
+    Ok(title)
+}
+
+/// Set the title of the console window to the given utf16 characters.
+pub fn set_console_title(title: &[u16]) -> ::std::io::Result<()> {
+    use self::wincon::SetConsoleTitleW;
+
+    let mut title = title.to_vec();
+    title.push(0);
+
+    unsafe {
+        if !is_true(SetConsoleTitleW(title.as_ptr())) {
+            return Err(::std::io::Error::last_os_error());
+        }
+    }
+
+    Ok(())
+}
+
 //use std::os::raw::c_void;
 use std::str;
 use winapi::ctypes::c_void;
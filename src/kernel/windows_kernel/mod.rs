@@ -2,6 +2,7 @@
 
 pub mod ansi_support;
 pub mod cursor;
+pub mod input;
 pub mod kernel;
 pub mod terminal;
 
@@ -8,12 +8,18 @@ mod shared;
 mod kernel;
 mod state;
 
+pub mod buffer;
+pub mod command;
 pub mod cursor;
+pub mod input;
 pub mod manager;
 pub mod style;
 pub mod terminal;
 
+pub use command::Command;
 pub use shared::crossterm::Crossterm;
+pub use shared::error::{Error, ErrorKind, Result};
+pub use shared::guard;
 pub use shared::screen;
 pub use shared::raw;
 pub use state::context::Context;
@@ -1,15 +1,24 @@
 //! This is an ANSI specific implementation for the screen manager
 //! This module is used for windows 10 terminals and unix terminals by default.
-//! This module uses the stdout to write to the console.
+//! This module uses the stdout to write to the console, unless an other output is given.
+//!
+//! By default every write is flushed directly so that it shows up on the screen.
+//! In queued mode the writes are collected in a buffer and are only written to the console when `flush()` is called,
+//! this reduces the number of system calls when drawing a lot of cells and avoids flickering.
 
 use std::any::Any;
 use std::io::{self, Write};
 
 use super::IScreenManager;
 
+/// When the queued output grows larger than this amount of bytes it is written to the console without waiting for `flush()`.
+const MAX_QUEUE_SIZE: usize = 64 * 1024;
+
 pub struct AnsiScreenManager {
     pub is_alternate_screen: bool,
-    output: Box<Write>,
+    is_queued: bool,
+    queue: Vec<u8>,
+    output: Box<Write + Send>,
 }
 
 impl IScreenManager for AnsiScreenManager {
@@ -17,23 +26,48 @@ impl IScreenManager for AnsiScreenManager {
         self.is_alternate_screen = is_alternate_screen;
     }
 
+    fn set_queued(&mut self, is_queued: bool) -> io::Result<()> {
+        self.is_queued = is_queued;
+
+        if is_queued {
+            Ok(())
+        } else {
+            self.flush()
+        }
+    }
+
+    fn is_queued(&self) -> bool {
+        self.is_queued
+    }
+
     fn write_string(&mut self, string: String) -> io::Result<usize> {
-        write!(self.output, "{}", string)?;
-        self.flush();
-        Ok(0)
+        self.write_str(&string)
     }
 
     fn write_str(&mut self, string: &str) -> io::Result<usize> {
-        write!(self.output, "{}", string)?;
-        self.flush();
+        if self.is_queued {
+            self.queue(string.as_bytes())?;
+        } else {
+            write!(self.output, "{}", string)?;
+            self.flush()?;
+        }
         Ok(0)
     }
 
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-        self.output.write(buf)
+        if self.is_queued {
+            self.queue(buf)?;
+            Ok(buf.len())
+        } else {
+            self.output.write(buf)
+        }
     }
 
     fn flush(&mut self) -> io::Result<()> {
+        if !self.queue.is_empty() {
+            self.output.write_all(&self.queue)?;
+            self.queue.clear();
+        }
         self.output.flush()
     }
 
@@ -44,9 +78,107 @@ impl IScreenManager for AnsiScreenManager {
 
 impl AnsiScreenManager {
     pub fn new() -> Self {
+        AnsiScreenManager::with_output(Box::from(io::stdout()))
+    }
+
+    /// Create a screen manager that writes to the given output instead of stdout.
+    pub fn with_output(output: Box<Write + Send>) -> Self {
         AnsiScreenManager {
-            output: (Box::from(io::stdout()) as Box<Write>),
+            output,
             is_alternate_screen: false,
+            is_queued: false,
+            queue: Vec::new(),
+        }
+    }
+
+    /// Add the given bytes to the queue, the queue is written to the console when it becomes too large.
+    fn queue(&mut self, buf: &[u8]) -> io::Result<()> {
+        self.queue.extend_from_slice(buf);
+
+        if self.queue.len() > MAX_QUEUE_SIZE {
+            self.flush()?;
+        }
+        Ok(())
+    }
+}
+
+/// Write the queued output to the console, otherwise it would get lost.
+impl Drop for AnsiScreenManager {
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AnsiScreenManager, MAX_QUEUE_SIZE};
+    use manager::IScreenManager;
+
+    use std::io::{self, Write};
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone)]
+    struct SharedOutput(Arc<Mutex<Vec<u8>>>);
+
+    impl SharedOutput {
+        fn len(&self) -> usize {
+            self.0.lock().unwrap().len()
+        }
+    }
+
+    impl Write for SharedOutput {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
         }
     }
+
+    fn screen_manager() -> (AnsiScreenManager, SharedOutput) {
+        let output = SharedOutput(Arc::new(Mutex::new(Vec::new())));
+        (AnsiScreenManager::with_output(Box::new(output.clone())), output)
+    }
+
+    #[test]
+    fn queued_output_is_written_in_order_on_flush() {
+        let (mut screen_manager, output) = screen_manager();
+        screen_manager.set_queued(true).unwrap();
+
+        screen_manager.write_str("a").unwrap();
+        IScreenManager::write(&mut screen_manager, b"b").unwrap();
+        screen_manager.write_string(String::from("c")).unwrap();
+        assert_eq!(output.len(), 0);
+
+        IScreenManager::flush(&mut screen_manager).unwrap();
+        assert_eq!(*output.0.lock().unwrap(), b"abc".to_vec());
+    }
+
+    #[test]
+    fn leaving_queued_mode_writes_the_queue() {
+        let (mut screen_manager, output) = screen_manager();
+        screen_manager.set_queued(true).unwrap();
+
+        screen_manager.write_str("queued").unwrap();
+        assert_eq!(output.len(), 0);
+
+        screen_manager.set_queued(false).unwrap();
+        screen_manager.write_str(" direct").unwrap();
+        assert_eq!(*output.0.lock().unwrap(), b"queued direct".to_vec());
+    }
+
+    #[test]
+    fn large_queue_is_written_without_flush() {
+        let (mut screen_manager, output) = screen_manager();
+        screen_manager.set_queued(true).unwrap();
+
+        let chunk = vec![b'x'; MAX_QUEUE_SIZE / 2];
+        IScreenManager::write(&mut screen_manager, &chunk).unwrap();
+        IScreenManager::write(&mut screen_manager, &chunk).unwrap();
+        assert_eq!(output.len(), 0);
+
+        IScreenManager::write(&mut screen_manager, b"y").unwrap();
+        assert_eq!(output.len(), MAX_QUEUE_SIZE + 1);
+    }
 }
@@ -14,6 +14,8 @@ use winapi::um::winnt::HANDLE;
 /// Struct that stores an specific platform implementation for screen related actions.
 pub struct ScreenManager {
     screen_manager: Box<IScreenManager>,
+    /// The output of the content, like the text of `Terminal::write`, when it differs from the output of the escape codes.
+    content: Option<Box<Write + Send>>,
 }
 
 impl ScreenManager {
@@ -30,6 +32,27 @@ impl ScreenManager {
 
         ScreenManager {
             screen_manager: screen_manager,
+            content: None,
+        }
+    }
+
+    /// Create a screen manager that writes to the given output, like a pty or a file, instead of stdout.
+    ///
+    /// The output is written with ANSI escape codes, also on windows.
+    pub fn with_output(output: Box<Write + Send>) -> ScreenManager {
+        ScreenManager {
+            screen_manager: Box::from(AnsiScreenManager::with_output(output)),
+            content: None,
+        }
+    }
+
+    /// Create a screen manager that writes the escape codes to the given output and the content to stdout.
+    ///
+    /// This is used when stdout is redirected, the escape codes then go to the terminal and the content into the pipe or file.
+    pub fn with_content_to_stdout(output: Box<Write + Send>) -> ScreenManager {
+        ScreenManager {
+            screen_manager: Box::from(AnsiScreenManager::with_output(output)),
+            content: Some(Box::from(io::stdout())),
         }
     }
 
@@ -39,6 +62,51 @@ impl ScreenManager {
             .toggle_is_alternate_screen(is_alternate_screen);
     }
 
+    /// Turn queued mode on or off.
+    ///
+    /// In queued mode the output of the cursor, color and terminal actions is collected in a buffer
+    /// and only written to the terminal when `flush()` is called or when the buffer becomes too large.
+    /// Turning queued mode off writes the queued output. By default the output is written directly.
+    ///
+    /// Note that this only has effect on terminals that support ANSI escape codes,
+    /// the actions that use the WinApi are always performed directly.
+    ///
+    /// #Example
+    ///
+    /// ```rust,no_run
+    ///
+    /// extern crate crossterm;
+    /// use self::crossterm::Context;
+    /// use self::crossterm::cursor::cursor;
+    ///
+    /// use std::io::Write;
+    ///
+    /// let context = Context::new();
+    /// context.screen_manager.lock().unwrap().set_queued(true).unwrap();
+    ///
+    /// let mut cursor = cursor(&context);
+    /// for y in 0..10 {
+    ///     cursor.goto(0, y).unwrap().print("@").unwrap();
+    /// }
+    ///
+    /// // The whole frame is written at once.
+    /// context.screen_manager.lock().unwrap().flush().unwrap();
+    ///
+    /// ```
+    pub fn set_queued(&mut self, is_queued: bool) -> io::Result<()> {
+        if !is_queued {
+            if let Some(ref mut content) = self.content {
+                content.flush()?;
+            }
+        }
+        self.screen_manager.set_queued(is_queued)
+    }
+
+    /// Check whether the output is queued until `flush()` is called.
+    pub fn is_queued(&self) -> bool {
+        self.screen_manager.is_queued()
+    }
+
     /// Write an ANSI code as String.
     pub fn write_string(&mut self, string: String) -> io::Result<usize> {
         self.screen_manager.write_string(string)
@@ -50,6 +118,25 @@ impl ScreenManager {
         self.screen_manager.write_str(string)
     }
 
+    /// Write content, like text, instead of an ANSI code.
+    ///
+    /// The content goes to stdout when the screen manager is created with `with_content_to_stdout`,
+    /// otherwise it is written like an ANSI code.
+    pub fn write_content(&mut self, string: &str) -> io::Result<()> {
+        match self.content {
+            Some(ref mut content) => {
+                content.write_all(string.as_bytes())?;
+
+                if self.screen_manager.is_queued() {
+                    Ok(())
+                } else {
+                    content.flush()
+                }
+            }
+            None => self.screen_manager.write_str(string).map(|_| ()),
+        }
+    }
+
     /// Can be used to get an specific implementation used for the current platform.
     pub fn as_any(&mut self) -> &mut Any {
         self.screen_manager.as_any()
@@ -62,6 +149,9 @@ impl Write for ScreenManager {
     }
 
     fn flush(&mut self) -> io::Result<()> {
+        if let Some(ref mut content) = self.content {
+            content.flush()?;
+        }
         self.screen_manager.flush()
     }
 }
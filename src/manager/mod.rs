@@ -32,9 +32,13 @@ pub use self::manager::ScreenManager;
 use std::any::Any;
 use std::io;
 
-pub trait IScreenManager {
+pub trait IScreenManager: Send {
     /// Toggle the value if alternatescreen is on.
     fn toggle_is_alternate_screen(&mut self, is_alternate_screen: bool);
+    /// Turn queued mode on or off, turning it off writes the queued output.
+    fn set_queued(&mut self, is_queued: bool) -> io::Result<()>;
+    /// Check whether the output is queued until `flush()` is called.
+    fn is_queued(&self) -> bool;
     /// Write ansi code as String to the current stdout.
     fn write_string(&mut self, string: String) -> io::Result<usize>;
     /// Write a &str to the current stdout.
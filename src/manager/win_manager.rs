@@ -5,7 +5,7 @@ use winapi::um::winnt::HANDLE;
 
 use std::any::Any;
 use std::io::{self, Write};
-use std::rc::Rc;
+use std::sync::Arc;
 
 pub struct WinApiScreenManager {
     pub is_alternate_screen: bool,
@@ -13,11 +13,25 @@ pub struct WinApiScreenManager {
     alternate_handle: HANDLE,
 }
 
+// The console handles belong to the process and can be used from any thread,
+// the screen manager is only accessed through the mutex in the `Context`.
+unsafe impl Send for WinApiScreenManager {}
+
 impl IScreenManager for WinApiScreenManager {
     fn toggle_is_alternate_screen(&mut self, is_alternate_screen: bool) {
         self.is_alternate_screen = is_alternate_screen;
     }
 
+    // The winapi calls for moving the cursor and coloring are not written to the output,
+    // so the output can not be queued without mixing up the order of the actions.
+    fn set_queued(&mut self, _is_queued: bool) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn is_queued(&self) -> bool {
+        false
+    }
+
     fn write_string(&mut self, string: String) -> io::Result<usize>
     {
         self.write(string.as_bytes())
@@ -0,0 +1,144 @@
+//! This module contains the base64 encoding that is used for the clipboard, the terminal sends and receives the clipboard as base64.
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Encode the given bytes as base64 with padding.
+pub fn encode(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+
+    for chunk in bytes.chunks(3) {
+        let b0 = chunk[0] as u32;
+        let b1 = chunk.get(1).map_or(0, |b| *b as u32);
+        let b2 = chunk.get(2).map_or(0, |b| *b as u32);
+        let triple = (b0 << 16) | (b1 << 8) | b2;
+
+        encoded.push(ALPHABET[(triple >> 18) as usize & 63] as char);
+        encoded.push(ALPHABET[(triple >> 12) as usize & 63] as char);
+
+        if chunk.len() > 1 {
+            encoded.push(ALPHABET[(triple >> 6) as usize & 63] as char);
+        } else {
+            encoded.push('=');
+        }
+
+        if chunk.len() > 2 {
+            encoded.push(ALPHABET[triple as usize & 63] as char);
+        } else {
+            encoded.push('=');
+        }
+    }
+
+    encoded
+}
+
+/// Decode the given base64, the padding is optional. Returns `None` when the input is not valid base64.
+pub fn decode(encoded: &[u8]) -> Option<Vec<u8>> {
+    let mut encoded = encoded;
+    while let Some((&b'=', rest)) = encoded.split_last() {
+        encoded = rest;
+    }
+
+    // A single character can not hold a whole byte.
+    if encoded.len() % 4 == 1 {
+        return None;
+    }
+
+    let mut decoded = Vec::with_capacity(encoded.len() / 4 * 3 + 2);
+
+    for chunk in encoded.chunks(4) {
+        let mut quad = 0u32;
+
+        for (i, byte) in chunk.iter().enumerate() {
+            quad |= (decode_char(*byte)? as u32) << (18 - 6 * i);
+        }
+
+        decoded.push((quad >> 16) as u8);
+
+        if chunk.len() > 2 {
+            decoded.push((quad >> 8) as u8);
+        }
+
+        if chunk.len() > 3 {
+            decoded.push(quad as u8);
+        }
+    }
+
+    Some(decoded)
+}
+
+fn decode_char(byte: u8) -> Option<u8> {
+    match byte {
+        b'A'..=b'Z' => Some(byte - b'A'),
+        b'a'..=b'z' => Some(byte - b'a' + 26),
+        b'0'..=b'9' => Some(byte - b'0' + 52),
+        b'+' => Some(62),
+        b'/' => Some(63),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode, encode};
+
+    /// The test vectors of RFC 4648.
+    const VECTORS: [(&str, &str); 7] = [
+        ("", ""),
+        ("f", "Zg=="),
+        ("fo", "Zm8="),
+        ("foo", "Zm9v"),
+        ("foob", "Zm9vYg=="),
+        ("fooba", "Zm9vYmE="),
+        ("foobar", "Zm9vYmFy"),
+    ];
+
+    #[test]
+    fn encode_vectors() {
+        for &(decoded, encoded) in VECTORS.iter() {
+            assert_eq!(encode(decoded.as_bytes()), encoded);
+        }
+    }
+
+    #[test]
+    fn decode_vectors() {
+        for &(decoded, encoded) in VECTORS.iter() {
+            assert_eq!(decode(encoded.as_bytes()), Some(decoded.as_bytes().to_vec()));
+        }
+    }
+
+    #[test]
+    fn decode_without_padding() {
+        for &(decoded, encoded) in VECTORS.iter() {
+            let unpadded = encoded.trim_end_matches('=');
+            assert_eq!(decode(unpadded.as_bytes()), Some(decoded.as_bytes().to_vec()));
+        }
+    }
+
+    #[test]
+    fn all_bytes_round_trip() {
+        let bytes: Vec<u8> = (0..=255u8).collect();
+        let encoded = encode(&bytes);
+
+        assert!(encoded.contains('+') && encoded.contains('/'));
+        assert_eq!(decode(encoded.as_bytes()), Some(bytes));
+    }
+
+    #[test]
+    fn decode_invalid_input() {
+        let cases: [&[u8]; 6] = [
+            // A single character in the last group can not hold a whole byte.
+            b"Z",
+            b"Zm9vY",
+            // Characters outside the alphabet.
+            b"Zm9v!A==",
+            b"Zm 9v",
+            // Padding in the middle.
+            b"Zg==Zg==",
+            "Zm9vé".as_bytes(),
+        ];
+
+        for bytes in cases.iter() {
+            assert_eq!(decode(bytes), None, "{:?}", bytes);
+        }
+    }
+}
@@ -25,13 +25,13 @@
 
 
 use super::super::cursor;
+use super::super::input;
 use super::super::style;
 use super::super::terminal::terminal;
-use Context;
+use {Context, Result};
 
 use std::fmt::Display;
 use std::mem;
-use std::rc::Rc;
 use std::sync::Arc;
 use std::convert::From;
 
@@ -46,13 +46,13 @@ use std::convert::From;
 ///      let cursor = crossterm.cursor();
 ///      let terminal = crossterm.terminal();
 pub struct Crossterm {
-    context: Rc<Context>
+    context: Arc<Context>
 }
 
 /// Create `Crossterm` instance from `Context`
-impl From<Rc<Context>> for Crossterm
+impl From<Arc<Context>> for Crossterm
 {
-    fn from(context: Rc<Context>) -> Self {
+    fn from(context: Arc<Context>) -> Self {
         return Crossterm {
             context: context
         }
@@ -105,6 +105,27 @@ impl Crossterm {
         return cursor::TerminalCursor::new(self.context.clone())
     }
 
+    /// Get an TerminalInput implementation whereon input related actions can be performed.
+    ///
+    /// #Example
+    ///
+    /// ```rust,no_run
+    ///
+    /// extern crate crossterm;
+    /// use crossterm::Crossterm;
+    ///
+    /// let crossterm = Crossterm::new();
+    /// let input = crossterm.input();
+    ///
+    /// // wait for the user to press a key.
+    /// let key = input.read_key();
+    ///
+    /// ```
+    pub fn input(&self) -> input::TerminalInput
+    {
+        return input::TerminalInput::new(self.context.clone());
+    }
+
     /// Get an Color implementation whereon color related actions can be performed.
     ///
     /// Check `/examples/version/color` in the library for more specific examples.
@@ -128,6 +149,8 @@ impl Crossterm {
 
     /// Wraps an displayable object so it can be formatted with colors and attributes.
     ///
+    /// The colors and attributes are left out when the styling policy disables them, see `style::StylePolicy`.
+    ///
     /// Check `/examples/color` in the library for more specific examples.
     ///
     /// #Example
@@ -172,13 +195,13 @@ impl Crossterm {
     /// crossterm.write("Some text \n Some text on new line.");
     ///
     /// ```
-    pub fn write<D: Display>(&self, value: D)
+    pub fn write<D: Display>(&self, value: D) -> Result<()>
     {
         self.terminal().write(value)
     }
 
     /// Get an copy of the context that `Crossterm` uses internally.
-    pub fn context(&self) -> Rc<Context> {
+    pub fn context(&self) -> Arc<Context> {
         self.context.clone()
     }
 }
\ No newline at end of file
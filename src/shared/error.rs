@@ -0,0 +1,109 @@
+//! This module contains the error type that is returned by the operations of crossterm.
+//!
+//! Writing to the terminal can fail because of a broken pipe, changing the terminal mode can fail because there is no terminal at all.
+//! These errors are returned to the caller so that the program can decide what to do, instead of being ignored or ending in a panic.
+
+use std::error;
+use std::fmt;
+use std::io;
+
+/// The result of the operations of crossterm.
+pub type Result<T> = ::std::result::Result<T, Error>;
+
+/// The kind of error that occurred.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// Writing to or reading from the terminal failed, for example because the output is a broken pipe.
+    Io,
+    /// The operation needs a terminal but the output or input is not a terminal.
+    NotATerminal,
+    /// A terminal state, like raw mode or the alternate screen, could not be changed.
+    StateChange,
+    /// The terminal did not answer a query, like the request for the cursor position, in time.
+    Timeout,
+}
+
+/// The error type of crossterm.
+#[derive(Debug)]
+pub struct Error {
+    kind: ErrorKind,
+    message: String,
+    source: Option<io::Error>,
+}
+
+impl Error {
+    /// Create a new error of the given kind with a message that describes what failed.
+    pub fn new(kind: ErrorKind, message: &str) -> Error {
+        Error {
+            kind,
+            message: message.to_string(),
+            source: None,
+        }
+    }
+
+    /// Create a new error of the given kind that is caused by the given io error.
+    pub fn with_source(kind: ErrorKind, message: &str, source: io::Error) -> Error {
+        Error {
+            kind,
+            message: message.to_string(),
+            source: Some(source),
+        }
+    }
+
+    /// Get the kind of this error.
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.source {
+            Some(ref source) => write!(f, "{}: {}", self.message, source),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        self.source.as_ref().map(|source| source as &(dyn error::Error + 'static))
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Error {
+        let kind = if is_not_a_terminal(&error) {
+            ErrorKind::NotATerminal
+        } else {
+            ErrorKind::Io
+        };
+
+        Error::with_source(kind, "terminal io failed", error)
+    }
+}
+
+/// An `Error` can be turned into an `io::Error`, so that `?` can be used in functions that return an `io::Result`.
+impl From<Error> for io::Error {
+    fn from(error: Error) -> io::Error {
+        match error {
+            Error {
+                kind: ErrorKind::Io,
+                source: Some(source),
+                ..
+            } => source,
+            error => io::Error::other(error),
+        }
+    }
+}
+
+#[cfg(unix)]
+fn is_not_a_terminal(error: &io::Error) -> bool {
+    error.raw_os_error() == Some(::libc::ENOTTY)
+}
+
+#[cfg(windows)]
+fn is_not_a_terminal(error: &io::Error) -> bool {
+    // ERROR_INVALID_HANDLE, the console functions fail with this error when there is no console.
+    error.raw_os_error() == Some(6)
+}
@@ -1,9 +1,13 @@
 //! Some actions need to preformed platform independently since they can not be solved `ANSI escape codes`.
 
-use std::rc::Rc;
-use std::sync::Mutex;
-use Context;
-use ScreenManager;
+use std::env;
+use std::fmt::Display;
+use std::sync::Arc;
+use std::time::Duration;
+use {Context, Result};
+
+#[cfg(windows)]
+use manager::AnsiScreenManager;
 
 #[cfg(windows)]
 use kernel::windows_kernel::terminal::{exit, terminal_size};
@@ -11,25 +15,42 @@ use kernel::windows_kernel::terminal::{exit, terminal_size};
 #[cfg(windows)]
 use kernel::windows_kernel::cursor::pos;
 
+#[cfg(windows)]
+use kernel::windows_kernel::kernel::{get_console_mode, get_output_handle};
+
+#[cfg(unix)]
+use kernel::unix_kernel::terminal::{exit, is_tty as is_tty_fd, pos, size_fd, terminal_size};
 #[cfg(unix)]
-use kernel::unix_kernel::terminal::{exit, pos, terminal_size};
+use libc::STDOUT_FILENO;
 
 /// Get the terminal size based on the current platform.
-pub fn get_terminal_size(screen_manager: &Rc<Mutex<ScreenManager>>) -> (u16, u16) {
+pub fn get_terminal_size(context: &Context) -> Result<(u16, u16)> {
+    #[cfg(unix)]
+    return Ok(terminal_size(size_fd(context))?);
+
+    #[cfg(windows)]
+    return Ok(terminal_size(&context.screen_manager));
+}
+
+/// Get the cursor position based on the current platform, the terminal answers within the timeout on unix systems.
+pub fn get_cursor_position(context: Arc<Context>, timeout: Duration) -> Result<(u16, u16)> {
     #[cfg(unix)]
-    return terminal_size();
+    return pos(&context, timeout);
 
     #[cfg(windows)]
-    return terminal_size(screen_manager);
+    return Ok(pos(&context.screen_manager));
 }
 
-/// Get the cursor position based on the current platform.
-pub fn get_cursor_position(context: Rc<Context>) -> (u16, u16) {
+/// Check whether stdout is connected to a terminal based on the current platform.
+pub fn is_tty() -> bool {
     #[cfg(unix)]
-    return pos(context.clone());
+    return is_tty_fd(STDOUT_FILENO);
 
     #[cfg(windows)]
-    return pos(&context.screen_manager);
+    return {
+        let mut mode = 0;
+        get_console_mode(&get_output_handle(), &mut mode)
+    };
 }
 
 /// exit the current terminal.
@@ -68,3 +89,80 @@ pub fn get_module<T>(winapi_impl: T, unix_impl: T) -> Option<T> {
 
     term
 }
+
+#[cfg(windows)]
+/// Get an module specific implementation for the given context.
+/// When the context writes to an other output than the console, like a pty or a file, the ansi implementation is used
+/// because the winapi implementation can only change the console. Otherwise this is the same as `get_module`.
+pub fn get_module_for_context<T>(context: &Context, winapi_impl: T, unix_impl: T) -> Option<T> {
+    if writes_ansi(context) {
+        Some(unix_impl)
+    } else {
+        get_module(winapi_impl, unix_impl)
+    }
+}
+
+#[cfg(windows)]
+/// Check whether the given context writes ANSI escape codes, the winapi screen manager writes to the console directly.
+pub fn writes_ansi(context: &Context) -> bool {
+    context
+        .screen_manager
+        .lock()
+        .unwrap()
+        .as_any()
+        .is::<AnsiScreenManager>()
+}
+
+#[cfg(unix)]
+/// Check whether the given context writes ANSI escape codes, this is always the case on unix systems.
+pub fn writes_ansi(_context: &Context) -> bool {
+    true
+}
+
+/// Check whether the terminal of the given context can show hyperlinks.
+///
+/// Terminals can not be asked whether they support hyperlinks, so the environment variables the terminals set are checked.
+/// `FORCE_HYPERLINK=1` and `FORCE_HYPERLINK=0` override the detection.
+pub fn supports_hyperlinks(context: &Context) -> bool {
+    if let Ok(force) = env::var("FORCE_HYPERLINK") {
+        return force != "0";
+    }
+
+    if !writes_ansi(context) {
+        return false;
+    }
+
+    // Windows Terminal, DomTerm and Konsole.
+    if env::var_os("WT_SESSION").is_some()
+        || env::var_os("DOMTERM").is_some()
+        || env::var_os("KONSOLE_VERSION").is_some()
+    {
+        return true;
+    }
+
+    // Terminals based on VTE, like GNOME Terminal, support hyperlinks since VTE 0.50.
+    if let Ok(version) = env::var("VTE_VERSION") {
+        if version.parse::<u32>().map(|version| version >= 5000).unwrap_or(false) {
+            return true;
+        }
+    }
+
+    if let Ok(program) = env::var("TERM_PROGRAM") {
+        match program.as_str() {
+            "iTerm.app" | "WezTerm" | "vscode" | "Hyper" | "ghostty" => return true,
+            _ => {}
+        }
+    }
+
+    match env::var("TERM") {
+        Ok(term) => {
+            term.contains("kitty") || term.contains("alacritty") || term.starts_with("foot") || term.contains("ghostty")
+        }
+        Err(_) => false,
+    }
+}
+
+/// Remove the control characters from the given value, they would end an escape code that contains the value early.
+pub fn without_control_chars<D: Display>(value: &D) -> String {
+    value.to_string().chars().filter(|c| !c.is_control()).collect()
+}
@@ -0,0 +1,283 @@
+//! With this module you can make sure that the terminal is restored when the program does not end normally.
+//!
+//! The changes made to the terminal are undone when the `Context` is dropped,
+//! but when the program panics the panic message is printed before that happens, which is lost on the alternate screen.
+//! And when the program is terminated by a signal the `Context` is not dropped at all,
+//! which leaves the shell of the user in raw mode without echo.
+//!
+//! A `TerminalGuard` installs a panic hook and handlers for `SIGINT`, `SIGTERM` and `SIGHUP` that restore the terminal:
+//! raw mode is disabled, the main screen is shown, the cursor is shown and the colors are reset.
+//! After that the default behaviour continues, the panic message is printed or the process is terminated.
+//!
+//! Only a panic on the main thread restores the terminal, since a panic on an other thread does not end the program.
+//! The main thread is recognised by its name `main`, so a panic in a test, which runs on a thread named after the test, does not restore the terminal.
+//!
+//! It also handles `SIGTSTP`, which is sent when `Ctrl-Z` is pressed outside of raw mode or by `kill -TSTP`.
+//! The changes are undone while the process is stopped and made again when it continues,
+//! after that a resize is reported (see `Event::Resize` and `Terminal::resize_events`) so that the screen can be drawn again.
+
+#[cfg(not(target_os = "windows"))]
+use kernel::unix_kernel::signal;
+#[cfg(not(target_os = "windows"))]
+use libc::SIGTSTP;
+#[cfg(target_os = "windows")]
+use kernel::windows_kernel::kernel;
+use manager::AnsiScreenManager;
+use {Context, Result};
+
+use std::io::{self, Write};
+use std::panic;
+use std::sync::{Arc, Mutex, MutexGuard, Once, TryLockError};
+use std::thread;
+
+/// The contexts that are restored when the program panics or is terminated, together with the number of guards of each context.
+static GUARDED_CONTEXTS: Mutex<Vec<(Arc<Context>, usize)>> = Mutex::new(Vec::new());
+
+static INSTALL_PANIC_HOOK: Once = Once::new();
+#[cfg(not(target_os = "windows"))]
+static INSTALL_SIGNAL_HANDLERS: Once = Once::new();
+
+/// A guard that restores the terminal of the given context when the program panics or is terminated by a signal.
+///
+/// The guard is opt-in, while it is alive the panic hook and the signal handlers restore the terminal.
+/// Signals are only handled on unix systems.
+///
+/// Only a panic on the main thread restores the terminal, a panic on an other thread does not end the program.
+/// Note that a panic on the main thread that is caught with `catch_unwind` restores the terminal as well.
+pub struct TerminalGuard {
+    context: Arc<Context>,
+}
+
+impl TerminalGuard {
+    /// Guard the terminal of the given context, the panic hook and the signal handlers are installed the first time this is called.
+    ///
+    /// A context can be guarded more than once, it is guarded until all its guards are dropped.
+    ///
+    /// #Example
+    ///
+    /// ```rust,no_run
+    ///
+    /// extern crate crossterm;
+    /// use crossterm::Context;
+    /// use crossterm::guard::TerminalGuard;
+    /// use crossterm::screen::AlternateScreen;
+    ///
+    /// let context = Context::new();
+    /// let _guard = TerminalGuard::new(context.clone()).unwrap();
+    ///
+    /// let screen = AlternateScreen::from(context.clone()).unwrap();
+    ///
+    /// // the panic message is printed on the main screen.
+    /// panic!("something went wrong");
+    ///
+    /// ```
+    pub fn new(context: Arc<Context>) -> Result<TerminalGuard> {
+        install_panic_hook();
+
+        #[cfg(not(target_os = "windows"))]
+        install_signal_handlers()?;
+
+        {
+            let mut contexts = lock(&GUARDED_CONTEXTS);
+
+            match contexts.iter_mut().find(|guarded| Arc::ptr_eq(&guarded.0, &context)) {
+                Some(guarded) => guarded.1 += 1,
+                None => contexts.push((context.clone(), 1)),
+            }
+        }
+
+        Ok(TerminalGuard { context })
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let mut contexts = lock(&GUARDED_CONTEXTS);
+
+        let index = contexts
+            .iter()
+            .position(|guarded| Arc::ptr_eq(&guarded.0, &self.context));
+
+        if let Some(index) = index {
+            contexts[index].1 -= 1;
+
+            if contexts[index].1 == 0 {
+                contexts.remove(index);
+            }
+        }
+    }
+}
+
+/// Install the panic hook that restores the guarded contexts before the previous hook prints the panic message,
+/// when the main thread panics.
+fn install_panic_hook() {
+    INSTALL_PANIC_HOOK.call_once(|| {
+        let previous_hook = panic::take_hook();
+
+        panic::set_hook(Box::new(move |info| {
+            if thread::current().name() == Some("main") {
+                // The panicking thread could hold one of the locks of a context, waiting for it would never end.
+                restore_guarded_contexts(false);
+            }
+
+            previous_hook(info);
+        }));
+    });
+}
+
+/// Install the signal handlers and start the thread that restores or suspends the guarded contexts when a signal is received.
+#[cfg(not(target_os = "windows"))]
+fn install_signal_handlers() -> io::Result<()> {
+    let mut result = Ok(());
+
+    INSTALL_SIGNAL_HANDLERS.call_once(|| {
+        let fd = match signal::install_handlers() {
+            Ok(fd) => fd,
+            Err(e) => {
+                result = Err(e);
+                return;
+            }
+        };
+
+        thread::spawn(move || loop {
+            match signal::wait_for_signal(fd) {
+                Ok(SIGTSTP) => suspend_guarded_contexts(),
+                Ok(signal) => {
+                    restore_guarded_contexts(true);
+                    signal::raise_with_previous_handler(signal);
+                }
+                Err(_) => {}
+            }
+        });
+    });
+
+    result
+}
+
+/// Undo the changes of all guarded contexts, stop the process and make the changes again when the process continues.
+#[cfg(not(target_os = "windows"))]
+fn suspend_guarded_contexts() {
+    // The contexts are cloned so that no lock is held while the process is stopped.
+    let contexts: Vec<Arc<Context>> = lock(&GUARDED_CONTEXTS)
+        .iter()
+        .map(|guarded| guarded.0.clone())
+        .collect();
+
+    for context in contexts.iter().rev() {
+        lock(&context.state_manager).suspend_changes();
+
+        let mut screen_manager = lock(&context.screen_manager);
+        // Show the cursor and reset the colors for the shell, the process is stopped regardless of errors.
+        let _ = screen_manager.write_str(concat!(csi!("?25h"), csi!("0m")));
+        let _ = screen_manager.flush();
+    }
+
+    let _ = signal::stop_process();
+
+    for context in contexts.iter() {
+        let _ = lock(&context.state_manager).resume_changes();
+        let _ = lock(&context.screen_manager).flush();
+    }
+
+    signal::raise_resize();
+}
+
+/// Restore the terminal of all guarded contexts, the last guarded context is restored first.
+///
+/// When `wait` is false the contexts whereof a lock is held are skipped.
+fn restore_guarded_contexts(wait: bool) {
+    let contexts = match GUARDED_CONTEXTS.try_lock() {
+        Ok(contexts) => contexts,
+        Err(TryLockError::Poisoned(poisoned)) => poisoned.into_inner(),
+        Err(TryLockError::WouldBlock) if wait => lock(&GUARDED_CONTEXTS),
+        Err(TryLockError::WouldBlock) => return,
+    };
+
+    for guarded in contexts.iter().rev() {
+        restore(&guarded.0, wait);
+    }
+}
+
+/// Undo all state changes of the context, show the cursor and reset the colors.
+fn restore(context: &Context, wait: bool) {
+    if let Some(mut state_manager) = try_lock(&context.state_manager, wait) {
+        state_manager.restore_changes();
+    }
+
+    if let Some(mut screen_manager) = try_lock(&context.screen_manager, wait) {
+        if screen_manager.as_any().is::<AnsiScreenManager>() {
+            // Nothing can be done when the terminal can not be written to, the other contexts are restored regardless.
+            let _ = screen_manager.write_str(concat!(csi!("?25h"), csi!("0m")));
+            let _ = screen_manager.flush();
+        } else {
+            // The kernel functions lock the screen manager themselves.
+            drop(screen_manager);
+
+            #[cfg(target_os = "windows")]
+            reset_console(context);
+        }
+    }
+}
+
+/// Show the cursor and reset the colors of the windows console.
+#[cfg(target_os = "windows")]
+fn reset_console(context: &Context) {
+    use winapi::um::wincon::{FOREGROUND_BLUE, FOREGROUND_GREEN, FOREGROUND_RED};
+
+    kernel::cursor_visibility(true, &context.screen_manager);
+    let _ = kernel::set_console_text_attribute(
+        FOREGROUND_RED | FOREGROUND_GREEN | FOREGROUND_BLUE,
+        &context.screen_manager,
+    );
+}
+
+/// Lock the mutex, a poisoned mutex is used anyway since the terminal should be restored regardless.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    match mutex.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+/// Lock the mutex, or only try to lock it when `wait` is false.
+fn try_lock<T>(mutex: &Mutex<T>, wait: bool) -> Option<MutexGuard<'_, T>> {
+    if wait {
+        return Some(lock(mutex));
+    }
+
+    match mutex.try_lock() {
+        Ok(guard) => Some(guard),
+        Err(TryLockError::Poisoned(poisoned)) => Some(poisoned.into_inner()),
+        Err(TryLockError::WouldBlock) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TerminalGuard, GUARDED_CONTEXTS};
+    use Context;
+
+    use std::sync::Arc;
+
+    fn guard_count(context: &Arc<Context>) -> Option<usize> {
+        super::lock(&GUARDED_CONTEXTS)
+            .iter()
+            .find(|guarded| Arc::ptr_eq(&guarded.0, context))
+            .map(|guarded| guarded.1)
+    }
+
+    #[test]
+    fn context_is_guarded_once_until_all_guards_are_dropped() {
+        let context = Context::with_output(Box::new(Vec::new()));
+
+        let first = TerminalGuard::new(context.clone()).unwrap();
+        let second = TerminalGuard::new(context.clone()).unwrap();
+        assert_eq!(guard_count(&context), Some(2));
+
+        drop(first);
+        assert_eq!(guard_count(&context), Some(1));
+
+        drop(second);
+        assert_eq!(guard_count(&context), None);
+    }
+}
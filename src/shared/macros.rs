@@ -3,3 +3,111 @@
 macro_rules! csi {
     ($( $l:expr ),*) => { concat!("\x1B[", $( $l ),*) };
 }
+
+/// This macro will take the input of an operating system command and wraps it in `ESC ]` and the string terminator `ESC \`.
+#[macro_export]
+macro_rules! osc {
+    ($( $l:expr ),*) => { concat!("\x1B]", $( $l ),*, "\x1B\\") };
+}
+
+/// Write the ANSI escape codes of the given commands to the given writer, without flushing the writer.
+///
+/// The writer can be anything that implements `std::io::Write`, like `stdout()` or the `ScreenManager`.
+/// Stops at the first command that can not be written and returns its error.
+///
+/// ```rust,no_run
+/// #[macro_use]
+/// extern crate crossterm;
+///
+/// use crossterm::command::{Goto, Print};
+/// use std::io::{stdout, Write};
+///
+/// fn main() {
+///     let mut stdout = stdout();
+///     queue!(stdout, Goto(10, 10), Print("@")).unwrap();
+///     stdout.flush().unwrap();
+/// }
+/// ```
+#[macro_export]
+macro_rules! queue {
+    ($writer:expr $(, $command:expr)* $(,)*) => {{
+        use ::std::io::Write;
+
+        let writer = &mut $writer;
+        let mut result: ::std::io::Result<()> = Ok(());
+        $(
+            if result.is_ok() {
+                result = write!(writer, "{}", $crate::Command::ansi_code(&$command));
+            }
+        )*
+        result
+    }};
+}
+
+/// Write the ANSI escape codes of the given commands to the given writer and flush the writer.
+///
+/// This is the same as `queue!` followed by `flush()`.
+///
+/// ```rust,no_run
+/// #[macro_use]
+/// extern crate crossterm;
+///
+/// use crossterm::command::{Clear, ClearType, Goto};
+/// use std::io::stdout;
+///
+/// fn main() {
+///     execute!(stdout(), Clear(ClearType::All), Goto(0, 0)).unwrap();
+/// }
+/// ```
+#[macro_export]
+macro_rules! execute {
+    ($writer:expr $(, $command:expr)* $(,)*) => {{
+        use ::std::io::Write;
+
+        let writer = &mut $writer;
+        let mut result: ::std::io::Result<()> = queue!(*writer $(, $command)*);
+        if result.is_ok() {
+            result = writer.flush();
+        }
+        result
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use command::{Goto, Print, SetScrollRegion};
+    use Context;
+
+    use std::io::{self, Write};
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone)]
+    struct SharedOutput(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedOutput {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn queue_writes_on_flush_and_execute_flushes() {
+        let output = SharedOutput(Arc::new(Mutex::new(Vec::new())));
+        let context = Context::with_output(Box::new(output.clone()));
+        let mut screen = context.screen_manager.lock().unwrap();
+        screen.set_queued(true).unwrap();
+
+        queue!(*screen, Goto(2, 3), Print("@")).unwrap();
+        assert!(output.0.lock().unwrap().is_empty());
+
+        execute!(*screen, Goto(u16::MAX, u16::MAX), SetScrollRegion(0, u16::MAX)).unwrap();
+        assert_eq!(
+            *output.0.lock().unwrap(),
+            b"\x1B[4;3H@\x1B[65535;65535H\x1B[1;65535r".to_vec()
+        );
+    }
+}
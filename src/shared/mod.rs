@@ -2,9 +2,12 @@
 
 #[macro_use]
 pub mod macros;
+pub mod base64;
 pub mod crossterm;
+pub mod error;
 pub mod functions;
 pub mod traits;
 
+pub mod guard;
 pub mod screen;
 pub mod raw;
@@ -14,43 +14,48 @@
 
 #[cfg(not(windows))]
 use super::super::state::commands::unix_command::EnableRawModeCommand;
+#[cfg(not(windows))]
+use kernel::unix_kernel::terminal;
 #[cfg(windows)]
 use state::commands::win_commands::EnableRawModeCommand;
 
 use state::commands::IStateCommand;
-use {CommandManager, Context};
+use {CommandManager, Context, Result};
 
 use std::io::{self, Write};
-use std::rc::Rc;
+use std::sync::Arc;
 
 /// A wrapper for the raw terminal state. Which can be used to write to.
 pub struct RawTerminal {
-    context: Rc<Context>,
-    command_id: u16,
+    context: Arc<Context>,
+    command_id: u32,
 }
 
 /// Trait withs contains a method for switching into raw mode.
 pub trait IntoRawMode: Write + Sized {
-    fn into_raw_mode(&self, context: Rc<Context>) -> io::Result<RawTerminal>;
+    fn into_raw_mode(&self, context: Arc<Context>) -> Result<RawTerminal>;
 }
 
 impl<W: Write> IntoRawMode for W {
     /// Raw mode means that input (stdin) won't be printed it will instead have to be written manually by
     /// the program. The input isn't canonicalised or line buffered (that is, you can
     /// read from input(stdin) one byte of a time).
-    fn into_raw_mode(&self, context: Rc<Context>) -> io::Result<RawTerminal> {
+    ///
+    /// An error is returned when the terminal mode could not be changed, for example when the input is not a terminal.
+    fn into_raw_mode(&self, context: Arc<Context>) -> Result<RawTerminal> {
+        #[cfg(not(windows))]
+        let command_id = EnableRawModeCommand::new(&context.state_manager, terminal::input_fd(&context));
+        #[cfg(windows)]
         let command_id = EnableRawModeCommand::new(&context.state_manager);
 
-        let success = CommandManager::execute(context.clone(), command_id);
+        // The command is removed again when enabling raw mode fails, because the `RawTerminal` is dropped then.
+        let raw_terminal = RawTerminal {
+            context: context.clone(),
+            command_id: command_id,
+        };
 
-        if success {
-            Ok(RawTerminal {
-                context: context.clone(),
-                command_id: command_id,
-            })
-        } else {
-            panic!("cannot move into raw mode")
-        }
+        CommandManager::execute(context.clone(), command_id)?;
+        Ok(raw_terminal)
     }
 }
 
@@ -73,6 +78,6 @@ impl Write for RawTerminal {
 /// If an instance of `RawTerminal` will be dropped all terminal changes that are made will be undone.
 impl Drop for RawTerminal {
     fn drop(&mut self) {
-        let success = CommandManager::undo(self.context.clone(), self.command_id);
+        let _ = CommandManager::remove(self.context.clone(), self.command_id);
     }
 }
@@ -23,7 +23,7 @@
 //!     // create context.
 //!     let context = crossterm::Context::new();
 //!     // create instance of Alternatescreen by the given context, this wil also switch to it.
-//!     let mut screen = crossterm::AlternateScreen::from(context.clone());
+//!     let mut screen = crossterm::AlternateScreen::from(context.clone()).unwrap();
 //!     // write to the alternate screen.
 //!     write!(screen,  "test");
 //!
@@ -32,7 +32,7 @@
 //!     // create crossterm.
 //!     let crossterm = ::crossterm::Crossterm::new();
 //!     // create instance of Alternatescreen by the given refrence to crossterm, this wil also switch to it.
-//!     let mut screen = crossterm::AlternateScreen::from(&crossterm);
+//!     let mut screen = crossterm::AlternateScreen::from(crossterm.context()).unwrap();
 //!     // write to the alternate screen.
 //!     write!(screen,  "test");
 //!
@@ -56,7 +56,7 @@
 //!     cursor.goto(10,10);
 //!
 //!     // create instance of Alternatescreen by the given refrence to crossterm, this wil also switch to it.
-//!     let mut screen = crossterm::AlternateScreen::from(&context);
+//!     let mut screen = crossterm::AlternateScreen::from(context.clone()).unwrap();
 //!
 //!     // now this cursor will be moving on the alternate screen sice it is using the same context as we have passed to the alternatescreen.
 //!     cursor.goto(5,4)
@@ -75,7 +75,7 @@
 //!      cursor.goto(10,10);
 //!
 //!      // create instance of Alternatescreen by the given refrence to crossterm, this wil also switch to it.
-//!      let mut screen = crossterm::AlternateScreen::from(&crossterm);
+//!      let mut screen = crossterm::AlternateScreen::from(crossterm.context()).unwrap();
 //!
 //!      // this cursor will be moving on the alternate screen since the current screen is the alternate screen.
 //!      let mut cursor = crossterm.cursor();
@@ -85,58 +85,40 @@
 
 use shared::functions;
 use state::commands::*;
-use Context;
+use {CommandManager, Context, Result};
 
 use std::io::{self, Write};
-use std::rc::Rc;
-use std::convert::From;
+use std::sync::Arc;
 
 pub struct AlternateScreen {
-    context: Rc<Context>,
-    command_id: u16,
+    context: Arc<Context>,
+    command_id: u32,
 }
 
 impl AlternateScreen {
     /// Get the alternate screen from the context.
     /// By calling this method the current screen will be changed to the alternate screen.
     /// And you get back an handle for that screen.
-    pub fn from(context: Rc<Context>) -> Self {
+    pub fn from(context: Arc<Context>) -> Result<Self> {
         let command_id = get_to_alternate_screen_command(context.clone());
 
+        // When switching fails the command is removed again when the screen is dropped.
         let screen = AlternateScreen {
             context: context.clone(),
             command_id: command_id,
         };
-        screen.to_alternate();
-        return screen;
+        screen.to_alternate()?;
+        Ok(screen)
     }
 
     /// Change the current screen to the mainscreen.
-    pub fn to_main(&self) {
-        let mut mutex = &self.context.state_manager;
-        {
-            let mut state_manager = mutex.lock().unwrap();
-
-            let mut mx = &state_manager.get(self.command_id);
-            {
-                let mut command = mx.lock().unwrap();
-                command.undo();
-            }
-        }
+    pub fn to_main(&self) -> Result<()> {
+        CommandManager::undo(self.context.clone(), self.command_id)
     }
 
     /// Change the current screen to alternate screen.
-    pub fn to_alternate(&self) {
-        let mut mutex = &self.context.state_manager;
-        {
-            let mut state_manager = mutex.lock().unwrap();
-
-            let mut mx = &state_manager.get(self.command_id);
-            {
-                let mut command = mx.lock().unwrap();
-                command.execute();
-            }
-        }
+    pub fn to_alternate(&self) -> Result<()> {
+        CommandManager::execute(self.context.clone(), self.command_id)
     }
 }
 
@@ -158,37 +140,20 @@ impl Write for AlternateScreen {
 
 impl Drop for AlternateScreen {
     fn drop(&mut self) {
-        use CommandManager;
-        CommandManager::undo(self.context.clone(), self.command_id);
-    }
-}
-
-use super::super::shared::crossterm::Crossterm;
-
-impl From<Crossterm> for AlternateScreen
-{
-    fn from(crossterm: Crossterm) -> Self {
-        let command_id = get_to_alternate_screen_command(crossterm.context());
-
-        let screen = AlternateScreen {
-            context: crossterm.context(),
-            command_id: command_id,
-        };
-        screen.to_alternate();
-        return screen;
+        let _ = CommandManager::remove(self.context.clone(), self.command_id);
     }
 }
 
 // Get the alternate screen command to enable and disable alternate screen based on the current platform
-fn get_to_alternate_screen_command(context: Rc<Context>) -> u16 {
+fn get_to_alternate_screen_command(context: Arc<Context>) -> u32 {
     #[cfg(target_os = "windows")]
-    let command_id = functions::get_module::<u16>(
-        win_commands::ToAlternateScreenBufferCommand::new(context.clone()),
-        shared_commands::ToAlternateScreenBufferCommand::new(context.clone()),
+    let command_id = functions::get_module::<u32>(
+        win_commands::ToAlternateScreenBufferCommand::new(&context),
+        shared_commands::ToAlternateScreenBufferCommand::new(&context),
     ).unwrap();
 
     #[cfg(not(target_os = "windows"))]
-    let command_id = shared_commands::ToAlternateScreenBufferCommand::new(context.clone());
+    let command_id = shared_commands::ToAlternateScreenBufferCommand::new(&context);
 
     return command_id;
 }
@@ -1,39 +1,27 @@
 //! A wrapper for executing and undoing commands.
 
-use super::commands::IStateCommand;
-use std::rc::Rc;
-use std::sync::Mutex;
-use Context;
+use std::sync::Arc;
+use {Context, Result};
 
 /// Simple wrapper for executing an command.
 pub struct CommandManager;
 
 impl CommandManager {
     /// execute an certain command by id.
-    pub fn execute(context: Rc<Context>, command_id: u16) -> bool {
-        let mut mutex: Rc<Mutex<Box<IStateCommand>>>;
-
+    pub fn execute(context: Arc<Context>, command_id: u32) -> Result<()> {
         let mut state = context.state_manager.lock().unwrap();
-        {
-            mutex = state.get(command_id);
-        }
-
-        let mut command = mutex.lock().unwrap();
-        let has_succeeded = command.execute();
-        return has_succeeded;
+        state.execute(command_id)
     }
 
     /// undo an certain command by id.
-    pub fn undo(context: Rc<Context>, command_id: u16) -> bool {
-        let mut mutex: Rc<Mutex<Box<IStateCommand>>>;
-
+    pub fn undo(context: Arc<Context>, command_id: u32) -> Result<()> {
         let mut state = context.state_manager.lock().unwrap();
-        {
-            mutex = state.get(command_id);
-        }
+        state.undo(command_id)
+    }
 
-        let mut command = mutex.lock().unwrap();
-        let has_succeeded = command.undo();
-        return has_succeeded;
+    /// remove an certain command by id, it is undone when it is still active.
+    pub fn remove(context: Arc<Context>, command_id: u32) -> Result<()> {
+        let mut state = context.state_manager.lock().unwrap();
+        state.remove(command_id)
     }
 }
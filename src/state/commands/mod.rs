@@ -9,8 +9,7 @@
 //!
 //! See the `StateManager` struct where we store the commands for more info.
 
-use std::rc::Rc;
-use std::sync::Mutex;
+use Result;
 
 #[cfg(unix)]
 pub mod unix_command;
@@ -27,7 +26,7 @@ pub use self::win_commands::*;
 
 /// This command is used for complex commands whits change the terminal state.
 /// By passing an `Context` instance this command will register it self to notify the terminal state change.
-pub trait IStateCommand {
-    fn execute(&mut self) -> bool;
-    fn undo(&mut self) -> bool;
+pub trait IStateCommand: Send + Sync {
+    fn execute(&mut self) -> Result<()>;
+    fn undo(&mut self) -> Result<()>;
 }
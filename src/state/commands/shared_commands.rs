@@ -1,57 +1,561 @@
 //! This module contains the commands that can be used for both unix and windows systems. Or else said terminals that support ansi codes.
+
+// The commands register themselves, `new` returns the id of the registered command.
+#![allow(clippy::new_ret_no_self)]
+
 use super::IStateCommand;
-use Context;
+use cursor::commands::{Hide, ResetShape, SetShape, Show};
+use cursor::CursorShape;
+use terminal::commands::{PopTitle, PushTitle, ResetScrollRegion, SetScrollRegion};
+use {Command, Context, Result, ScreenManager};
 
-use std::rc::Rc;
+use std::sync::{Arc, Mutex};
 
+/// This command changes nothing, it is used for features that the windows console does not support.
+#[cfg(target_os = "windows")]
 pub struct EmptyCommand;
 
+#[cfg(target_os = "windows")]
 impl IStateCommand for EmptyCommand {
-    fn execute(&mut self) -> bool {
-        return false;
+    fn execute(&mut self) -> Result<()> {
+        Ok(())
     }
 
-    fn undo(&mut self) -> bool {
-        return false;
+    fn undo(&mut self) -> Result<()> {
+        Ok(())
     }
 }
 
 /// This command is used for switching to alternate screen and back to main screen.
 pub struct ToAlternateScreenBufferCommand {
-    context: Rc<Context>,
+    screen_manager: Arc<Mutex<ScreenManager>>,
 }
 
 impl ToAlternateScreenBufferCommand {
-    pub fn new(context: Rc<Context>) -> u16 {
+    pub fn new(context: &Context) -> u32 {
         let mut state = context.state_manager.lock().unwrap();
         {
-            let key = state.get_changes_count();
             let command = ToAlternateScreenBufferCommand {
-                context: context.clone(),
+                screen_manager: context.screen_manager.clone(),
             };
 
-            state.register_change(Box::from(command), key);
-            key
+            state.register_change(Box::from(command))
         }
     }
 }
 
 impl IStateCommand for ToAlternateScreenBufferCommand {
-    fn execute(&mut self) -> bool {
-        let mut screen = self.context.screen_manager.lock().unwrap();
+    fn execute(&mut self) -> Result<()> {
+        let mut screen = self.screen_manager.lock().unwrap();
         {
-            screen.write_str(csi!("?1049h"));
+            screen.write_str(csi!("?1049h"))?;
             screen.toggle_is_alternate_screen(true);
-            return true;
+            Ok(())
         }
     }
 
-    fn undo(&mut self) -> bool {
-        let mut screen = self.context.screen_manager.lock().unwrap();
+    fn undo(&mut self) -> Result<()> {
+        let mut screen = self.screen_manager.lock().unwrap();
         {
-            screen.write_str(csi!("?1049l"));
+            screen.write_str(csi!("?1049l"))?;
             screen.toggle_is_alternate_screen(false);
-            return true;
+            Ok(())
+        }
+    }
+}
+
+/// This command is used for enabling and disabling the reporting of mouse events.
+///
+/// The terminal is asked to report presses and releases (`?1000`), drags (`?1002`) and all movement (`?1003`).
+/// The urxvt (`?1015`) and SGR (`?1006`) encodings are enabled as well so that coordinates larger than 223 can be reported,
+/// terminals that do not know these encodings ignore them and fall back to the X10 encoding.
+pub struct EnableMouseCaptureCommand {
+    screen_manager: Arc<Mutex<ScreenManager>>,
+}
+
+impl EnableMouseCaptureCommand {
+    pub fn new(context: &Context) -> u32 {
+        let mut state = context.state_manager.lock().unwrap();
+        {
+            let command = EnableMouseCaptureCommand {
+                screen_manager: context.screen_manager.clone(),
+            };
+
+            state.register_change(Box::from(command))
+        }
+    }
+}
+
+impl IStateCommand for EnableMouseCaptureCommand {
+    fn execute(&mut self) -> Result<()> {
+        let mut screen = self.screen_manager.lock().unwrap();
+        {
+            screen
+                .write_str(concat!(
+                    csi!("?1000h"),
+                    csi!("?1002h"),
+                    csi!("?1003h"),
+                    csi!("?1015h"),
+                    csi!("?1006h")
+                ))?;
+            Ok(())
+        }
+    }
+
+    fn undo(&mut self) -> Result<()> {
+        let mut screen = self.screen_manager.lock().unwrap();
+        {
+            screen
+                .write_str(concat!(
+                    csi!("?1006l"),
+                    csi!("?1015l"),
+                    csi!("?1003l"),
+                    csi!("?1002l"),
+                    csi!("?1000l")
+                ))?;
+            Ok(())
+        }
+    }
+}
+
+/// This command is used for enabling and disabling bracketed paste mode.
+///
+/// In this mode the terminal wraps pasted text in `ESC [ 200 ~` and `ESC [ 201 ~`,
+/// so that the pasted text can be told apart from typed keys.
+pub struct EnableBracketedPasteCommand {
+    screen_manager: Arc<Mutex<ScreenManager>>,
+}
+
+impl EnableBracketedPasteCommand {
+    pub fn new(context: &Context) -> u32 {
+        let mut state = context.state_manager.lock().unwrap();
+        {
+            let command = EnableBracketedPasteCommand {
+                screen_manager: context.screen_manager.clone(),
+            };
+
+            state.register_change(Box::from(command))
+        }
+    }
+}
+
+impl IStateCommand for EnableBracketedPasteCommand {
+    fn execute(&mut self) -> Result<()> {
+        let mut screen = self.screen_manager.lock().unwrap();
+        {
+            screen.write_str(csi!("?2004h"))?;
+            Ok(())
+        }
+    }
+
+    fn undo(&mut self) -> Result<()> {
+        let mut screen = self.screen_manager.lock().unwrap();
+        {
+            screen.write_str(csi!("?2004l"))?;
+            Ok(())
+        }
+    }
+}
+
+/// This command is used for enabling and disabling the reporting of focus changes.
+///
+/// When enabled the terminal sends `ESC [ I` when its window gains focus and `ESC [ O` when it loses focus.
+pub struct EnableFocusReportingCommand {
+    screen_manager: Arc<Mutex<ScreenManager>>,
+}
+
+impl EnableFocusReportingCommand {
+    pub fn new(context: &Context) -> u32 {
+        let mut state = context.state_manager.lock().unwrap();
+        {
+            let command = EnableFocusReportingCommand {
+                screen_manager: context.screen_manager.clone(),
+            };
+
+            state.register_change(Box::from(command))
+        }
+    }
+}
+
+impl IStateCommand for EnableFocusReportingCommand {
+    fn execute(&mut self) -> Result<()> {
+        let mut screen = self.screen_manager.lock().unwrap();
+        {
+            screen.write_str(csi!("?1004h"))?;
+            Ok(())
+        }
+    }
+
+    fn undo(&mut self) -> Result<()> {
+        let mut screen = self.screen_manager.lock().unwrap();
+        {
+            screen.write_str(csi!("?1004l"))?;
+            Ok(())
+        }
+    }
+}
+
+/// This command is used for hiding the cursor and showing it again.
+///
+/// Every hide takes the place of the previous hide command, showing the cursor removes the command.
+pub struct HideCursorCommand {
+    screen_manager: Arc<Mutex<ScreenManager>>,
+}
+
+impl HideCursorCommand {
+    /// The name the command is registered under, see `StateManager::replace_named_change`.
+    pub const NAME: &'static str = "hide_cursor";
+
+    /// Register and execute the command, it replaces the previous hide command.
+    pub fn new(context: &Context) -> Result<u32> {
+        let mut state = context.state_manager.lock().unwrap();
+        {
+            let command = HideCursorCommand {
+                screen_manager: context.screen_manager.clone(),
+            };
+
+            state.replace_named_change(Self::NAME, Box::from(command))
+        }
+    }
+}
+
+impl IStateCommand for HideCursorCommand {
+    fn execute(&mut self) -> Result<()> {
+        let mut screen = self.screen_manager.lock().unwrap();
+        {
+            screen.write_string(Hide.ansi_code())?;
+            Ok(())
+        }
+    }
+
+    fn undo(&mut self) -> Result<()> {
+        let mut screen = self.screen_manager.lock().unwrap();
+        {
+            screen.write_string(Show.ansi_code())?;
+            Ok(())
+        }
+    }
+}
+
+/// This command is used for setting the shape of the cursor and resetting it.
+///
+/// Every shape that is set takes the place of the previous shape command, so that the last shape is set again after a suspend.
+/// Terminals can not report the current shape, so the default shape of the terminal is restored.
+pub struct CursorShapeCommand {
+    screen_manager: Arc<Mutex<ScreenManager>>,
+    shape: CursorShape,
+}
+
+impl CursorShapeCommand {
+    /// The name the command is registered under, see `StateManager::replace_named_change`.
+    pub const NAME: &'static str = "cursor_shape";
+
+    /// Register and execute the command, it replaces the previous shape command.
+    pub fn new(context: &Context, shape: CursorShape) -> Result<u32> {
+        let mut state = context.state_manager.lock().unwrap();
+        {
+            let command = CursorShapeCommand {
+                screen_manager: context.screen_manager.clone(),
+                shape,
+            };
+
+            state.replace_named_change(Self::NAME, Box::from(command))
+        }
+    }
+}
+
+impl IStateCommand for CursorShapeCommand {
+    fn execute(&mut self) -> Result<()> {
+        let mut screen = self.screen_manager.lock().unwrap();
+        {
+            screen.write_string(SetShape(self.shape).ansi_code())?;
+            Ok(())
+        }
+    }
+
+    fn undo(&mut self) -> Result<()> {
+        let mut screen = self.screen_manager.lock().unwrap();
+        {
+            screen.write_string(ResetShape.ansi_code())?;
+            Ok(())
         }
     }
 }
+
+/// This command is used for setting the scroll region and resetting it, so that the whole screen scrolls again.
+///
+/// Every region that is set takes the place of the previous region command, so that the last region is set again after a suspend.
+pub struct ScrollRegionCommand {
+    screen_manager: Arc<Mutex<ScreenManager>>,
+    top: u16,
+    bottom: u16,
+}
+
+impl ScrollRegionCommand {
+    /// The name the command is registered under, see `StateManager::replace_named_change`.
+    pub const NAME: &'static str = "scroll_region";
+
+    /// Register and execute the command, it replaces the previous region command.
+    pub fn new(context: &Context, top: u16, bottom: u16) -> Result<u32> {
+        let mut state = context.state_manager.lock().unwrap();
+        {
+            let command = ScrollRegionCommand {
+                screen_manager: context.screen_manager.clone(),
+                top,
+                bottom,
+            };
+
+            state.replace_named_change(Self::NAME, Box::from(command))
+        }
+    }
+}
+
+impl IStateCommand for ScrollRegionCommand {
+    fn execute(&mut self) -> Result<()> {
+        let mut screen = self.screen_manager.lock().unwrap();
+        {
+            screen.write_string(SetScrollRegion(self.top, self.bottom).ansi_code())?;
+            Ok(())
+        }
+    }
+
+    fn undo(&mut self) -> Result<()> {
+        let mut screen = self.screen_manager.lock().unwrap();
+        {
+            screen.write_string(ResetScrollRegion.ansi_code())?;
+            Ok(())
+        }
+    }
+}
+
+/// This command is used for saving the window title and restoring it afterwards.
+///
+/// The title and icon name are pushed on the title stack of the terminal and popped again on undo,
+/// terminals that do not have a title stack ignore both.
+pub struct TitleStackCommand {
+    screen_manager: Arc<Mutex<ScreenManager>>,
+}
+
+impl TitleStackCommand {
+    /// The name the command is registered under, see `StateManager::replace_named_change`.
+    pub const NAME: &'static str = "title_stack";
+
+    /// Register and execute the command the first time, afterwards the id of the registered command is returned.
+    pub fn new(context: &Context) -> Result<u32> {
+        let mut state = context.state_manager.lock().unwrap();
+
+        if let Some(id) = state.named_change(Self::NAME) {
+            return Ok(id);
+        }
+
+        let command = TitleStackCommand {
+            screen_manager: context.screen_manager.clone(),
+        };
+
+        state.replace_named_change(Self::NAME, Box::from(command))
+    }
+}
+
+impl IStateCommand for TitleStackCommand {
+    fn execute(&mut self) -> Result<()> {
+        let mut screen = self.screen_manager.lock().unwrap();
+        {
+            screen.write_string(PushTitle.ansi_code())?;
+            Ok(())
+        }
+    }
+
+    fn undo(&mut self) -> Result<()> {
+        let mut screen = self.screen_manager.lock().unwrap();
+        {
+            screen.write_string(PopTitle.ansi_code())?;
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EnableBracketedPasteCommand, EnableMouseCaptureCommand, ScrollRegionCommand};
+    use cursor::{cursor, CursorShape};
+    use terminal::terminal;
+    use {CommandManager, Context};
+
+    use std::io::{self, Write};
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone)]
+    struct SharedOutput(Arc<Mutex<Vec<u8>>>);
+
+    impl SharedOutput {
+        fn take(&self) -> String {
+            let bytes = ::std::mem::take(&mut *self.0.lock().unwrap());
+            String::from_utf8(bytes).unwrap()
+        }
+    }
+
+    impl Write for SharedOutput {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn context() -> (Arc<Context>, SharedOutput) {
+        let output = SharedOutput(Arc::new(Mutex::new(Vec::new())));
+        (Context::with_output(Box::new(output.clone())), output)
+    }
+
+    fn suspend_and_resume(context: &Context) {
+        let mut state = context.state_manager.lock().unwrap();
+        state.suspend_changes();
+        state.resume_changes().unwrap();
+    }
+
+    #[test]
+    fn cursor_shape_is_set_again_on_resume() {
+        let (context, output) = context();
+        let cursor = cursor(&context);
+
+        cursor.set_shape(CursorShape::BlinkingBar).unwrap();
+        cursor.set_shape(CursorShape::SteadyUnderline).unwrap();
+        assert_eq!(output.take(), "\x1B[5 q\x1B[4 q");
+
+        suspend_and_resume(&context);
+        assert_eq!(output.take(), "\x1B[0 q\x1B[4 q");
+
+        context.state_manager.lock().unwrap().restore_changes();
+        assert_eq!(output.take(), "\x1B[0 q");
+    }
+
+    #[test]
+    fn dropping_the_context_restores_the_changes() {
+        let (context, output) = context();
+
+        let mouse_capture = EnableMouseCaptureCommand::new(&context);
+        CommandManager::execute(context.clone(), mouse_capture).unwrap();
+        let bracketed_paste = EnableBracketedPasteCommand::new(&context);
+        CommandManager::execute(context.clone(), bracketed_paste).unwrap();
+        output.take();
+
+        // The last activated change is undone first.
+        drop(context);
+        assert_eq!(
+            output.take(),
+            "\x1B[?2004l\x1B[?1006l\x1B[?1015l\x1B[?1003l\x1B[?1002l\x1B[?1000l"
+        );
+    }
+
+    #[test]
+    fn dropping_the_context_resets_the_cursor_shape() {
+        let (context, output) = context();
+
+        cursor(&context).set_shape(CursorShape::BlinkingBlock).unwrap();
+        assert_eq!(output.take(), "\x1B[1 q");
+
+        drop(context);
+        assert_eq!(output.take(), "\x1B[0 q");
+    }
+
+    #[test]
+    fn scroll_region_is_set_again_on_resume() {
+        let (context, output) = context();
+        let terminal = terminal(&context);
+
+        terminal.set_scroll_region(1, 10).unwrap();
+        terminal.set_scroll_region(2, 20).unwrap();
+        assert_eq!(output.take(), "\x1B[2;11r\x1B[3;21r");
+
+        suspend_and_resume(&context);
+        assert_eq!(output.take(), "\x1B[r\x1B[3;21r");
+    }
+
+    #[test]
+    fn dropping_the_context_resets_the_scroll_region() {
+        let (context, output) = context();
+
+        terminal(&context).set_scroll_region(1, 10).unwrap();
+        assert_eq!(output.take(), "\x1B[2;11r");
+
+        drop(context);
+        assert_eq!(output.take(), "\x1B[r");
+    }
+
+    #[test]
+    fn dropping_the_context_restores_the_title() {
+        let (context, output) = context();
+        let terminal = terminal(&context);
+
+        terminal.set_title("first").unwrap();
+        terminal.set_title("second").unwrap();
+        assert!(output.take().starts_with("\x1B[22;0t\x1B]"));
+
+        drop(terminal);
+        drop(context);
+        assert_eq!(output.take(), "\x1B[23;0t");
+    }
+
+    #[test]
+    fn reset_scroll_region_undoes_the_command() {
+        let (context, output) = context();
+        let terminal = terminal(&context);
+
+        terminal.set_scroll_region(1, 10).unwrap();
+        terminal.reset_scroll_region().unwrap();
+        assert_eq!(output.take(), "\x1B[2;11r\x1B[r");
+        assert_eq!(
+            context.state_manager.lock().unwrap().named_change(ScrollRegionCommand::NAME),
+            None
+        );
+
+        // Nothing is left to undo or to make again.
+        suspend_and_resume(&context);
+        context.state_manager.lock().unwrap().restore_changes();
+        assert_eq!(output.take(), "");
+
+        // Without a region the reset is still written.
+        terminal.reset_scroll_region().unwrap();
+        assert_eq!(output.take(), "\x1B[r");
+
+        terminal.set_scroll_region(0, 5).unwrap();
+        assert_eq!(output.take(), "\x1B[1;6r");
+        context.state_manager.lock().unwrap().restore_changes();
+        assert_eq!(output.take(), "\x1B[r");
+    }
+
+    #[test]
+    fn hidden_cursor_is_hidden_again_on_resume() {
+        let (context, output) = context();
+        let cursor = cursor(&context);
+
+        cursor.hide().unwrap();
+        cursor.hide().unwrap();
+        assert_eq!(output.take(), "\x1B[?25l\x1B[?25l");
+
+        suspend_and_resume(&context);
+        assert_eq!(output.take(), "\x1B[?25h\x1B[?25l");
+
+        cursor.show().unwrap();
+        assert_eq!(output.take(), "\x1B[?25h");
+
+        // A shown cursor stays shown.
+        suspend_and_resume(&context);
+        assert_eq!(output.take(), "");
+    }
+
+    #[test]
+    fn dropping_the_context_shows_the_cursor() {
+        let (context, output) = context();
+        let cursor = cursor(&context);
+
+        cursor.hide().unwrap();
+        assert_eq!(output.take(), "\x1B[?25l");
+
+        drop(cursor);
+        drop(context);
+        assert_eq!(output.take(), "\x1B[?25h");
+    }
+}
@@ -2,64 +2,54 @@
 
 use super::IStateCommand;
 use kernel::unix_kernel::terminal;
-use termios::{tcsetattr, Termios, CREAD, ECHO, ICANON, TCSAFLUSH};
-use {CommandManager, Context, StateManager};
+use termios::{tcsetattr, Termios, ECHO, ICANON, TCSANOW};
+use {Error, ErrorKind, Result, StateManager};
 
-const FD_STDIN: ::std::os::unix::io::RawFd = 1;
-
-use std::rc::Rc;
+use std::os::unix::io::RawFd;
 use std::sync::Mutex;
 
 /// This command is used for switching to NoncanonicalMode.
 #[derive(Copy, Clone)]
 pub struct NoncanonicalModeCommand {
-    key: u16,
+    original_mode: Option<Termios>,
+    fd: RawFd,
 }
 
 impl NoncanonicalModeCommand {
-    pub fn new(state_manager: &Mutex<StateManager>) -> u16 {
+    pub fn new(state_manager: &Mutex<StateManager>, fd: RawFd) -> u32 {
         let mut state = state_manager.lock().unwrap();
         {
-            let key = state.get_changes_count();
-            let command = NoncanonicalModeCommand { key: key };
+            let command = NoncanonicalModeCommand {
+                original_mode: None,
+                fd: fd,
+            };
 
-            state.register_change(Box::from(command), key);
-            key
+            state.register_change(Box::from(command))
         }
     }
 }
 
 impl IStateCommand for NoncanonicalModeCommand {
-    fn execute(&mut self) -> bool {
-        // Set noncanonical mode
-        if let Ok(orig) = Termios::from_fd(FD_STDIN) {
-            let mut noncan = orig.clone();
-            noncan.c_lflag &= !ICANON;
-            noncan.c_lflag &= !ECHO;
-            noncan.c_lflag &= !CREAD;
-            match tcsetattr(FD_STDIN, TCSAFLUSH, &noncan) {
-                Ok(_) => return true,
-                Err(_) => return false,
-            };
-        } else {
-            return false;
-        }
+    fn execute(&mut self) -> Result<()> {
+        // Set noncanonical mode, the mode is changed right away so that input that is waiting is not discarded.
+        let orig = Termios::from_fd(self.fd)?;
+        let mut noncan = orig.clone();
+        noncan.c_lflag &= !ICANON;
+        noncan.c_lflag &= !ECHO;
+        tcsetattr(self.fd, TCSANOW, &noncan)?;
+
+        self.original_mode = Some(orig);
+        Ok(())
     }
 
-    fn undo(&mut self) -> bool {
-        // Disable noncanonical mode
-        if let Ok(orig) = Termios::from_fd(FD_STDIN) {
-            let mut noncan = orig.clone();
-            noncan.c_lflag &= ICANON;
-            noncan.c_lflag &= ECHO;
-            noncan.c_lflag &= CREAD;
-
-            match tcsetattr(FD_STDIN, TCSAFLUSH, &noncan) {
-                Ok(_) => return true,
-                Err(_) => return false,
-            };
-        } else {
-            return false;
+    fn undo(&mut self) -> Result<()> {
+        // Disable noncanonical mode by restoring the mode the terminal had before.
+        match self.original_mode {
+            Some(ref original_mode) => {
+                tcsetattr(self.fd, TCSANOW, original_mode)?;
+                Ok(())
+            }
+            None => Err(Error::new(ErrorKind::StateChange, "noncanonical mode is not enabled")),
         }
     }
 }
@@ -67,50 +57,42 @@ impl IStateCommand for NoncanonicalModeCommand {
 /// This command is used for enabling and disabling raw mode for the terminal.
 pub struct EnableRawModeCommand {
     original_mode: Option<Box<Termios>>,
-    command_id: u16,
+    fd: RawFd,
 }
 
 impl EnableRawModeCommand {
-    pub fn new(state_manager: &Mutex<StateManager>) -> u16 {
+    pub fn new(state_manager: &Mutex<StateManager>, fd: RawFd) -> u32 {
         let mut state = state_manager.lock().unwrap();
         {
-            let key = state.get_changes_count();
             let command = EnableRawModeCommand {
                 original_mode: None,
-                command_id: key,
+                fd: fd,
             };
 
-            state.register_change(Box::from(command), key);
-            key
+            state.register_change(Box::from(command))
         }
     }
 }
 
 impl IStateCommand for EnableRawModeCommand {
-    fn execute(&mut self) -> bool {
-        let original_mode = terminal::get_terminal_mode();
+    fn execute(&mut self) -> Result<()> {
+        let original_mode = terminal::get_terminal_mode(self.fd)?;
 
-        if let Ok(original_mode) = original_mode {
-            self.original_mode = Some(Box::from(original_mode));
-            let mut new_mode = original_mode;
-            terminal::make_raw(&mut new_mode);
-            terminal::set_terminal_mode(&new_mode);
-            true
-        } else {
-            return false;
-        }
-    }
+        let mut new_mode = original_mode;
+        terminal::make_raw(&mut new_mode);
+        terminal::set_terminal_mode(self.fd, &new_mode)?;
 
-    fn undo(&mut self) -> bool {
-        if let Some(ref original_mode) = self.original_mode {
-            let result = terminal::set_terminal_mode(&original_mode);
+        self.original_mode = Some(Box::from(original_mode));
+        Ok(())
+    }
 
-            match result {
-                Ok(()) => true,
-                Err(_) => false,
+    fn undo(&mut self) -> Result<()> {
+        match self.original_mode {
+            Some(ref original_mode) => {
+                terminal::set_terminal_mode(self.fd, &original_mode)?;
+                Ok(())
             }
-        } else {
-            return false;
+            None => Err(Error::new(ErrorKind::StateChange, "raw mode is not enabled")),
         }
     }
 }
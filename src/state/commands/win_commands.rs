@@ -1,16 +1,16 @@
 //! This module contains the commands that can be used for windows systems.
 
 use super::IStateCommand;
-use {Context, StateManager};
+use {Context, Error, ErrorKind, Result, ScreenManager, StateManager};
 
 use kernel::windows_kernel::{ansi_support, kernel};
+use std::io;
 use std::mem;
 use winapi::shared::minwindef::DWORD;
 use winapi::um::wincon;
 use winapi::um::wincon::{CHAR_INFO, COORD, ENABLE_VIRTUAL_TERMINAL_PROCESSING, SMALL_RECT};
 
-use std::rc::Rc;
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
 
 /// This command is used for enabling and disabling ANSI code support for windows systems,
 /// For more info check: https://docs.microsoft.com/en-us/windows/console/console-virtual-terminal-sequences.
@@ -29,43 +29,50 @@ impl EnableAnsiCommand {
 }
 
 impl IStateCommand for EnableAnsiCommand {
-    fn execute(&mut self) -> bool {
+    fn execute(&mut self) -> Result<()> {
         // we need to check whether we tried to enable ansi before. If we have we can just return if that had succeeded.
         if ansi_support::has_been_tried_to_enable_ansi() && ansi_support::ansi_enabled() {
-            return ansi_support::windows_supportable();
+            if ansi_support::windows_supportable() {
+                return Ok(());
+            }
+
+            return Err(Error::new(
+                ErrorKind::StateChange,
+                "ANSI escape codes are not supported",
+            ));
         } else {
             let output_handle = kernel::get_output_handle();
 
             let mut dw_mode: DWORD = 0;
             if !kernel::get_console_mode(&output_handle, &mut dw_mode) {
-                return false;
+                return Err(io::Error::last_os_error().into());
             }
 
             dw_mode |= self.mask;
             if !kernel::set_console_mode(&output_handle, dw_mode) {
-                return false;
+                return Err(io::Error::last_os_error().into());
             }
-            return true;
+            return Ok(());
         }
     }
 
-    fn undo(&mut self) -> bool {
+    fn undo(&mut self) -> Result<()> {
         if ansi_support::ansi_enabled() {
             let output_handle = kernel::get_output_handle();
 
             let mut dw_mode: DWORD = 0;
             if !kernel::get_console_mode(&output_handle, &mut dw_mode) {
-                return false;
+                return Err(io::Error::last_os_error().into());
             }
 
             dw_mode &= !self.mask;
             if !kernel::set_console_mode(&output_handle, dw_mode) {
-                return false;
+                return Err(io::Error::last_os_error().into());
             }
 
             ansi_support::set_ansi_enabled(false);
         }
-        return true;
+        Ok(())
     }
 }
 
@@ -74,85 +81,135 @@ impl IStateCommand for EnableAnsiCommand {
 #[derive(Clone, Copy)]
 pub struct EnableRawModeCommand {
     mask: DWORD,
-    key: u16,
 }
 
 impl EnableRawModeCommand {
-    pub fn new(state_manager: &Mutex<StateManager>) -> u16 {
+    pub fn new(state_manager: &Mutex<StateManager>) -> u32 {
         use self::wincon::{ENABLE_ECHO_INPUT, ENABLE_LINE_INPUT, ENABLE_PROCESSED_INPUT};
 
         let mut state = state_manager.lock().unwrap();
         {
-            let key = state.get_changes_count();
             let command = EnableRawModeCommand {
                 mask: ENABLE_LINE_INPUT | ENABLE_PROCESSED_INPUT | ENABLE_ECHO_INPUT,
-                key: key,
             };
-            state.register_change(Box::from(command), key);
-            key
+            state.register_change(Box::from(command))
         }
     }
 }
 
 impl IStateCommand for EnableRawModeCommand {
-    fn execute(&mut self) -> bool {
+    fn execute(&mut self) -> Result<()> {
         let input_handle = kernel::get_input_handle();
 
         let mut dw_mode: DWORD = 0;
         if !kernel::get_console_mode(&input_handle, &mut dw_mode) {
-            return false;
+            return Err(io::Error::last_os_error().into());
         }
 
         let new_mode = dw_mode & !self.mask;
 
         if !kernel::set_console_mode(&input_handle, new_mode) {
-            return false;
+            return Err(io::Error::last_os_error().into());
         }
 
-        true
+        Ok(())
     }
 
-    fn undo(&mut self) -> bool {
+    fn undo(&mut self) -> Result<()> {
         let output_handle = kernel::get_output_handle();
 
         let mut dw_mode: DWORD = 0;
         if !kernel::get_console_mode(&output_handle, &mut dw_mode) {
-            return false;
+            return Err(io::Error::last_os_error().into());
         }
 
         let new_mode = dw_mode | self.mask;
 
         if !kernel::set_console_mode(&output_handle, new_mode) {
-            return false;
+            return Err(io::Error::last_os_error().into());
         }
 
-        true
+        Ok(())
+    }
+}
+
+/// This command is used for enabling and disabling the mouse input events of the windows console.
+/// Quick edit mode is disabled while the mouse is captured because it takes over the mouse to select text.
+/// For more info check: https://docs.microsoft.com/en-us/windows/console/setconsolemode.
+pub struct EnableMouseCaptureCommand {
+    original_mode: Option<DWORD>,
+}
+
+impl EnableMouseCaptureCommand {
+    pub fn new(context: &Context) -> u32 {
+        let mut state = context.state_manager.lock().unwrap();
+        {
+            let command = EnableMouseCaptureCommand { original_mode: None };
+
+            state.register_change(Box::from(command))
+        }
+    }
+}
+
+impl IStateCommand for EnableMouseCaptureCommand {
+    fn execute(&mut self) -> Result<()> {
+        use self::wincon::{ENABLE_EXTENDED_FLAGS, ENABLE_MOUSE_INPUT, ENABLE_QUICK_EDIT_MODE};
+
+        let input_handle = kernel::get_input_handle();
+
+        let mut dw_mode: DWORD = 0;
+        if !kernel::get_console_mode(&input_handle, &mut dw_mode) {
+            return Err(io::Error::last_os_error().into());
+        }
+
+        self.original_mode = Some(dw_mode);
+
+        let new_mode = (dw_mode | ENABLE_MOUSE_INPUT | ENABLE_EXTENDED_FLAGS) & !ENABLE_QUICK_EDIT_MODE;
+
+        if !kernel::set_console_mode(&input_handle, new_mode) {
+            return Err(io::Error::last_os_error().into());
+        }
+
+        Ok(())
+    }
+
+    fn undo(&mut self) -> Result<()> {
+        match self.original_mode {
+            Some(original_mode) => {
+                let input_handle = kernel::get_input_handle();
+
+                if !kernel::set_console_mode(&input_handle, original_mode) {
+                    return Err(io::Error::last_os_error().into());
+                }
+
+                Ok(())
+            }
+            None => Err(Error::new(ErrorKind::StateChange, "mouse capture is not enabled")),
+        }
     }
 }
 
 /// This command is used for switching to alternate screen and back to main screen.
 /// check https://docs.microsoft.com/en-us/windows/console/reading-and-writing-blocks-of-characters-and-attributes for more info
 pub struct ToAlternateScreenBufferCommand {
-    context: Rc<Context>,
+    screen_manager: Arc<Mutex<ScreenManager>>,
 }
 
 impl ToAlternateScreenBufferCommand {
-    pub fn new(context: Rc<Context>) -> u16 {
+    pub fn new(context: &Context) -> u32 {
         let mut state = context.state_manager.lock().unwrap();
         {
-            let key = state.get_changes_count();
             let command = ToAlternateScreenBufferCommand {
-                context: context.clone(),
+                screen_manager: context.screen_manager.clone(),
             };
 
-            state.register_change(Box::from(command), key);
-            key
+            state.register_change(Box::from(command))
         }
     }
 }
 
 impl IStateCommand for ToAlternateScreenBufferCommand {
-    fn execute(&mut self) -> bool {
+    fn execute(&mut self) -> Result<()> {
         use super::super::super::manager::WinApiScreenManager;
 
         let handle = kernel::get_output_handle();
@@ -163,7 +220,7 @@ impl IStateCommand for ToAlternateScreenBufferCommand {
         // Make the new screen buffer the active screen buffer.
         kernel::set_active_screen_buffer(new_handle);
 
-        let mut screen_manager = self.context.screen_manager.lock().unwrap();
+        let mut screen_manager = self.screen_manager.lock().unwrap();
         screen_manager.toggle_is_alternate_screen(true);
 
         let b: &mut WinApiScreenManager = match screen_manager
@@ -171,23 +228,68 @@ impl IStateCommand for ToAlternateScreenBufferCommand {
             .downcast_mut::<WinApiScreenManager>()
         {
             Some(b) => b,
-            None => panic!(""),
+            None => {
+                return Err(Error::new(
+                    ErrorKind::StateChange,
+                    "the alternate screen buffer needs a winapi screen manager",
+                ))
+            }
         };
 
         b.set_alternate_handle(new_handle);
 
-        true
+        Ok(())
     }
 
-    fn undo(&mut self) -> bool {
+    fn undo(&mut self) -> Result<()> {
         let handle = kernel::get_output_handle();
         kernel::set_active_screen_buffer(handle);
 
         {
-            let mut screen_manager = self.context.screen_manager.lock().unwrap();
+            let mut screen_manager = self.screen_manager.lock().unwrap();
             screen_manager.toggle_is_alternate_screen(false);
         }
 
-        true
+        Ok(())
+    }
+}
+
+/// This command is used for saving the title of the console window and restoring it afterwards.
+pub struct TitleStackCommand {
+    original_title: Option<Vec<u16>>,
+}
+
+impl TitleStackCommand {
+    /// The name the command is registered under, see `StateManager::replace_named_change`.
+    pub const NAME: &'static str = "title_stack";
+
+    /// Register and execute the command the first time, afterwards the id of the registered command is returned.
+    pub fn new(context: &Context) -> Result<u32> {
+        let mut state = context.state_manager.lock().unwrap();
+
+        if let Some(id) = state.named_change(Self::NAME) {
+            return Ok(id);
+        }
+
+        let command = TitleStackCommand { original_title: None };
+
+        state.replace_named_change(Self::NAME, Box::from(command))
+    }
+}
+
+impl IStateCommand for TitleStackCommand {
+    fn execute(&mut self) -> Result<()> {
+        self.original_title = Some(kernel::get_console_title()?);
+        Ok(())
+    }
+
+    fn undo(&mut self) -> Result<()> {
+        match self.original_title {
+            Some(ref original_title) => {
+                kernel::set_console_title(original_title)?;
+                Ok(())
+            }
+            None => Err(Error::new(ErrorKind::StateChange, "the title is not saved")),
+        }
     }
 }
@@ -46,15 +46,47 @@
 //! Check the documentation of `AlternateScreen` for more info about how to properly manage the `Context` of the terminal when using the alternate screen.
 //! If you don't use alternate screen functionalities please checkout the `Crossterm` documentation whits will make things easier for you. Since you don't have to manage the `Context` by your self.
 
+use input::Event;
+use shared::functions;
+use style::StylePolicy;
+#[cfg(not(target_os = "windows"))]
+use kernel::unix_kernel::signal::ResizeListener;
+#[cfg(not(target_os = "windows"))]
+use kernel::unix_kernel::terminal;
+#[cfg(not(target_os = "windows"))]
+use libc::{STDIN_FILENO, STDOUT_FILENO};
 use {ScreenManager, StateManager};
 
-use std::rc::Rc;
-use std::sync::Mutex;
+use std::collections::VecDeque;
+#[cfg(not(target_os = "windows"))]
+use std::fs::File;
+use std::io::Write;
+#[cfg(not(target_os = "windows"))]
+use std::os::unix::io::{AsRawFd, RawFd};
+use std::sync::{Arc, Mutex};
 
 /// This type is the context of the current terminal. The context is a wrapper for states changes of the terminal and can be used for managing the output of the terminal.
+///
+/// The context is `Send` and `Sync`, so an `Arc<Context>` can be cloned into other threads, for example a render thread and an input thread.
 pub struct Context {
-    pub screen_manager: Rc<Mutex<ScreenManager>>,
+    pub screen_manager: Arc<Mutex<ScreenManager>>,
     pub state_manager: Mutex<StateManager>,
+    /// Events that are read from the terminal but not yet returned by `TerminalInput::read()`.
+    pub event_queue: Mutex<VecDeque<Event>>,
+    /// The styling policy of this context, `None` means the policy that is set with `style::set_style_policy`.
+    pub style_policy: Mutex<Option<StylePolicy>>,
+    /// Whether the content is written to a terminal, this is `false` when stdout is redirected and for the output of `with_output`.
+    pub output_is_tty: bool,
+    /// Gets notified when the terminal is resized, it is created the first time input is read.
+    #[cfg(not(target_os = "windows"))]
+    pub resize_listener: Mutex<Option<Arc<ResizeListener>>>,
+    /// The file descriptor of the terminal the input is read from and whereof the size and mode are changed,
+    /// `None` means stdin for the input and mode and stdout for the size.
+    #[cfg(not(target_os = "windows"))]
+    pub input_fd: Option<RawFd>,
+    /// The opened `/dev/tty` when stdin or stdout is not a terminal, it is kept here so that its file descriptor stays open.
+    #[cfg(not(target_os = "windows"))]
+    tty: Option<File>,
 }
 
 impl Context {
@@ -65,6 +97,10 @@ impl Context {
     /// You must provide this context otherwise crossterm would not be able to restore to the original state of the terminal.
     /// Also futures like rawscreen and ansi codes can not be used.
     ///
+    /// On unix systems `/dev/tty` is used when stdin or stdout is not a terminal, for example when the program is used in a pipeline.
+    /// The escape codes are then written to `/dev/tty` instead of stdout and the input, raw mode and terminal size use `/dev/tty` as well.
+    /// The content, like the text of `Terminal::write` and `TerminalCursor::print`, is still written to stdout.
+    ///
     /// #Example
     ///
     /// ```rust
@@ -80,17 +116,142 @@ impl Context {
     /// let color = terminal::color(&context);
     ///
     /// ```
-    pub fn new() -> Rc<Context> {
-        Rc::new(Context {
-            screen_manager: Rc::new(Mutex::new(ScreenManager::new())),
+    pub fn new() -> Arc<Context> {
+        #[cfg(not(target_os = "windows"))]
+        {
+            if let Some(context) = Context::from_tty() {
+                return Arc::new(context);
+            }
+        }
+
+        let mut context = Context::from_screen_manager(ScreenManager::new());
+        context.output_is_tty = functions::is_tty();
+        Arc::new(context)
+    }
+
+    /// Create a new Context that writes to the given output instead of stdout, like a pty you spawned or an in memory buffer.
+    ///
+    /// The cursor, color and terminal actions are written to this output as ANSI escape codes.
+    /// The input, raw mode and terminal size still use stdin and stdout, use `with_output_and_input` to use an other terminal for those.
+    /// The output is not seen as a terminal, so colors are only written when the styling policy is `StylePolicy::Always`.
+    ///
+    /// #Example
+    ///
+    /// ```rust
+    ///
+    /// extern crate crossterm;
+    /// use crossterm::Context;
+    /// use crossterm::cursor;
+    ///
+    /// use std::io::sink;
+    ///
+    /// let context = Context::with_output(Box::new(sink()));
+    /// cursor::cursor(&context).goto(5, 5);
+    ///
+    /// ```
+    pub fn with_output(output: Box<Write + Send>) -> Arc<Context> {
+        Arc::new(Context::from_screen_manager(ScreenManager::with_output(output)))
+    }
+
+    /// Create a new Context that writes to the given output and uses the given file descriptor for the input,
+    /// raw mode and the terminal size. The output and the file descriptor should belong to the same terminal.
+    ///
+    /// Note that the file descriptor is not closed by the Context.
+    ///
+    /// #Example
+    ///
+    /// ```rust,no_run
+    ///
+    /// extern crate crossterm;
+    /// use crossterm::Context;
+    ///
+    /// use std::fs::OpenOptions;
+    /// use std::os::unix::io::AsRawFd;
+    ///
+    /// let tty = OpenOptions::new().read(true).write(true).open("/dev/tty").unwrap();
+    /// let input_fd = tty.as_raw_fd();
+    ///
+    /// let context = Context::with_output_and_input(Box::new(tty.try_clone().unwrap()), input_fd);
+    ///
+    /// ```
+    #[cfg(not(target_os = "windows"))]
+    pub fn with_output_and_input(output: Box<Write + Send>, input_fd: RawFd) -> Arc<Context> {
+        let mut context = Context::from_screen_manager(ScreenManager::with_output(output));
+        context.input_fd = Some(input_fd);
+        context.output_is_tty = terminal::is_tty(input_fd);
+        Arc::new(context)
+    }
+
+    /// Set the styling policy of this context, it overrides the policy that is set with `style::set_style_policy`.
+    ///
+    /// #Example
+    ///
+    /// ```rust
+    ///
+    /// extern crate crossterm;
+    /// use crossterm::style::StylePolicy;
+    /// use crossterm::Context;
+    ///
+    /// use std::io::sink;
+    ///
+    /// // Write colors to the output even though it is not a terminal.
+    /// let context = Context::with_output(Box::new(sink()));
+    /// context.set_style_policy(StylePolicy::Always);
+    ///
+    /// ```
+    pub fn set_style_policy(&self, policy: StylePolicy) {
+        *self.style_policy.lock().unwrap() = Some(policy);
+    }
+
+    /// Create a Context that uses `/dev/tty` when stdin or stdout is not a terminal,
+    /// returns `None` when both are terminals or when there is no controlling terminal.
+    #[cfg(not(target_os = "windows"))]
+    fn from_tty() -> Option<Context> {
+        let stdout_is_tty = terminal::is_tty(STDOUT_FILENO);
+
+        if terminal::is_tty(STDIN_FILENO) && stdout_is_tty {
+            return None;
+        }
+
+        let tty = terminal::open_tty().ok()?;
+
+        // The content still goes to stdout, so that it ends up in the pipe or file.
+        let screen_manager = if stdout_is_tty {
+            ScreenManager::new()
+        } else {
+            ScreenManager::with_content_to_stdout(Box::new(tty.try_clone().ok()?))
+        };
+
+        let mut context = Context::from_screen_manager(screen_manager);
+        context.input_fd = Some(tty.as_raw_fd());
+        context.output_is_tty = stdout_is_tty;
+        context.tty = Some(tty);
+        Some(context)
+    }
+
+    fn from_screen_manager(screen_manager: ScreenManager) -> Context {
+        Context {
+            screen_manager: Arc::new(Mutex::new(screen_manager)),
             state_manager: Mutex::new(StateManager::new()),
-        })
+            event_queue: Mutex::new(VecDeque::new()),
+            style_policy: Mutex::new(None),
+            output_is_tty: false,
+            #[cfg(not(target_os = "windows"))]
+            resize_listener: Mutex::new(None),
+            #[cfg(not(target_os = "windows"))]
+            input_fd: None,
+            #[cfg(not(target_os = "windows"))]
+            tty: None,
+        }
     }
 }
 
-use std::io::Write;
-
-/// Revert the changes made to the terminal (this is not running currently don't know why, probably because Context is stored in an Rc<> and it will not be dropped because of that.).
+/// Revert the changes made to the terminal, this happens when the last `Arc<Context>` is dropped.
+///
+/// The modules and the screens that are created from a context hold a clone of its `Arc`, so they have to be dropped as well.
+/// The registered state commands only hold the screen manager, so they do not keep the context alive.
+///
+/// Use a `TerminalGuard` to also revert the changes when the program panics or is terminated by a signal.
 impl Drop for Context {
     fn drop(&mut self) {
         let mut changes = self.state_manager.lock().unwrap();
@@ -3,51 +3,321 @@
 //!  Because this is a terminal manipulating library there will be made changes to terminal when running an process.
 //!  If you stop the process you want the terminal back in its original state.
 //!  Therefore, I need to track the changes made to the terminal.
+//!
+//!  The changes are stored on a stack in the order they are activated, so that they can be undone in the reverse order.
+//!  For example raw mode that is enabled after switching to the alternate screen is disabled before switching back to the main screen.
 
-use super::commands::shared_commands::EmptyCommand;
 use super::commands::IStateCommand;
+use {Error, ErrorKind, Result};
 
-use std::collections::HashMap;
-use std::rc::Rc;
-use std::sync::Mutex;
+/// A registered state command together with the number of times it is executed and not yet undone.
+struct StateChange {
+    id: u32,
+    command: Box<dyn IStateCommand>,
+    active_count: u32,
+}
 
 /// Struct that stores the changed states of the terminal.
 pub struct StateManager {
-    changed_states: HashMap<u16, Rc<Mutex<Box<IStateCommand>>>>,
+    changed_states: Vec<StateChange>,
+    next_id: u32,
+    is_suspended: bool,
+    /// The ids of the changes that are registered with `replace_named_change`.
+    named_changes: Vec<(&'static str, u32)>,
 }
 
 impl StateManager {
     /// Create new Context where the terminals states can be handled.
     pub fn new() -> StateManager {
         StateManager {
-            changed_states: HashMap::new(),
+            changed_states: Vec::new(),
+            next_id: 0,
+            is_suspended: false,
+            named_changes: Vec::new(),
         }
     }
 
-    /// Restore all changes that are made to the terminal.
+    /// Restore all changes that are made to the terminal, the last activated change is undone first.
     pub fn restore_changes(&mut self) {
-        for (id, item) in self.changed_states.iter_mut() {
-            let mut item = item.lock().unwrap();
-            item.undo();
+        for change in self.changed_states.iter_mut().rev() {
+            if change.active_count > 0 {
+                // When suspended the change is already undone.
+                // The other changes are restored as well when one of them fails, so the error is ignored.
+                if !self.is_suspended {
+                    let _ = change.command.undo();
+                }
+                change.active_count = 0;
+            }
+        }
+
+        self.is_suspended = false;
+    }
+
+    /// Undo all active changes in the reverse order, but remember them so that `resume_changes` can make them again.
+    ///
+    /// This is used for giving the terminal back to the shell while the process is stopped.
+    pub fn suspend_changes(&mut self) {
+        if self.is_suspended {
+            return;
+        }
+
+        for change in self.changed_states.iter_mut().rev() {
+            if change.active_count > 0 {
+                let _ = change.command.undo();
+            }
+        }
+
+        self.is_suspended = true;
+    }
+
+    /// Execute the changes that are undone by `suspend_changes` again, in the order they were activated.
+    ///
+    /// A change that fails is not active anymore, the other changes are still executed and the last error is returned.
+    pub fn resume_changes(&mut self) -> Result<()> {
+        let mut result = Ok(());
+
+        if !self.is_suspended {
+            return result;
+        }
+
+        for change in self.changed_states.iter_mut() {
+            if change.active_count > 0 {
+                if let Err(e) = change.command.execute() {
+                    change.active_count = 0;
+                    result = Err(e);
+                }
+            }
+        }
+
+        self.is_suspended = false;
+        result
+    }
+
+    /// Register new changed state, the returned id can be used to execute and undo it.
+    ///
+    /// Ids are never reused, an id of a removed state will not point to an other state.
+    pub fn register_change(&mut self, change: Box<dyn IStateCommand>) -> u32 {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        self.changed_states.push(StateChange {
+            id,
+            command: change,
+            active_count: 0,
+        });
+
+        id
+    }
+
+    /// Register and execute a change under the given name, the change that is registered under that name before is removed without undoing it.
+    ///
+    /// This is used for changes like the cursor shape where a new change takes the place of the previous one, only the last one has to be undone.
+    /// When the new change fails the previous change is kept.
+    pub fn replace_named_change(&mut self, name: &'static str, change: Box<dyn IStateCommand>) -> Result<u32> {
+        let id = self.register_change(change);
+
+        if let Err(e) = self.execute(id) {
+            let _ = self.remove(id);
+            return Err(e);
+        }
+
+        if let Some(previous) = self.named_change(name) {
+            if let Ok(index) = self.position(previous) {
+                self.changed_states.remove(index);
+            }
         }
+
+        self.named_changes.retain(|&(named, _)| named != name);
+        self.named_changes.push((name, id));
+
+        Ok(id)
     }
 
-    /// Register new changed state with the given key.
-    pub fn register_change(&mut self, change: Box<IStateCommand>, key: u16) {
-        self.changed_states.insert(key, Rc::new(Mutex::new(change)));
+    /// Get the id of the change that is registered under the given name with `replace_named_change`.
+    pub fn named_change(&self, name: &str) -> Option<u32> {
+        self.named_changes
+            .iter()
+            .find(|&&(named, _)| named == name)
+            .map(|&(_, id)| id)
     }
 
-    /// Get an state command from storage by id.
-    pub fn get(&mut self, state_key: u16) -> Rc<Mutex<Box<IStateCommand>>> {
-        if self.changed_states.contains_key(&state_key) {
-            return self.changed_states[&state_key].clone();
+    /// Execute the state command with the given id.
+    ///
+    /// Nested executes are counted, the command is only executed when it is not active yet.
+    /// When executed it is moved to the top of the stack.
+    /// While the changes are suspended the command is only executed by `resume_changes`.
+    pub fn execute(&mut self, state_key: u32) -> Result<()> {
+        let index = self.position(state_key)?;
+
+        if self.changed_states[index].active_count > 0 {
+            self.changed_states[index].active_count += 1;
+            return Ok(());
+        }
+
+        let mut change = self.changed_states.remove(index);
+        let result = if self.is_suspended {
+            Ok(())
+        } else {
+            change.command.execute()
+        };
+
+        if result.is_ok() {
+            change.active_count = 1;
+            self.changed_states.push(change);
+        } else {
+            self.changed_states.insert(index, change);
         }
 
-        return Rc::new(Mutex::new(Box::new(EmptyCommand)));
+        result
     }
 
-    /// get the count of changes made. This could be used for the identifier when registering a state.
-    pub fn get_changes_count(&self) -> u16 {
-        return self.changed_states.len() as u16;
+    /// Undo the state command with the given id.
+    ///
+    /// The command is only undone when every execute is matched by an undo, undoing an inactive command does nothing.
+    /// While the changes are suspended the command is already undone, it is then only not executed again by `resume_changes`.
+    pub fn undo(&mut self, state_key: u32) -> Result<()> {
+        let index = self.position(state_key)?;
+        let change = &mut self.changed_states[index];
+
+        match change.active_count {
+            0 => Ok(()),
+            1 => {
+                change.active_count = 0;
+
+                if self.is_suspended {
+                    Ok(())
+                } else {
+                    change.command.undo()
+                }
+            }
+            _ => {
+                change.active_count -= 1;
+                Ok(())
+            }
+        }
+    }
+
+    /// Remove the state command with the given id, it is undone first when it is still active.
+    pub fn remove(&mut self, state_key: u32) -> Result<()> {
+        let index = self.position(state_key)?;
+        let mut change = self.changed_states.remove(index);
+        self.named_changes.retain(|&(_, id)| id != state_key);
+
+        // While the changes are suspended the command is already undone.
+        if change.active_count > 0 && !self.is_suspended {
+            change.command.undo()
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Check whether the state command with the given id is executed and not yet undone.
+    pub fn is_active(&self, state_key: u32) -> bool {
+        match self.position(state_key) {
+            Ok(index) => self.changed_states[index].active_count > 0,
+            Err(_) => false,
+        }
+    }
+
+    fn position(&self, state_key: u32) -> Result<usize> {
+        self.changed_states
+            .iter()
+            .position(|change| change.id == state_key)
+            .ok_or_else(|| Error::new(ErrorKind::StateChange, "the terminal state is not registered"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StateManager;
+    use state::commands::IStateCommand;
+    use Result;
+
+    use std::sync::{Arc, Mutex};
+
+    /// A command that records when it is executed and undone.
+    struct RecordingCommand {
+        name: &'static str,
+        log: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl IStateCommand for RecordingCommand {
+        fn execute(&mut self) -> Result<()> {
+            self.log.lock().unwrap().push(format!("execute {}", self.name));
+            Ok(())
+        }
+
+        fn undo(&mut self) -> Result<()> {
+            self.log.lock().unwrap().push(format!("undo {}", self.name));
+            Ok(())
+        }
+    }
+
+    fn register(state: &mut StateManager, name: &'static str, log: &Arc<Mutex<Vec<String>>>) -> u32 {
+        state.register_change(Box::new(RecordingCommand {
+            name,
+            log: log.clone(),
+        }))
+    }
+
+    fn take(log: &Arc<Mutex<Vec<String>>>) -> Vec<String> {
+        ::std::mem::take(&mut *log.lock().unwrap())
+    }
+
+    #[test]
+    fn nested_executes_are_undone_by_the_last_undo() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut state = StateManager::new();
+        let id = register(&mut state, "a", &log);
+
+        state.execute(id).unwrap();
+        state.execute(id).unwrap();
+        state.undo(id).unwrap();
+        assert!(state.is_active(id));
+        assert_eq!(take(&log), vec!["execute a"]);
+
+        state.undo(id).unwrap();
+        assert!(!state.is_active(id));
+        assert_eq!(take(&log), vec!["undo a"]);
+    }
+
+    #[test]
+    fn changes_are_restored_in_reverse_order() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut state = StateManager::new();
+        let a = register(&mut state, "a", &log);
+        let b = register(&mut state, "b", &log);
+        let c = register(&mut state, "c", &log);
+
+        state.execute(b).unwrap();
+        state.execute(a).unwrap();
+        state.execute(c).unwrap();
+        take(&log);
+
+        state.restore_changes();
+        assert_eq!(take(&log), vec!["undo c", "undo a", "undo b"]);
+        assert!(!state.is_active(a));
+    }
+
+    #[test]
+    fn changes_made_while_suspended_wait_for_the_resume() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut state = StateManager::new();
+        let a = register(&mut state, "a", &log);
+        let b = register(&mut state, "b", &log);
+        let c = register(&mut state, "c", &log);
+
+        state.execute(a).unwrap();
+        state.execute(b).unwrap();
+        state.suspend_changes();
+        assert_eq!(take(&log), vec!["execute a", "execute b", "undo b", "undo a"]);
+
+        state.execute(c).unwrap();
+        state.undo(a).unwrap();
+        state.remove(b).unwrap();
+        assert!(take(&log).is_empty());
+
+        state.resume_changes().unwrap();
+        assert_eq!(take(&log), vec!["execute c"]);
     }
 }
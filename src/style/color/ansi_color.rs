@@ -1,93 +1,183 @@
 //! This is an ANSI specific implementation for styling related action.
 //! This module is used for windows 10 terminals and unix terminals by default.
 
+use super::super::commands::{ResetColor, SetBg, SetFg};
 use super::super::{Color, ColorType};
-use super::ITerminalColor;
-use ScreenManager;
+use super::support::{self, ColorSupport};
+use super::{palette, ITerminalColor};
+use {Command, Result, ScreenManager};
 
-use std::rc::Rc;
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
 
 /// This struct is an ansi implementation for color related actions.
 pub struct AnsiColor
 {
-    screen_manager: Rc<Mutex<ScreenManager>>
+    screen_manager: Arc<Mutex<ScreenManager>>
 }
 
 impl AnsiColor {
-    pub fn new(screen_manager: Rc<Mutex<ScreenManager>>) -> Box<AnsiColor> {
+    pub fn new(screen_manager: Arc<Mutex<ScreenManager>>) -> Box<AnsiColor> {
         Box::from(AnsiColor { screen_manager })
     }
 }
 
 impl ITerminalColor for AnsiColor {
-    fn set_fg(&self, fg_color: Color ) {
-        let mx_guard = &self.screen_manager;
-        let mut screen = mx_guard.lock().unwrap();
-
-        screen.write_string(format!(
-            csi!("{}m"),
-            self.color_value(fg_color, ColorType::Foreground)
-        ));
+    fn set_fg(&self, fg_color: Color) -> Result<()> {
+        let mut screen = self.screen_manager.lock().unwrap();
+        screen.write_string(SetFg(fg_color).ansi_code())?;
+        Ok(())
     }
 
-    fn set_bg(&self, bg_color: Color) {
-        let mx_guard = &self.screen_manager;
-        let mut screen = mx_guard.lock().unwrap();
-
-        screen.write_string(format!(
-            csi!("{}m"),
-            self.color_value(bg_color, ColorType::Background)
-        ));
+    fn set_bg(&self, bg_color: Color) -> Result<()> {
+        let mut screen = self.screen_manager.lock().unwrap();
+        screen.write_string(SetBg(bg_color).ansi_code())?;
+        Ok(())
     }
 
-    fn reset(&self) {
+    fn reset(&self) -> Result<()> {
         let mut screen = self.screen_manager.lock().unwrap();
-        {
-            screen.write_str(csi!("0m"));
-        }
+        screen.write_string(ResetColor.ansi_code())?;
+        Ok(())
     }
+}
 
-    fn color_value(&self, color: Color, color_type: ColorType) -> String {
-        let mut ansi_value = String::new();
-
-        match color_type {
-            ColorType::Foreground => ansi_value.push_str("38;"),
-            ColorType::Background => ansi_value.push_str("48;"),
-        }
+/// Gets an value that represents an color from the given `Color` and `ColorType`, like `38;5;9` for a red foreground.
+///
+/// Colors the terminal can not show are replaced by the nearest color it can show, see `ColorSupport`.
+pub fn color_value(color: Color, color_type: ColorType) -> String {
+    color_value_for(color, color_type, support::color_support())
+}
 
-        #[cfg(unix)]
-        let rgb_val: String;
-
-        let color_val = match color {
-            Color::Black => "5;0",
-            Color::Red => "5;9",
-            Color::DarkRed => "5;1",
-            Color::Green => "5;10",
-            Color::DarkGreen => "5;2",
-            Color::Yellow => "5;11",
-            Color::DarkYellow => "5;3",
-            Color::Blue => "5;12",
-            Color::DarkBlue => "5;4",
-            Color::Magenta => "5;13",
-            Color::DarkMagenta => "5;5",
-            Color::Cyan => "5;14",
-            Color::DarkCyan => "5;6",
-            Color::Grey => "5;15",
-            Color::White => "5;7",
-            #[cfg(unix)]
-            Color::Rgb { r, g, b } => {
-                rgb_val = format!("2;{};{};{}", r, g, b);
-                rgb_val.as_str()
-            }
-            #[cfg(unix)]
-            Color::AnsiValue(val) => {
-                rgb_val = format!("5;{}", val);
-                rgb_val.as_str()
+/// Gets an value that represents an color for a terminal with the given color support.
+///
+/// With `ColorSupport::Ansi256` and `ColorSupport::TrueColor` the named colors are written as one of the 256 colors, like `38;5;9`.
+/// With `ColorSupport::Ansi16` all colors are written with the basic codes `30`-`37` and `90`-`97`
+/// (`40`-`47` and `100`-`107` for the background), because those terminals do not know the `38;5` codes.
+pub fn color_value_for(color: Color, color_type: ColorType, color_support: ColorSupport) -> String {
+    let value = match color {
+        Color::Black => 0,
+        Color::Red => 9,
+        Color::DarkRed => 1,
+        Color::Green => 10,
+        Color::DarkGreen => 2,
+        Color::Yellow => 11,
+        Color::DarkYellow => 3,
+        Color::Blue => 12,
+        Color::DarkBlue => 4,
+        Color::Magenta => 13,
+        Color::DarkMagenta => 5,
+        Color::Cyan => 14,
+        Color::DarkCyan => 6,
+        Color::Grey => 15,
+        Color::White => 7,
+        Color::Rgb { r, g, b } => match color_support {
+            ColorSupport::TrueColor => {
+                return match color_type {
+                    ColorType::Foreground => format!("38;2;{};{};{}", r, g, b),
+                    ColorType::Background => format!("48;2;{};{};{}", r, g, b),
+                }
             }
+            ColorSupport::Ansi256 => palette::nearest_ansi_256((r, g, b)),
+            _ => palette::nearest_ansi_16((r, g, b)),
+        },
+        Color::AnsiValue(value) => value,
+    };
+
+    if color_support >= ColorSupport::Ansi256 {
+        return match color_type {
+            ColorType::Foreground => format!("38;5;{}", value),
+            ColorType::Background => format!("48;5;{}", value),
         };
+    }
+
+    // Terminals with 16 colors only know the codes of the base colors.
+    let value = if value < 16 {
+        value
+    } else {
+        palette::nearest_ansi_16(palette::ansi_value_to_rgb(value))
+    };
+
+    let code = match (color_type, value < 8) {
+        (ColorType::Foreground, true) => 30 + value,
+        (ColorType::Foreground, false) => 90 + value - 8,
+        (ColorType::Background, true) => 40 + value,
+        (ColorType::Background, false) => 100 + value - 8,
+    };
+
+    code.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::color_value_for;
+    use style::{Color, ColorSupport, ColorType};
+
+    fn fg(color: Color, support: ColorSupport) -> String {
+        color_value_for(color, ColorType::Foreground, support)
+    }
+
+    fn bg(color: Color, support: ColorSupport) -> String {
+        color_value_for(color, ColorType::Background, support)
+    }
+
+    #[test]
+    fn true_color() {
+        let rgb = Color::Rgb { r: 10, g: 20, b: 30 };
+
+        assert_eq!(fg(rgb, ColorSupport::TrueColor), "38;2;10;20;30");
+        assert_eq!(bg(rgb, ColorSupport::TrueColor), "48;2;10;20;30");
+        assert_eq!(fg(Color::AnsiValue(100), ColorSupport::TrueColor), "38;5;100");
+        assert_eq!(fg(Color::Red, ColorSupport::TrueColor), "38;5;9");
+    }
+
+    #[test]
+    fn rgb_is_downsampled_to_256_colors() {
+        assert_eq!(fg(Color::Rgb { r: 255, g: 0, b: 0 }, ColorSupport::Ansi256), "38;5;196");
+        assert_eq!(bg(Color::Rgb { r: 250, g: 10, b: 5 }, ColorSupport::Ansi256), "48;5;196");
+        assert_eq!(fg(Color::Rgb { r: 128, g: 128, b: 128 }, ColorSupport::Ansi256), "38;5;244");
+        assert_eq!(fg(Color::AnsiValue(42), ColorSupport::Ansi256), "38;5;42");
+        assert_eq!(bg(Color::DarkBlue, ColorSupport::Ansi256), "48;5;4");
+    }
+
+    #[test]
+    fn named_colors_use_the_basic_codes_with_16_colors() {
+        let cases = [
+            (Color::Black, "30", "40"),
+            (Color::DarkRed, "31", "41"),
+            (Color::DarkGreen, "32", "42"),
+            (Color::DarkYellow, "33", "43"),
+            (Color::DarkBlue, "34", "44"),
+            (Color::DarkMagenta, "35", "45"),
+            (Color::DarkCyan, "36", "46"),
+            (Color::White, "37", "47"),
+            (Color::Red, "91", "101"),
+            (Color::Green, "92", "102"),
+            (Color::Yellow, "93", "103"),
+            (Color::Blue, "94", "104"),
+            (Color::Magenta, "95", "105"),
+            (Color::Cyan, "96", "106"),
+            (Color::Grey, "97", "107"),
+        ];
+
+        for &(color, foreground, background) in cases.iter() {
+            assert_eq!(fg(color, ColorSupport::Ansi16), foreground, "{:?}", color);
+            assert_eq!(bg(color, ColorSupport::Ansi16), background, "{:?}", color);
+        }
+    }
+
+    #[test]
+    fn other_colors_are_downsampled_to_16_colors() {
+        assert_eq!(fg(Color::Rgb { r: 250, g: 10, b: 5 }, ColorSupport::Ansi16), "91");
+        assert_eq!(bg(Color::Rgb { r: 0, g: 0, b: 120 }, ColorSupport::Ansi16), "44");
+        assert_eq!(fg(Color::AnsiValue(8), ColorSupport::Ansi16), "90");
+        assert_eq!(fg(Color::AnsiValue(196), ColorSupport::Ansi16), "91");
+        assert_eq!(fg(Color::AnsiValue(232), ColorSupport::Ansi16), "30");
+    }
 
-        ansi_value.push_str(color_val);
-        ansi_value
+    #[test]
+    fn no_color_uses_the_basic_codes() {
+        // The codes are only written when styling is enabled, then the base colors are the safest choice.
+        assert_eq!(fg(Color::Red, ColorSupport::NoColor), "91");
+        assert_eq!(fg(Color::Rgb { r: 0, g: 128, b: 0 }, ColorSupport::NoColor), "32");
     }
 }
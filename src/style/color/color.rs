@@ -3,23 +3,25 @@
 
 use super::super::super::shared::functions;
 use super::*;
-use std::io;
-use std::rc::Rc;
-use std::sync::Mutex;
-use style::Color;
-use {Context, ScreenManager};
+use std::sync::{Arc, Mutex};
+use style::{policy, Color, ColorSupport};
+use {Context, Result, ScreenManager};
 
 /// Struct that stores an specific platform implementation for color related actions.
+///
+/// Setting and resetting the colors does nothing when the styling policy of the context disables colors, see `StylePolicy`.
 pub struct TerminalColor {
     color: Box<ITerminalColor>,
-    screen_manager: Rc<Mutex<ScreenManager>>,
+    screen_manager: Arc<Mutex<ScreenManager>>,
+    context: Arc<Context>,
 }
 
 impl TerminalColor {
     /// Create new instance whereon color related actions can be performed.
-    pub fn new(context: Rc<Context>) -> TerminalColor {
+    pub fn new(context: Arc<Context>) -> TerminalColor {
         #[cfg(target_os = "windows")]
-        let color = functions::get_module::<Box<ITerminalColor>>(
+        let color = functions::get_module_for_context::<Box<ITerminalColor>>(
+            &context,
             WinApiColor::new(context.screen_manager.clone()),
             AnsiColor::new(context.screen_manager.clone()),
         ).unwrap();
@@ -30,6 +32,7 @@ impl TerminalColor {
         TerminalColor {
             color: color,
             screen_manager: context.screen_manager.clone(),
+            context: context,
         }
     }
 
@@ -54,8 +57,12 @@ impl TerminalColor {
     /// colored_terminal.set_fg(Color::from("Red"));
     ///
     /// ```
-    pub fn set_fg(&self, color: Color) {
-        self.color.set_fg(color);
+    pub fn set_fg(&self, color: Color) -> Result<()> {
+        if !policy::is_styling_enabled(&self.context) {
+            return Ok(());
+        }
+
+        self.color.set_fg(color)
     }
 
     /// Set the background color to the given color.
@@ -80,8 +87,12 @@ impl TerminalColor {
     /// colored_terminal.set_bg(Color::from("Red"));
     ///
     /// ```
-    pub fn set_bg(&self, color: Color) {
-        self.color.set_bg(color);
+    pub fn set_bg(&self, color: Color) -> Result<()> {
+        if !policy::is_styling_enabled(&self.context) {
+            return Ok(());
+        }
+
+        self.color.set_bg(color)
     }
 
     /// Reset the terminal colors and attributes to default.
@@ -101,31 +112,55 @@ impl TerminalColor {
     /// colored_terminal.reset();
     ///
     /// ```
-    pub fn reset(&self) {
-        self.color.reset();
-    }
+    pub fn reset(&self) -> Result<()> {
+        if !policy::is_styling_enabled(&self.context) {
+            return Ok(());
+        }
 
-    /// Get available color count.
-    pub fn get_available_color_count(&self) -> io::Result<u16> {
-        use std::env;
+        self.color.reset()
+    }
 
-        Ok(match env::var_os("TERM") {
-            Some(val) => {
-                if val.to_str().unwrap_or("").contains("256color") {
-                    256
-                } else {
-                    8
-                }
-            }
-            None => 8,
+    /// Get available color count, this is the number of `Color::AnsiValue` colors the terminal can show.
+    ///
+    /// See `color_support` to check whether the terminal can show RGB colors.
+    pub fn get_available_color_count(&self) -> Result<u16> {
+        Ok(match self.color_support() {
+            ColorSupport::NoColor => 0,
+            ColorSupport::Ansi16 => 16,
+            ColorSupport::Ansi256 | ColorSupport::TrueColor => 256,
         })
     }
+
+    /// Get the colors the terminal can show, it is detected once with `ColorSupport::detect`.
+    ///
+    /// Colors the terminal can not show are replaced by the nearest color it can show,
+    /// like the nearest of the 256 colors for an RGB color.
+    ///
+    /// #Example
+    ///
+    /// ```rust
+    /// extern crate crossterm;
+    ///
+    /// use self::crossterm::style::{color, ColorSupport};
+    /// use crossterm::Context;
+    ///
+    /// let context = Context::new();
+    /// let colored_terminal = color(&context);
+    ///
+    /// if colored_terminal.color_support() == ColorSupport::TrueColor {
+    ///     println!("the terminal can show all RGB colors");
+    /// }
+    ///
+    /// ```
+    pub fn color_support(&self) -> ColorSupport {
+        support::color_support()
+    }
 }
 
 /// Get an Color implementation whereon color related actions can be performed.
 ///
 /// Check `/examples/version/color` in the library for more specific examples.
 ///
-pub fn color(context: &Rc<Context>) -> Box<TerminalColor> {
+pub fn color(context: &Arc<Context>) -> Box<TerminalColor> {
     Box::from(TerminalColor::new(context.clone()))
 }
@@ -1,6 +1,8 @@
 pub mod color;
 
-mod ansi_color;
+pub mod ansi_color;
+pub mod palette;
+pub mod support;
 #[cfg(target_os = "windows")]
 mod winapi_color;
 
@@ -8,11 +10,8 @@ use self::ansi_color::AnsiColor;
 #[cfg(target_os = "windows")]
 use self::winapi_color::WinApiColor;
 
-use super::{Color, ColorType};
-use ScreenManager;
-
-use std::rc::Rc;
-use std::sync::Mutex;
+use super::Color;
+use Result;
 
 ///! This trait defines the actions that can be preformed with the terminal color.
 ///! This trait can be implemented so that an concrete implementation of the ITerminalColor can forfill
@@ -22,13 +21,11 @@ use std::sync::Mutex;
 ///!
 ///! This trait is implemented for `WINAPI` (Windows specific) and `ANSI` (Unix specific),
 ///! so that color related actions can be preformed on both unix and windows systems.
-pub trait ITerminalColor {
+pub trait ITerminalColor: Send + Sync {
     /// Set the foreground color to the given color.
-    fn set_fg(&self, fg_color: Color);
+    fn set_fg(&self, fg_color: Color) -> Result<()>;
     /// Set the background color to the given color.
-    fn set_bg(&self, fg_color: Color);
+    fn set_bg(&self, fg_color: Color) -> Result<()>;
     /// Reset the terminal color to default.
-    fn reset(&self);
-    /// Gets an value that represents an color from the given `Color` and `ColorType`.
-    fn color_value(&self, color: Color, color_type: ColorType) -> String;
+    fn reset(&self) -> Result<()>;
 }
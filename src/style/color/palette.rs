@@ -0,0 +1,247 @@
+//! This module contains the mapping of RGB and ANSI value colors to the nearest color a terminal can show,
+//! like the named colors for the windows console or the 256 color palette for terminals without RGB colors.
+
+use super::super::Color;
+
+/// The named colors with the RGB value the windows console shows them with.
+const NAMED_COLORS: [(Color, (u8, u8, u8)); 15] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::DarkRed, (128, 0, 0)),
+    (Color::DarkGreen, (0, 128, 0)),
+    (Color::DarkYellow, (128, 128, 0)),
+    (Color::DarkBlue, (0, 0, 128)),
+    (Color::DarkMagenta, (128, 0, 128)),
+    (Color::DarkCyan, (0, 128, 128)),
+    (Color::Grey, (128, 128, 128)),
+    (Color::Red, (255, 0, 0)),
+    (Color::Green, (0, 255, 0)),
+    (Color::Yellow, (255, 255, 0)),
+    (Color::Blue, (0, 0, 255)),
+    (Color::Magenta, (255, 0, 255)),
+    (Color::Cyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+/// The named colors of the first 16 ANSI values, the light grey of value 7 is shown as white.
+const ANSI_COLORS: [Color; 16] = [
+    Color::Black,
+    Color::DarkRed,
+    Color::DarkGreen,
+    Color::DarkYellow,
+    Color::DarkBlue,
+    Color::DarkMagenta,
+    Color::DarkCyan,
+    Color::White,
+    Color::Grey,
+    Color::Red,
+    Color::Green,
+    Color::Yellow,
+    Color::Blue,
+    Color::Magenta,
+    Color::Cyan,
+    Color::White,
+];
+
+/// The RGB values of the first 16 ANSI values as most terminals show them by default.
+const ANSI_16_COLORS: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (128, 0, 0),
+    (0, 128, 0),
+    (128, 128, 0),
+    (0, 0, 128),
+    (128, 0, 128),
+    (0, 128, 128),
+    (192, 192, 192),
+    (128, 128, 128),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (0, 0, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+impl Color {
+    /// Get the named color that is nearest to this color, the named colors are returned as they are.
+    ///
+    /// This color is used by the backends that can not show RGB and ANSI value colors, like the windows console.
+    ///
+    /// #Example
+    ///
+    /// ```rust
+    /// extern crate crossterm;
+    /// use crossterm::style::Color;
+    ///
+    /// assert_eq!(Color::Rgb { r: 150, g: 20, b: 10 }.to_named(), Color::DarkRed);
+    /// assert_eq!(Color::AnsiValue(9).to_named(), Color::Red);
+    ///
+    /// ```
+    pub fn to_named(self) -> Color {
+        match self {
+            Color::Rgb { r, g, b } => nearest_named((r, g, b)),
+            Color::AnsiValue(value) if value < 16 => ANSI_COLORS[value as usize],
+            Color::AnsiValue(value) => nearest_named(ansi_value_to_rgb(value)),
+            color => color,
+        }
+    }
+}
+
+/// Get the RGB value of the given value of the 256 color palette.
+///
+/// The terminals can be configured to show the first 16 values with other RGB values, the default values are returned for these.
+pub fn ansi_value_to_rgb(value: u8) -> (u8, u8, u8) {
+    match value {
+        0..=15 => ANSI_16_COLORS[value as usize],
+        // A 6x6x6 color cube.
+        16..=231 => {
+            let value = value - 16;
+            let level = |n: u8| if n == 0 { 0 } else { 55 + n * 40 };
+
+            (level(value / 36), level(value / 6 % 6), level(value % 6))
+        }
+        // A grey scale from dark to light.
+        _ => {
+            let grey = 8 + (value - 232) * 10;
+            (grey, grey, grey)
+        }
+    }
+}
+
+/// Get the squared distance between two RGB values.
+pub fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let channel = |a: u8, b: u8| {
+        let difference = a as i32 - b as i32;
+        (difference * difference) as u32
+    };
+
+    channel(a.0, b.0) + channel(a.1, b.1) + channel(a.2, b.2)
+}
+
+/// Get the ANSI value of the 256 color palette that is nearest to the given RGB value.
+///
+/// Only the color cube and the grey scale are used, because the first 16 values differ per terminal.
+pub fn nearest_ansi_256(rgb: (u8, u8, u8)) -> u8 {
+    nearest_ansi_value(rgb, 16, 255)
+}
+
+/// Get the ANSI value of the 16 base colors that is nearest to the given RGB value.
+pub fn nearest_ansi_16(rgb: (u8, u8, u8)) -> u8 {
+    nearest_ansi_value(rgb, 0, 15)
+}
+
+fn nearest_ansi_value(rgb: (u8, u8, u8), first: u8, last: u8) -> u8 {
+    (first..=last)
+        .min_by_key(|value| distance(rgb, ansi_value_to_rgb(*value)))
+        .unwrap_or(first)
+}
+
+fn nearest_named(rgb: (u8, u8, u8)) -> Color {
+    NAMED_COLORS
+        .iter()
+        .min_by_key(|&&(_, named)| distance(rgb, named))
+        .map_or(Color::White, |&(color, _)| color)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ansi_value_to_rgb, distance, nearest_ansi_16, nearest_ansi_256};
+    use style::Color;
+
+    #[test]
+    fn color_cube_boundaries() {
+        assert_eq!(ansi_value_to_rgb(16), (0, 0, 0));
+        assert_eq!(ansi_value_to_rgb(17), (0, 0, 95));
+        assert_eq!(ansi_value_to_rgb(21), (0, 0, 255));
+        assert_eq!(ansi_value_to_rgb(22), (0, 95, 0));
+        assert_eq!(ansi_value_to_rgb(52), (95, 0, 0));
+        assert_eq!(ansi_value_to_rgb(196), (255, 0, 0));
+        assert_eq!(ansi_value_to_rgb(231), (255, 255, 255));
+    }
+
+    #[test]
+    fn grey_scale_boundaries() {
+        assert_eq!(ansi_value_to_rgb(232), (8, 8, 8));
+        assert_eq!(ansi_value_to_rgb(244), (128, 128, 128));
+        assert_eq!(ansi_value_to_rgb(255), (238, 238, 238));
+    }
+
+    #[test]
+    fn base_colors() {
+        assert_eq!(ansi_value_to_rgb(0), (0, 0, 0));
+        assert_eq!(ansi_value_to_rgb(7), (192, 192, 192));
+        assert_eq!(ansi_value_to_rgb(9), (255, 0, 0));
+        assert_eq!(ansi_value_to_rgb(15), (255, 255, 255));
+    }
+
+    #[test]
+    fn distance_is_squared() {
+        assert_eq!(distance((0, 0, 0), (0, 0, 0)), 0);
+        assert_eq!(distance((0, 0, 0), (3, 4, 0)), 25);
+        assert_eq!(distance((255, 255, 255), (0, 0, 0)), 3 * 255 * 255);
+    }
+
+    #[test]
+    fn exact_palette_colors_are_found() {
+        for value in 16..=255u8 {
+            assert_eq!(nearest_ansi_256(ansi_value_to_rgb(value)), value);
+        }
+
+        for value in 0..16u8 {
+            assert_eq!(nearest_ansi_16(ansi_value_to_rgb(value)), value);
+        }
+    }
+
+    #[test]
+    fn nearest_palette_colors() {
+        // The base colors are not used for the 256 colors, because terminals show them differently.
+        assert_eq!(nearest_ansi_256((255, 0, 0)), 196);
+        assert_eq!(nearest_ansi_256((0, 0, 0)), 16);
+        assert_eq!(nearest_ansi_256((250, 10, 5)), 196);
+        assert_eq!(nearest_ansi_256((100, 100, 100)), 241);
+        assert_eq!(nearest_ansi_256((1, 1, 1)), 16);
+
+        assert_eq!(nearest_ansi_16((250, 10, 5)), 9);
+        assert_eq!(nearest_ansi_16((130, 0, 0)), 1);
+        assert_eq!(nearest_ansi_16((200, 200, 200)), 7);
+        assert_eq!(nearest_ansi_16((140, 140, 140)), 8);
+    }
+
+    #[test]
+    fn nearest_console_colors() {
+        let cases = vec![
+            (Color::Rgb { r: 0, g: 0, b: 0 }, Color::Black),
+            (Color::Rgb { r: 255, g: 255, b: 255 }, Color::White),
+            (Color::Rgb { r: 240, g: 20, b: 20 }, Color::Red),
+            (Color::Rgb { r: 120, g: 10, b: 10 }, Color::DarkRed),
+            (Color::Rgb { r: 10, g: 250, b: 10 }, Color::Green),
+            (Color::Rgb { r: 0, g: 120, b: 120 }, Color::DarkCyan),
+            (Color::Rgb { r: 130, g: 130, b: 130 }, Color::Grey),
+            (Color::Rgb { r: 250, g: 250, b: 0 }, Color::Yellow),
+            (Color::AnsiValue(0), Color::Black),
+            (Color::AnsiValue(1), Color::DarkRed),
+            (Color::AnsiValue(7), Color::White),
+            (Color::AnsiValue(8), Color::Grey),
+            (Color::AnsiValue(12), Color::Blue),
+            (Color::AnsiValue(15), Color::White),
+            (Color::AnsiValue(21), Color::Blue),
+            (Color::AnsiValue(196), Color::Red),
+            (Color::AnsiValue(232), Color::Black),
+            (Color::AnsiValue(244), Color::Grey),
+            (Color::AnsiValue(255), Color::White),
+        ];
+
+        for (color, named) in cases {
+            assert_eq!(color.to_named(), named, "{:?}", color);
+        }
+    }
+
+    #[test]
+    fn named_colors_stay_the_same() {
+        let named = [Color::Black, Color::DarkYellow, Color::Magenta, Color::Grey, Color::White];
+
+        for color in named.iter() {
+            assert_eq!(color.to_named(), *color);
+        }
+    }
+}
@@ -0,0 +1,369 @@
+//! This module contains the detection of the number of colors the terminal can show.
+
+use std::cmp;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// The detected color support, it is detected the first time it is needed.
+static COLOR_SUPPORT: Mutex<Option<ColorSupport>> = Mutex::new(None);
+
+/// The index of the `colors` capability in the numbers section of a terminfo entry.
+const TERMINFO_COLORS: usize = 13;
+
+/// The colors a terminal can show, from no colors to all RGB colors.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ColorSupport {
+    /// The terminal can not show colors or the user does not want colors.
+    NoColor,
+    /// The 16 base colors, these are written with the basic codes `30`-`37` and `90`-`97` (`40`-`47` and `100`-`107` for the background).
+    Ansi16,
+    /// The 256 colors of `Color::AnsiValue`.
+    Ansi256,
+    /// All colors of `Color::Rgb`.
+    TrueColor,
+}
+
+impl ColorSupport {
+    /// Detect the colors the terminal can show with the environment variables and the terminfo database.
+    ///
+    /// `COLORTERM=truecolor` and `COLORTERM=24bit` mean all RGB colors, otherwise `TERM` and its terminfo entry decide.
+    /// `NO_COLOR` (with a value that is not empty) gives `NoColor` and `CLICOLOR_FORCE` (with a value other than `0`) gives at least `Ansi16`,
+    /// `CLICOLOR_FORCE` wins when both are set.
+    ///
+    /// #Example
+    ///
+    /// ```rust
+    /// extern crate crossterm;
+    /// use crossterm::style::ColorSupport;
+    ///
+    /// if ColorSupport::detect() >= ColorSupport::Ansi256 {
+    ///     println!("the terminal can show 256 colors");
+    /// }
+    ///
+    /// ```
+    pub fn detect() -> ColorSupport {
+        detect_with(
+            |name| env::var_os(name).map(|value| value.to_string_lossy().into_owned()),
+            terminfo_colors,
+        )
+    }
+}
+
+/// Get the color support of the terminal, it is detected once and reused afterwards.
+pub fn color_support() -> ColorSupport {
+    let mut color_support = COLOR_SUPPORT.lock().unwrap();
+
+    *color_support.get_or_insert_with(ColorSupport::detect)
+}
+
+/// Detect the color support with the given environment variables,
+/// `terminfo` reads the `colors` capability of a terminal and returns `None` when the terminal has no terminfo entry.
+fn detect_with<V, T>(var: V, terminfo: T) -> ColorSupport
+where
+    V: Fn(&str) -> Option<String>,
+    T: Fn(&str) -> Option<i32>,
+{
+    let support = detect_depth(&var, terminfo);
+
+    if var("CLICOLOR_FORCE").is_some_and(|force| force != "0") {
+        return cmp::max(support, ColorSupport::Ansi16);
+    }
+
+    if var("NO_COLOR").is_some_and(|value| !value.is_empty()) {
+        return ColorSupport::NoColor;
+    }
+
+    support
+}
+
+fn detect_depth<V, T>(var: &V, terminfo: T) -> ColorSupport
+where
+    V: Fn(&str) -> Option<String>,
+    T: Fn(&str) -> Option<i32>,
+{
+    if let Some(colorterm) = var("COLORTERM") {
+        if colorterm == "truecolor" || colorterm == "24bit" {
+            return ColorSupport::TrueColor;
+        }
+    }
+
+    let term = match var("TERM") {
+        Some(term) => term,
+        // The windows console shows all colors when it accepts ANSI escape codes.
+        None if cfg!(target_os = "windows") => return ColorSupport::TrueColor,
+        None => return ColorSupport::NoColor,
+    };
+
+    if term == "dumb" {
+        return ColorSupport::NoColor;
+    }
+
+    if term.ends_with("-direct") || term.contains("truecolor") {
+        return ColorSupport::TrueColor;
+    }
+
+    if term.contains("256color") {
+        return ColorSupport::Ansi256;
+    }
+
+    match terminfo(&term) {
+        Some(colors) if colors >= 1 << 24 => ColorSupport::TrueColor,
+        Some(colors) if colors >= 256 => ColorSupport::Ansi256,
+        Some(colors) if colors >= 8 => ColorSupport::Ansi16,
+        Some(_) => ColorSupport::NoColor,
+        // Most terminals show the base colors.
+        None => ColorSupport::Ansi16,
+    }
+}
+
+/// Read the `colors` capability of the terminfo entry of the given terminal, returns `None` when there is no entry.
+fn terminfo_colors(term: &str) -> Option<i32> {
+    let first = term.chars().next()?;
+
+    // The entries are stored in a directory named after the first letter, or its hexadecimal value on macOS.
+    let subdirectories = [first.to_string(), format!("{:x}", first as u32)];
+
+    for directory in terminfo_directories() {
+        for subdirectory in subdirectories.iter() {
+            if let Ok(entry) = fs::read(directory.join(subdirectory).join(term)) {
+                return parse_terminfo_colors(&entry);
+            }
+        }
+    }
+
+    None
+}
+
+fn terminfo_directories() -> Vec<PathBuf> {
+    let mut directories = Vec::new();
+
+    if let Some(directory) = env::var_os("TERMINFO") {
+        directories.push(PathBuf::from(directory));
+    }
+
+    if let Some(home) = env::var_os("HOME") {
+        directories.push(Path::new(&home).join(".terminfo"));
+    }
+
+    if let Ok(list) = env::var("TERMINFO_DIRS") {
+        directories.extend(list.split(':').filter(|directory| !directory.is_empty()).map(PathBuf::from));
+    }
+
+    for directory in ["/etc/terminfo", "/lib/terminfo", "/usr/share/terminfo", "/usr/lib/terminfo"].iter() {
+        directories.push(PathBuf::from(directory));
+    }
+
+    directories
+}
+
+/// Parse the `colors` capability of a compiled terminfo entry, see `man term` for the format.
+fn parse_terminfo_colors(entry: &[u8]) -> Option<i32> {
+    let read = |offset: usize, size: usize| -> Option<i32> {
+        let bytes = entry.get(offset..offset + size)?;
+
+        Some(match size {
+            2 => i16::from_le_bytes([bytes[0], bytes[1]]) as i32,
+            _ => i32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+        })
+    };
+
+    // The extended format stores the numbers in 4 bytes instead of 2.
+    let number_size = match read(0, 2)? {
+        0o432 => 2,
+        0o1036 => 4,
+        _ => return None,
+    };
+
+    let names_size = read(2, 2)?;
+    let booleans_count = read(4, 2)?;
+    let numbers_count = read(6, 2)?;
+
+    if names_size < 0 || booleans_count < 0 {
+        return None;
+    }
+
+    // A terminal without the capability, like `vt100`, has no colors.
+    if numbers_count <= TERMINFO_COLORS as i32 {
+        return Some(0);
+    }
+
+    // The numbers start at an even offset after the header of 12 bytes, the names and the booleans.
+    let mut offset = 12 + names_size as usize + booleans_count as usize;
+    offset += offset % 2;
+
+    // Capabilities that are absent or cancelled have a negative value.
+    read(offset + TERMINFO_COLORS * number_size, number_size).map(|colors| cmp::max(colors, 0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{detect_with, parse_terminfo_colors, ColorSupport};
+
+    /// Detect the color support with the given environment variables and terminfo entries.
+    fn detect(vars: &[(&str, &str)], entries: &[(&str, i32)]) -> ColorSupport {
+        detect_with(
+            |name| {
+                vars.iter()
+                    .find(|&&(var, _)| var == name)
+                    .map(|&(_, value)| value.to_string())
+            },
+            |term| {
+                entries
+                    .iter()
+                    .find(|&&(entry, _)| entry == term)
+                    .map(|&(_, colors)| colors)
+            },
+        )
+    }
+
+    /// Create a compiled terminfo entry with the given numbers, the numbers are 4 bytes in the extended format.
+    fn terminfo_entry(extended: bool, numbers: &[i32]) -> Vec<u8> {
+        let mut entry = Vec::new();
+        let names = b"test|a terminal for the tests\0";
+        let booleans = [1u8, 0, 1];
+
+        let push_i16 = |entry: &mut Vec<u8>, value: i16| entry.extend_from_slice(&value.to_le_bytes());
+
+        push_i16(&mut entry, if extended { 0o1036 } else { 0o432 });
+        push_i16(&mut entry, names.len() as i16);
+        push_i16(&mut entry, booleans.len() as i16);
+        push_i16(&mut entry, numbers.len() as i16);
+        // The number and size of the strings, these are not used.
+        push_i16(&mut entry, 0);
+        push_i16(&mut entry, 0);
+
+        entry.extend_from_slice(names);
+        entry.extend_from_slice(&booleans);
+
+        // The numbers start at an even offset.
+        if entry.len() % 2 == 1 {
+            entry.push(0);
+        }
+
+        for number in numbers {
+            if extended {
+                entry.extend_from_slice(&number.to_le_bytes());
+            } else {
+                push_i16(&mut entry, *number as i16);
+            }
+        }
+
+        entry
+    }
+
+    /// The numbers of an entry with the given `colors` capability.
+    fn with_colors(colors: i32) -> Vec<i32> {
+        vec![80, 0, 24, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, colors, 64]
+    }
+
+    #[test]
+    fn colorterm_means_true_color() {
+        assert_eq!(detect(&[("COLORTERM", "truecolor")], &[]), ColorSupport::TrueColor);
+        assert_eq!(detect(&[("COLORTERM", "24bit"), ("TERM", "xterm")], &[]), ColorSupport::TrueColor);
+        assert_eq!(
+            detect(&[("COLORTERM", "yes"), ("TERM", "xterm-256color")], &[]),
+            ColorSupport::Ansi256
+        );
+    }
+
+    #[test]
+    fn term_names() {
+        let cases = [
+            ("dumb", ColorSupport::NoColor),
+            ("xterm-direct", ColorSupport::TrueColor),
+            ("xterm-truecolor", ColorSupport::TrueColor),
+            ("xterm-256color", ColorSupport::Ansi256),
+            ("screen-256color", ColorSupport::Ansi256),
+        ];
+
+        for &(term, expected) in cases.iter() {
+            assert_eq!(detect(&[("TERM", term)], &[]), expected, "{}", term);
+        }
+    }
+
+    #[test]
+    fn terminfo_colors_decide_for_other_terminals() {
+        let entries = [("vt100", 0), ("xterm", 8), ("rxvt-88", 88), ("fancy", 256), ("direct", 1 << 24)];
+
+        let cases = [
+            ("vt100", ColorSupport::NoColor),
+            ("xterm", ColorSupport::Ansi16),
+            ("rxvt-88", ColorSupport::Ansi16),
+            ("fancy", ColorSupport::Ansi256),
+            ("direct", ColorSupport::TrueColor),
+            // Most terminals without a terminfo entry show the base colors.
+            ("unknown", ColorSupport::Ansi16),
+        ];
+
+        for &(term, expected) in cases.iter() {
+            assert_eq!(detect(&[("TERM", term)], &entries), expected, "{}", term);
+        }
+    }
+
+    #[test]
+    fn without_term() {
+        let expected = if cfg!(target_os = "windows") {
+            ColorSupport::TrueColor
+        } else {
+            ColorSupport::NoColor
+        };
+
+        assert_eq!(detect(&[], &[]), expected);
+    }
+
+    #[test]
+    fn no_color_and_clicolor_force() {
+        let xterm = ("TERM", "xterm-256color");
+
+        assert_eq!(detect(&[xterm, ("NO_COLOR", "1")], &[]), ColorSupport::NoColor);
+        // An empty `NO_COLOR` is ignored.
+        assert_eq!(detect(&[xterm, ("NO_COLOR", "")], &[]), ColorSupport::Ansi256);
+
+        assert_eq!(detect(&[("TERM", "dumb"), ("CLICOLOR_FORCE", "1")], &[]), ColorSupport::Ansi16);
+        assert_eq!(detect(&[xterm, ("CLICOLOR_FORCE", "1")], &[]), ColorSupport::Ansi256);
+        assert_eq!(detect(&[("TERM", "dumb"), ("CLICOLOR_FORCE", "0")], &[]), ColorSupport::NoColor);
+
+        // `CLICOLOR_FORCE` wins from `NO_COLOR`.
+        assert_eq!(
+            detect(&[xterm, ("NO_COLOR", "1"), ("CLICOLOR_FORCE", "1")], &[]),
+            ColorSupport::Ansi256
+        );
+    }
+
+    #[test]
+    fn parse_legacy_terminfo() {
+        assert_eq!(parse_terminfo_colors(&terminfo_entry(false, &with_colors(8))), Some(8));
+        assert_eq!(parse_terminfo_colors(&terminfo_entry(false, &with_colors(256))), Some(256));
+    }
+
+    #[test]
+    fn parse_extended_terminfo() {
+        assert_eq!(
+            parse_terminfo_colors(&terminfo_entry(true, &with_colors(1 << 24))),
+            Some(1 << 24)
+        );
+    }
+
+    #[test]
+    fn parse_terminfo_without_colors() {
+        // An entry with less numbers than the index of `colors`.
+        assert_eq!(parse_terminfo_colors(&terminfo_entry(false, &[80, 0, 24])), Some(0));
+        // An absent capability.
+        assert_eq!(parse_terminfo_colors(&terminfo_entry(false, &with_colors(-1))), Some(0));
+        // A cancelled capability.
+        assert_eq!(parse_terminfo_colors(&terminfo_entry(false, &with_colors(-2))), Some(0));
+    }
+
+    #[test]
+    fn parse_invalid_terminfo() {
+        assert_eq!(parse_terminfo_colors(&[]), None);
+        assert_eq!(parse_terminfo_colors(b"not a terminfo entry"), None);
+
+        // The entry ends before the numbers.
+        let mut entry = terminfo_entry(false, &with_colors(8));
+        entry.truncate(40);
+        assert_eq!(parse_terminfo_colors(&entry), None);
+    }
+}
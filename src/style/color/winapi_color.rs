@@ -2,19 +2,17 @@ use super::super::{Color, ColorType};
 use super::ITerminalColor;
 use kernel::windows_kernel::kernel;
 use winapi::um::wincon;
-use ScreenManager;
-use super::super::super::manager::WinApiScreenManager;
+use {Result, ScreenManager};
 
-use std::rc::Rc;
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
 
 /// This struct is an windows implementation for color related actions.
 pub struct WinApiColor {
-    screen_manager: Rc<Mutex<ScreenManager>>,
+    screen_manager: Arc<Mutex<ScreenManager>>,
 }
 
 impl WinApiColor {
-    pub fn new(screen_manager: Rc<Mutex<ScreenManager>>) -> Box<WinApiColor> {
+    pub fn new(screen_manager: Arc<Mutex<ScreenManager>>) -> Box<WinApiColor> {
         Box::from(WinApiColor {
             screen_manager: screen_manager,
         })
@@ -22,17 +20,17 @@ impl WinApiColor {
 }
 
 impl ITerminalColor for WinApiColor {
-    fn set_fg(&self, fg_color: Color) {
-        let color_value = &self.color_value(fg_color, ColorType::Foreground);
+    fn set_fg(&self, fg_color: Color) -> Result<()> {
+        let color_value = winapi_color_value(fg_color, ColorType::Foreground);
 
-        let csbi = kernel::get_console_screen_buffer_info(&self.screen_manager);
+        let csbi = kernel::try_get_console_screen_buffer_info(&self.screen_manager)?;
 
         // Notice that the color values are stored in wAttribute.
         // So we need to use bitwise operators to check if the values exists or to get current console colors.
         let mut color: u16;
         let attrs = csbi.wAttributes;
         let bg_color = attrs & 0x0070;
-        color = color_value.parse::<u16>().unwrap() | bg_color;
+        color = color_value | bg_color;
 
         // background intensity is a separate value in attrs,
         // wee need to check if this was applied to the current bg color.
@@ -40,20 +38,21 @@ impl ITerminalColor for WinApiColor {
             color = color | wincon::BACKGROUND_INTENSITY as u16;
         }
 
-        kernel::set_console_text_attribute(color, &self.screen_manager);
+        kernel::set_console_text_attribute(color, &self.screen_manager)?;
+        Ok(())
     }
 
-    fn set_bg(&self, bg_color: Color) {
-        let color_value = &self.color_value(bg_color, ColorType::Background);
+    fn set_bg(&self, bg_color: Color) -> Result<()> {
+        let color_value = winapi_color_value(bg_color, ColorType::Background);
 
-        let (csbi,handle) = kernel::get_buffer_info_and_hande(&self.screen_manager);
+        let csbi = kernel::try_get_console_screen_buffer_info(&self.screen_manager)?;
 
         // Notice that the color values are stored in wAttribute.
         // So wee need to use bitwise operators to check if the values exists or to get current console colors.
         let mut color: u16;
         let attrs = csbi.wAttributes;
         let fg_color = attrs & 0x0007;
-        color = fg_color | color_value.parse::<u16>().unwrap();
+        color = fg_color | color_value;
 
         // Foreground intensity is a separate value in attrs,
         // So we need to check if this was applied to the current fg color.
@@ -61,71 +60,77 @@ impl ITerminalColor for WinApiColor {
             color = color | wincon::FOREGROUND_INTENSITY as u16;
         }
 
-        kernel::set_console_text_attribute(color, &self.screen_manager);
+        kernel::set_console_text_attribute(color, &self.screen_manager)?;
+        Ok(())
     }
 
-    fn reset(&self) {
-        self.set_bg(Color::Black);
-        self.set_fg(Color::White);
+    fn reset(&self) -> Result<()> {
+        self.set_bg(Color::Black)?;
+        self.set_fg(Color::White)
     }
+}
 
-    /// This will get the winapi color value from the Color and ColorType struct
-    fn color_value(&self, color: Color, color_type: ColorType) -> String {
-        use style::{Color, ColorType};
-
-        let winapi_color: u16;
-
-        let fg_green = wincon::FOREGROUND_GREEN;
-        let fg_red = wincon::FOREGROUND_RED;
-        let fg_blue = wincon::FOREGROUND_BLUE;
-        let fg_intensity = wincon::FOREGROUND_INTENSITY;
-
-        let bg_green = wincon::BACKGROUND_GREEN;
-        let bg_red = wincon::BACKGROUND_RED;
-        let bg_blue = wincon::BACKGROUND_BLUE;
-        let bg_intensity = wincon::BACKGROUND_INTENSITY;
-
-        match color_type {
-            ColorType::Foreground => {
-                winapi_color = match color {
-                    Color::Black => 0,
-                    Color::Red => fg_intensity | fg_red,
-                    Color::DarkRed => fg_red,
-                    Color::Green => fg_intensity | fg_green,
-                    Color::DarkGreen => fg_green,
-                    Color::Yellow => fg_intensity | fg_green | fg_red,
-                    Color::DarkYellow => fg_green | fg_red,
-                    Color::Blue => fg_intensity | fg_blue,
-                    Color::DarkBlue => fg_blue,
-                    Color::Magenta => fg_intensity | fg_red | fg_blue,
-                    Color::DarkMagenta => fg_red | fg_blue,
-                    Color::Cyan => fg_intensity | fg_green | fg_blue,
-                    Color::DarkCyan => fg_green | fg_blue,
-                    Color::Grey => fg_intensity,
-                    Color::White => fg_intensity | fg_red | fg_green | fg_blue,
-                };
-            }
-            ColorType::Background => {
-                winapi_color = match color {
-                    Color::Black => 0,
-                    Color::Red => bg_intensity | bg_red,
-                    Color::DarkRed => bg_red,
-                    Color::Green => bg_intensity | bg_green,
-                    Color::DarkGreen => bg_green,
-                    Color::Yellow => bg_intensity | bg_green | bg_red,
-                    Color::DarkYellow => bg_green | bg_red,
-                    Color::Blue => bg_intensity | bg_blue,
-                    Color::DarkBlue => bg_blue,
-                    Color::Magenta => bg_intensity | bg_red | bg_blue,
-                    Color::DarkMagenta => bg_red | bg_blue,
-                    Color::Cyan => bg_intensity | bg_green | bg_blue,
-                    Color::DarkCyan => bg_green | bg_blue,
-                    Color::Grey => bg_intensity,
-                    Color::White => bg_intensity | bg_red | bg_green | bg_blue,
-                };
-            }
-        };
-
-        winapi_color.to_string()
-    }
+/// Get the winapi color attributes of the given color.
+fn winapi_color_value(color: Color, color_type: ColorType) -> u16 {
+    let winapi_color: u16;
+
+    let fg_green = wincon::FOREGROUND_GREEN;
+    let fg_red = wincon::FOREGROUND_RED;
+    let fg_blue = wincon::FOREGROUND_BLUE;
+    let fg_intensity = wincon::FOREGROUND_INTENSITY;
+
+    let bg_green = wincon::BACKGROUND_GREEN;
+    let bg_red = wincon::BACKGROUND_RED;
+    let bg_blue = wincon::BACKGROUND_BLUE;
+    let bg_intensity = wincon::BACKGROUND_INTENSITY;
+
+    match color_type {
+        ColorType::Foreground => {
+            winapi_color = match color {
+                Color::Black => 0,
+                Color::Red => fg_intensity | fg_red,
+                Color::DarkRed => fg_red,
+                Color::Green => fg_intensity | fg_green,
+                Color::DarkGreen => fg_green,
+                Color::Yellow => fg_intensity | fg_green | fg_red,
+                Color::DarkYellow => fg_green | fg_red,
+                Color::Blue => fg_intensity | fg_blue,
+                Color::DarkBlue => fg_blue,
+                Color::Magenta => fg_intensity | fg_red | fg_blue,
+                Color::DarkMagenta => fg_red | fg_blue,
+                Color::Cyan => fg_intensity | fg_green | fg_blue,
+                Color::DarkCyan => fg_green | fg_blue,
+                Color::Grey => fg_intensity,
+                Color::White => fg_intensity | fg_red | fg_green | fg_blue,
+                // The console can only show the named colors, so the nearest of these is used.
+                Color::Rgb { .. } | Color::AnsiValue(_) => {
+                    return winapi_color_value(color.to_named(), color_type)
+                }
+            };
+        }
+        ColorType::Background => {
+            winapi_color = match color {
+                Color::Black => 0,
+                Color::Red => bg_intensity | bg_red,
+                Color::DarkRed => bg_red,
+                Color::Green => bg_intensity | bg_green,
+                Color::DarkGreen => bg_green,
+                Color::Yellow => bg_intensity | bg_green | bg_red,
+                Color::DarkYellow => bg_green | bg_red,
+                Color::Blue => bg_intensity | bg_blue,
+                Color::DarkBlue => bg_blue,
+                Color::Magenta => bg_intensity | bg_red | bg_blue,
+                Color::DarkMagenta => bg_red | bg_blue,
+                Color::Cyan => bg_intensity | bg_green | bg_blue,
+                Color::DarkCyan => bg_green | bg_blue,
+                Color::Grey => bg_intensity,
+                Color::White => bg_intensity | bg_red | bg_green | bg_blue,
+                Color::Rgb { .. } | Color::AnsiValue(_) => {
+                    return winapi_color_value(color.to_named(), color_type)
+                }
+            };
+        }
+    };
+
+    winapi_color
 }
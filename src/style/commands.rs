@@ -0,0 +1,87 @@
+//! This module contains the commands for styling related actions, see the `command` module for how to use them.
+
+use super::color::ansi_color;
+use super::{Attribute, Color, ColorType, Hyperlink};
+use command::Command;
+use shared::functions::without_control_chars;
+
+use std::fmt::Display;
+
+/// Set the foreground color to the given color.
+#[derive(Debug, Copy, Clone)]
+pub struct SetFg(pub Color);
+
+impl Command for SetFg {
+    fn ansi_code(&self) -> String {
+        format!(csi!("{}m"), ansi_color::color_value(self.0, ColorType::Foreground))
+    }
+}
+
+/// Set the background color to the given color.
+#[derive(Debug, Copy, Clone)]
+pub struct SetBg(pub Color);
+
+impl Command for SetBg {
+    fn ansi_code(&self) -> String {
+        format!(csi!("{}m"), ansi_color::color_value(self.0, ColorType::Background))
+    }
+}
+
+/// Apply the given attribute to the text that is written after this command.
+#[derive(Debug, Copy, Clone)]
+pub struct SetAttr(pub Attribute);
+
+impl Command for SetAttr {
+    fn ansi_code(&self) -> String {
+        format!(csi!("{}m"), self.0 as i16)
+    }
+}
+
+/// Reset the colors and attributes to the default.
+#[derive(Debug, Copy, Clone)]
+pub struct ResetColor;
+
+impl Command for ResetColor {
+    fn ansi_code(&self) -> String {
+        csi!("0m").to_string()
+    }
+}
+
+/// Print any displayable content, this makes it possible to mix text with other commands.
+#[derive(Debug, Copy, Clone)]
+pub struct Print<D: Display>(pub D);
+
+impl<D: Display> Command for Print<D> {
+    fn ansi_code(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+/// Start a hyperlink, the text that is written until `EndHyperlink` opens the uri when it is clicked.
+#[derive(Debug, Clone)]
+pub struct BeginHyperlink(pub Hyperlink);
+
+impl Command for BeginHyperlink {
+    fn ansi_code(&self) -> String {
+        // The parameters are separated by `:` and the parameters by `;`, so these can not be used in the id.
+        let params = match self.0.id {
+            Some(ref id) => format!(
+                "id={}",
+                without_control_chars(id).replace([':', ';'], "")
+            ),
+            None => String::new(),
+        };
+
+        format!(osc!("8;{};{}"), params, without_control_chars(&self.0.uri))
+    }
+}
+
+/// End the hyperlink that was started with `BeginHyperlink`.
+#[derive(Debug, Copy, Clone)]
+pub struct EndHyperlink;
+
+impl Command for EndHyperlink {
+    fn ansi_code(&self) -> String {
+        osc!("8;;").to_string()
+    }
+}
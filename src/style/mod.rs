@@ -1,10 +1,15 @@
 //! This module is used for styling the terminal text.
 //! Under styling we can think of coloring the font and applying attributes to it.
 
+pub mod commands;
+
 mod color;
+mod policy;
 mod styles;
 
 pub use self::color::color::{color, TerminalColor};
+pub use self::color::support::ColorSupport;
+pub use self::policy::{set_style_policy, style_policy, StylePolicy};
 pub use self::styles::objectstyle::ObjectStyle;
 pub use self::styles::styledobject::StyledObject;
 
@@ -25,7 +30,7 @@ pub enum Attribute {
 }
 
 /// Colors that are available for coloring the termainal font.
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum Color {
     Black,
 
@@ -50,16 +55,40 @@ pub enum Color {
     Grey,
     White,
 
-    #[cfg(unix)]
     Rgb {
         r: u8,
         g: u8,
         b: u8,
     },
-    #[cfg(unix)]
     AnsiValue(u8),
 }
 
+/// The target of a hyperlink, terminals that support hyperlinks open the uri when the linked text is clicked.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hyperlink {
+    pub uri: String,
+    /// Text with the same id is highlighted as one link, for example a link that is split over multiple lines.
+    pub id: Option<String>,
+}
+
+impl Hyperlink {
+    /// Create a hyperlink to the given uri, like `https://github.com/TimonPost/crossterm` or `file:///etc/hosts`.
+    pub fn new(uri: &str) -> Hyperlink {
+        Hyperlink {
+            uri: uri.to_string(),
+            id: None,
+        }
+    }
+
+    /// Create a hyperlink to the given uri with an id.
+    pub fn with_id(uri: &str, id: &str) -> Hyperlink {
+        Hyperlink {
+            uri: uri.to_string(),
+            id: Some(id.to_string()),
+        }
+    }
+}
+
 /// Color types that can be used to determine if the Color enum is an Fore- or Background Color
 #[derive(Debug, Copy, Clone)]
 pub enum ColorType {
@@ -0,0 +1,69 @@
+//! This module contains the policy that decides whether colors and attributes are written to the output.
+
+use super::color::support::{self, ColorSupport};
+use Context;
+
+use std::env;
+use std::sync::Mutex;
+
+/// The policy of the contexts that have no policy of their own.
+static STYLE_POLICY: Mutex<StylePolicy> = Mutex::new(StylePolicy::Auto);
+
+/// Decides whether colors and attributes are written, the text itself is always written.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum StylePolicy {
+    /// Always write colors and attributes.
+    Always,
+    /// Write colors and attributes when the output is a terminal that can show colors and `NO_COLOR` is not set,
+    /// `CLICOLOR_FORCE` (with a value other than `0`) writes them anyway.
+    Auto,
+    /// Never write colors and attributes.
+    Never,
+}
+
+/// Set the styling policy of all contexts that have no policy of their own, the default policy is `StylePolicy::Auto`.
+///
+/// Use `Context::set_style_policy` to set the policy of one context.
+///
+/// #Example
+///
+/// ```rust
+/// extern crate crossterm;
+/// use crossterm::style::{set_style_policy, StylePolicy};
+///
+/// // For example when the user passed `--color=never`.
+/// set_style_policy(StylePolicy::Never);
+///
+/// ```
+pub fn set_style_policy(policy: StylePolicy) {
+    *STYLE_POLICY.lock().unwrap() = policy;
+}
+
+/// Get the styling policy of the contexts that have no policy of their own.
+pub fn style_policy() -> StylePolicy {
+    *STYLE_POLICY.lock().unwrap()
+}
+
+/// Check whether colors and attributes should be written to the output of the given context.
+///
+/// With `StylePolicy::Auto` the output is a terminal when stdout is one, also when the escape codes go to `/dev/tty`.
+pub fn is_styling_enabled(context: &Context) -> bool {
+    let policy = context
+        .style_policy
+        .lock()
+        .unwrap()
+        .unwrap_or_else(style_policy);
+
+    match policy {
+        StylePolicy::Always => true,
+        StylePolicy::Never => false,
+        StylePolicy::Auto => {
+            if env::var("CLICOLOR_FORCE").map(|force| force != "0").unwrap_or(false) {
+                return true;
+            }
+
+            // The color support is `NoColor` when `NO_COLOR` is set.
+            context.output_is_tty && support::color_support() != ColorSupport::NoColor
+        }
+    }
+}
@@ -1,21 +1,20 @@
 //! This module contains the `object style` that can be applied to an `styled object`.
 
-use style::{Color, StyledObject};
+use style::{Color, Hyperlink, StyledObject};
 use Context;
 
 use std::fmt::Display;
-use std::rc::Rc;
+use std::sync::Arc;
 
-#[cfg(unix)]
 use super::super::Attribute;
 
 /// Struct that contains the style properties that can be applied to an displayable object.
-#[derive(Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ObjectStyle {
     pub fg_color: Option<Color>,
     pub bg_color: Option<Color>,
+    pub hyperlink: Option<Hyperlink>,
 
-    #[cfg(unix)]
     pub attrs: Vec<Attribute>,
 }
 
@@ -24,7 +23,7 @@ impl Default for ObjectStyle {
         ObjectStyle {
             fg_color: Some(Color::White),
             bg_color: Some(Color::Black),
-            #[cfg(unix)]
+            hyperlink: None,
             attrs: Vec::new(),
         }
     }
@@ -32,7 +31,9 @@ impl Default for ObjectStyle {
 
 impl ObjectStyle {
     /// Apply an `StyledObject` to the passed displayable object.
-    pub fn apply_to<D: Display>(&self, val: D, context: Rc<Context>) -> StyledObject<D>
+    ///
+    /// The style is only written when the styling policy of the context allows it, see `StylePolicy`.
+    pub fn apply_to<D: Display>(&self, val: D, context: Arc<Context>) -> StyledObject<D>
     {
         StyledObject {
             object_style: self.clone(),
@@ -46,7 +47,7 @@ impl ObjectStyle {
         return ObjectStyle {
             fg_color: None,
             bg_color: None,
-            #[cfg(unix)]
+            hyperlink: None,
             attrs: Vec::new(),
         };
     }
@@ -63,7 +64,12 @@ impl ObjectStyle {
         self
     }
 
-    #[cfg(unix)]
+    /// Link the text of `ObjectStyle` to the passed hyperlink.
+    pub fn link(mut self, hyperlink: Hyperlink) -> ObjectStyle {
+        self.hyperlink = Some(hyperlink);
+        self
+    }
+
     pub fn add_attr(&mut self, attr: Attribute) {
         self.attrs.push(attr);
     }
@@ -1,24 +1,24 @@
 //! This module contains the logic to style an object that contains some state witch can be styled.
 
-use Context;
+use shared::functions;
+use style::commands::{BeginHyperlink, EndHyperlink, ResetColor, SetAttr, SetBg, SetFg};
+use {Command, Context};
 
 use std::fmt::{self,Display};
-use std::io::Write;
-use std::rc::Rc;
+use std::sync::Arc;
 
-#[cfg(unix)]
 use super::super::Attribute;
 
 #[cfg(windows)]
 use super::super::super::manager::WinApiScreenManager;
 
-use style::{Color, ObjectStyle};
+use style::{policy, Color, Hyperlink, ObjectStyle};
 
 /// Struct that contains both the style and the content wits can be styled.
 pub struct StyledObject<D: Display> {
     pub object_style: ObjectStyle,
     pub content: D,
-    pub context: Rc<Context>,
+    pub context: Arc<Context>,
 }
 
 impl<D: Display> StyledObject<D>{
@@ -72,6 +72,32 @@ impl<D: Display> StyledObject<D>{
         self
     }
 
+    /// Link the styled object to the passed `Hyperlink`, the terminal opens the uri when the text is clicked.
+    ///
+    /// Terminals that do not support hyperlinks show the text without the link.
+    ///
+    /// #Example
+    ///
+    /// ```rust
+    /// extern crate crossterm;
+    /// use self::crossterm::style::Hyperlink;
+    /// use self::crossterm::terminal;
+    /// use self::crossterm::Context;
+    ///
+    /// let context = Context::new();
+    /// let term = terminal::terminal(&context);
+    ///
+    /// println!("{}", term.paint("crossterm").link(Hyperlink::new("https://github.com/TimonPost/crossterm")));
+    ///
+    /// // Text with the same id is highlighted as one link.
+    /// println!("{}", term.paint("src/lib.rs").link(Hyperlink::with_id("file:///src/lib.rs", "lib")));
+    ///
+    /// ```
+    pub fn link(mut self, hyperlink: Hyperlink) -> StyledObject<D> {
+        self.object_style = self.object_style.link(hyperlink);
+        self
+    }
+
     /// Set the attribute of an styled object to the passed `Attribute`
     ///
     /// #Example
@@ -84,62 +110,52 @@ impl<D: Display> StyledObject<D>{
     /// println!("{}", paint("Bold").attr(Attribute::Bold));
     ///
     /// ```
-    #[cfg(unix)]
     pub fn attr(mut self, attr: Attribute) -> StyledObject<D> {
         &self.object_style.add_attr(attr);
         self
     }
 
     /// Increase the font intensity.
-    #[cfg(unix)]
     #[inline(always)]
     pub fn bold(self) -> StyledObject<D> {
         self.attr(Attribute::Bold)
     }
     /// Faint (decreased intensity) (Not widely supported).
-    #[cfg(unix)]
     #[inline(always)]
     pub fn dim(self) -> StyledObject<D> {
         self.attr(Attribute::Dim)
     }
     /// Make the font italic (Not widely supported; Sometimes treated as inverse).
-    #[cfg(unix)]
     #[inline(always)]
     pub fn italic(self) -> StyledObject<D> {
         self.attr(Attribute::Italic)
     }
     /// Underline font.
-    #[cfg(unix)]
     #[inline(always)]
     pub fn underlined(self) -> StyledObject<D> {
         self.attr(Attribute::Underlined)
     }
     /// Slow Blink (less than 150 per minute; not widely supported).
-    #[cfg(unix)]
     #[inline(always)]
     pub fn slow_blink(self) -> StyledObject<D> {
         self.attr(Attribute::SlowBlink)
     }
     /// Rapid Blink (MS-DOS ANSI.SYS; 150+ per minute; not widely supported).
-    #[cfg(unix)]
     #[inline(always)]
     pub fn rapid_blink(self) -> StyledObject<D> {
         self.attr(Attribute::RapidBlink)
     }
     /// Swap foreground and background colors.
-    #[cfg(unix)]
     #[inline(always)]
     pub fn reverse(self) -> StyledObject<D> {
         self.attr(Attribute::Reverse)
     }
     /// Hide text (Not widely supported).
-    #[cfg(unix)]
     #[inline(always)]
     pub fn hidden(self) -> StyledObject<D> {
         self.attr(Attribute::Hidden)
     }
     /// Characters legible, but marked for deletion. Not widely supported.
-    #[cfg(unix)]
     #[inline(always)]
     pub fn crossed_out(self) -> StyledObject<D> {
         self.attr(Attribute::CrossedOut)
@@ -149,50 +165,70 @@ impl<D: Display> StyledObject<D>{
 impl <D:Display> Display for StyledObject<D>
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        // Without styling only the content is written, also without the hyperlink.
+        if !policy::is_styling_enabled(&self.context) {
+            return write!(f, "{}", self.content);
+        }
 
-        let mut colored_terminal = super::super::color(&self.context);
-        let mut reset = true;
+        // The windows console colors the text that is written while the color is set, so the content is written to the console directly.
+        if !functions::writes_ansi(&self.context) {
+            return self.write_to_console();
+        }
+
+        let mut string = String::new();
 
         if let Some(bg) = self.object_style.bg_color {
-            colored_terminal.set_bg(bg);
-            reset = true;
+            string.push_str(&SetBg(bg).ansi_code());
         }
 
         if let Some(fg) = self.object_style.fg_color {
-            colored_terminal.set_fg(fg);
-            reset = true;
+            string.push_str(&SetFg(fg).ansi_code());
         }
 
-        #[cfg(unix)]
-            for attr in self.object_style.attrs.iter() {
-            let mutex = &self.context.screen_manager;
-            {
-                let mut screen = mutex.lock().unwrap();
-                screen.write_string(format!(csi!("{}m"), *attr as i16));
-            }
-            reset = true;
+        for attr in self.object_style.attrs.iter() {
+            string.push_str(&SetAttr(*attr).ansi_code());
         }
 
-        let mutex = &self.context.screen_manager;
-        {
-            let mut screen_manager = mutex.lock().unwrap();
+        let hyperlink = match self.object_style.hyperlink {
+            Some(ref hyperlink) if functions::supports_hyperlinks(&self.context) => Some(hyperlink),
+            _ => None,
+        };
 
-            use std::fmt::Write;
-            let mut string = String::new();
-            write!(string, "{}", self.content).unwrap();
-            screen_manager.write_string(string);
+        if let Some(hyperlink) = hyperlink {
+            string.push_str(&BeginHyperlink(hyperlink.clone()).ansi_code());
         }
 
-        let mutex = &self.context.screen_manager;
-        {
-            let mut screen = mutex.lock().unwrap();
-            screen.flush();
+        write!(f, "{}{}", string, self.content)?;
+
+        if hyperlink.is_some() {
+            f.write_str(&EndHyperlink.ansi_code())?;
         }
 
-        if reset {
-            colored_terminal.reset();
+        f.write_str(&ResetColor.ansi_code())
+    }
+}
+
+impl<D: Display> StyledObject<D> {
+    /// Write the content with the WinApi colors, the attributes and hyperlinks can not be shown by the windows console.
+    fn write_to_console(&self) -> Result<(), fmt::Error> {
+        let colored_terminal = super::super::color(&self.context);
+
+        // Formatting can only fail with `fmt::Error`, the cause of a failed write is lost.
+        if let Some(bg) = self.object_style.bg_color {
+            colored_terminal.set_bg(bg).map_err(|_| fmt::Error)?;
+        }
+
+        if let Some(fg) = self.object_style.fg_color {
+            colored_terminal.set_fg(fg).map_err(|_| fmt::Error)?;
+        }
+
+        {
+            let mut screen_manager = self.context.screen_manager.lock().unwrap();
+            screen_manager
+                .write_content(&self.content.to_string())
+                .map_err(|_| fmt::Error)?;
         }
 
-        Ok(())
+        colored_terminal.reset().map_err(|_| fmt::Error)
     }
 }
@@ -1,73 +1,141 @@
 //! This is an `ANSI escape code` specific implementation for terminal related action.
 //! This module is used for windows 10 terminals and unix terminals by default.
 
-use super::{ClearType, ITerminal, Rc};
+use super::commands::*;
+use super::{Arc, ClearType, ClipboardSelection, ITerminal};
 use super::super::cursor::cursor;
 use shared::functions;
-use Context;
+use state::commands::shared_commands::{ScrollRegionCommand, TitleStackCommand};
+use {Command, Context, Result};
+
+use std::io;
 
 /// This struct is an ansi implementation for terminal related actions.
 pub struct AnsiTerminal {
-    context: Rc<Context>,
+    context: Arc<Context>,
 }
 
 impl AnsiTerminal {
-    pub fn new(context: Rc<Context>) -> Box<AnsiTerminal> {
+    pub fn new(context: Arc<Context>) -> Box<AnsiTerminal> {
         Box::from(AnsiTerminal { context: context })
     }
 }
 
 impl ITerminal for AnsiTerminal {
-    fn clear(&self, clear_type: ClearType) {
+    fn clear(&self, clear_type: ClearType) -> Result<()> {
         let mut screen_manager = self.context.screen_manager.lock().unwrap();
-        {
-            match clear_type {
-                ClearType::All => {
-                    screen_manager.write_str(csi!("2J"));
-                }
-                ClearType::FromCursorDown => {
-                    screen_manager.write_str(csi!("J"));
-                }
-                ClearType::FromCursorUp => {
-                    screen_manager.write_str(csi!("1J"));
-                }
-                ClearType::CurrentLine => {
-                    screen_manager.write_str(csi!("2K"));
-                }
-                ClearType::UntilNewLine => {
-                    screen_manager.write_str(csi!("K"));
-                }
-            };
-        }
+        screen_manager.write_string(Clear(clear_type).ansi_code())?;
+        Ok(())
     }
 
-    fn terminal_size(&self) -> (u16, u16) {
-        functions::get_terminal_size(&self.context.screen_manager)
+    fn terminal_size(&self) -> Result<(u16, u16)> {
+        functions::get_terminal_size(&self.context)
     }
 
-    fn scroll_up(&self, count: i16) {
+    fn scroll_up(&self, count: i16) -> Result<()> {
         let mut screen = self.context.screen_manager.lock().unwrap();
-        {
-            panic!();
-            screen.write_string(format!(csi!("{}S"), count));
-        }
+        screen.write_string(ScrollUp(count).ansi_code())?;
+        Ok(())
     }
 
-    fn scroll_down(&self, count: i16) {
+    fn scroll_down(&self, count: i16) -> Result<()> {
         let mut screen = self.context.screen_manager.lock().unwrap();
-        {
-            screen.write_string(format!(csi!("{}T"), count));
-        }
+        screen.write_string(ScrollDown(count).ansi_code())?;
+        Ok(())
     }
 
-    fn set_size(&self, width: i16, height: i16) {
+    fn set_size(&self, width: i16, height: i16) -> Result<()> {
         let mut screen = self.context.screen_manager.lock().unwrap();
-        {
-            screen.write_string(format!(csi!("8;{};{}t"), width, height));
+        screen.write_string(SetSize(width, height).ansi_code())?;
+        Ok(())
+    }
+
+    fn set_scroll_region(&self, top: u16, bottom: u16) -> Result<()> {
+        if top >= bottom {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "the top of the scroll region must be above the bottom",
+            ).into());
+        }
+
+        // The command takes the place of the previous region command, executing it sets the region.
+        ScrollRegionCommand::new(&self.context, top, bottom)?;
+        Ok(())
+    }
+
+    fn reset_scroll_region(&self) -> Result<()> {
+        let mut state = self.context.state_manager.lock().unwrap();
+
+        match state.named_change(ScrollRegionCommand::NAME) {
+            // Removing the command undoes it, which resets the region.
+            Some(id) => state.remove(id),
+            None => {
+                let mut screen = self.context.screen_manager.lock().unwrap();
+                screen.write_string(ResetScrollRegion.ansi_code())?;
+                Ok(())
+            }
         }
     }
 
+    fn insert_lines(&self, count: u16) -> Result<()> {
+        let mut screen = self.context.screen_manager.lock().unwrap();
+        screen.write_string(InsertLines(count).ansi_code())?;
+        Ok(())
+    }
+
+    fn delete_lines(&self, count: u16) -> Result<()> {
+        let mut screen = self.context.screen_manager.lock().unwrap();
+        screen.write_string(DeleteLines(count).ansi_code())?;
+        Ok(())
+    }
+
+    fn insert_chars(&self, count: u16) -> Result<()> {
+        let mut screen = self.context.screen_manager.lock().unwrap();
+        screen.write_string(InsertChars(count).ansi_code())?;
+        Ok(())
+    }
+
+    fn delete_chars(&self, count: u16) -> Result<()> {
+        let mut screen = self.context.screen_manager.lock().unwrap();
+        screen.write_string(DeleteChars(count).ansi_code())?;
+        Ok(())
+    }
+
+    fn erase_chars(&self, count: u16) -> Result<()> {
+        let mut screen = self.context.screen_manager.lock().unwrap();
+        screen.write_string(EraseChars(count).ansi_code())?;
+        Ok(())
+    }
+
+    fn set_title(&self, title: &str) -> Result<()> {
+        save_title(&self.context)?;
+
+        let mut screen = self.context.screen_manager.lock().unwrap();
+        screen.write_string(SetTitle(title).ansi_code())?;
+        Ok(())
+    }
+
+    fn set_icon_name(&self, name: &str) -> Result<()> {
+        save_title(&self.context)?;
+
+        let mut screen = self.context.screen_manager.lock().unwrap();
+        screen.write_string(SetIconName(name).ansi_code())?;
+        Ok(())
+    }
+
+    fn set_clipboard(&self, selection: ClipboardSelection, text: &str) -> Result<()> {
+        let mut screen = self.context.screen_manager.lock().unwrap();
+        screen.write_string(SetClipboard(selection, text).ansi_code())?;
+        Ok(())
+    }
+
     fn exit(&self) {
         functions::exit_terminal();
     }
 }
+
+/// Push the title of the user on the title stack the first time the title is changed, so that it is restored afterwards.
+fn save_title(context: &Context) -> Result<()> {
+    TitleStackCommand::new(context)?;
+    Ok(())
+}
@@ -0,0 +1,275 @@
+//! This module contains the commands for terminal related actions, see the `command` module for how to use them.
+
+use super::{ClearType, ClipboardSelection, Multiplexer};
+use command::Command;
+use shared::base64;
+use shared::functions::without_control_chars;
+
+use std::fmt::Display;
+
+/// Clear the terminal by specifying the clear type.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Clear(pub ClearType);
+
+impl Command for Clear {
+    fn ansi_code(&self) -> String {
+        match self.0 {
+            ClearType::All => csi!("2J"),
+            ClearType::FromCursorDown => csi!("J"),
+            ClearType::FromCursorUp => csi!("1J"),
+            ClearType::CurrentLine => csi!("2K"),
+            ClearType::UntilNewLine => csi!("K"),
+        }.to_string()
+    }
+}
+
+/// Scroll `n` lines up in the terminal.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ScrollUp(pub i16);
+
+impl Command for ScrollUp {
+    fn ansi_code(&self) -> String {
+        format!(csi!("{}S"), self.0)
+    }
+}
+
+/// Scroll `n` lines down in the terminal.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ScrollDown(pub i16);
+
+impl Command for ScrollDown {
+    fn ansi_code(&self) -> String {
+        format!(csi!("{}T"), self.0)
+    }
+}
+
+/// Resize the terminal to the given width and height.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct SetSize(pub i16, pub i16);
+
+impl Command for SetSize {
+    fn ansi_code(&self) -> String {
+        format!(csi!("8;{};{}t"), self.0, self.1)
+    }
+}
+
+/// Limit scrolling to the lines `top` to `bottom` (inclusive), the lines outside this region stay in place.
+///
+/// Note that the lines are 0 based, just like the position used by `TerminalCursor::goto`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct SetScrollRegion(pub u16, pub u16);
+
+impl Command for SetScrollRegion {
+    fn ansi_code(&self) -> String {
+        format!(csi!("{};{}r"), self.0.saturating_add(1), self.1.saturating_add(1))
+    }
+}
+
+/// Let the whole screen scroll again.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ResetScrollRegion;
+
+impl Command for ResetScrollRegion {
+    fn ansi_code(&self) -> String {
+        csi!("r").to_string()
+    }
+}
+
+/// Insert `n` blank lines at the cursor, the lines below are moved down within the scroll region.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct InsertLines(pub u16);
+
+impl Command for InsertLines {
+    fn ansi_code(&self) -> String {
+        format!(csi!("{}L"), self.0)
+    }
+}
+
+/// Delete `n` lines at the cursor, the lines below are moved up within the scroll region.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct DeleteLines(pub u16);
+
+impl Command for DeleteLines {
+    fn ansi_code(&self) -> String {
+        format!(csi!("{}M"), self.0)
+    }
+}
+
+/// Insert `n` blank characters at the cursor, the rest of the line is moved to the right.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct InsertChars(pub u16);
+
+impl Command for InsertChars {
+    fn ansi_code(&self) -> String {
+        format!(csi!("{}@"), self.0)
+    }
+}
+
+/// Delete `n` characters at the cursor, the rest of the line is moved to the left.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct DeleteChars(pub u16);
+
+impl Command for DeleteChars {
+    fn ansi_code(&self) -> String {
+        format!(csi!("{}P"), self.0)
+    }
+}
+
+/// Erase `n` characters starting at the cursor without moving the rest of the line.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct EraseChars(pub u16);
+
+impl Command for EraseChars {
+    fn ansi_code(&self) -> String {
+        format!(csi!("{}X"), self.0)
+    }
+}
+
+/// Set the title of the terminal window.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct SetTitle<D: Display>(pub D);
+
+impl<D: Display> Command for SetTitle<D> {
+    fn ansi_code(&self) -> String {
+        format!(osc!("2;{}"), without_control_chars(&self.0))
+    }
+}
+
+/// Set the icon name of the terminal window, this is the name shown when the window is minimized or in the taskbar.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct SetIconName<D: Display>(pub D);
+
+impl<D: Display> Command for SetIconName<D> {
+    fn ansi_code(&self) -> String {
+        format!(osc!("1;{}"), without_control_chars(&self.0))
+    }
+}
+
+/// Set both the title and the icon name of the terminal window.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct SetTitleAndIconName<D: Display>(pub D);
+
+impl<D: Display> Command for SetTitleAndIconName<D> {
+    fn ansi_code(&self) -> String {
+        format!(osc!("0;{}"), without_control_chars(&self.0))
+    }
+}
+
+/// Save the title and the icon name on the title stack of the terminal so that they can be restored with `PopTitle`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct PushTitle;
+
+impl Command for PushTitle {
+    fn ansi_code(&self) -> String {
+        csi!("22;0t").to_string()
+    }
+}
+
+/// Restore the title and the icon name that were saved last with `PushTitle`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct PopTitle;
+
+impl Command for PopTitle {
+    fn ansi_code(&self) -> String {
+        csi!("23;0t").to_string()
+    }
+}
+
+/// Copy the given text to the given selection of the terminal, this also works for programs on a remote machine over ssh.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct SetClipboard<D: Display>(pub ClipboardSelection, pub D);
+
+impl<D: Display> Command for SetClipboard<D> {
+    fn ansi_code(&self) -> String {
+        format!(
+            osc!("52;{};{}"),
+            self.0.code(),
+            base64::encode(self.1.to_string().as_bytes())
+        )
+    }
+}
+
+/// Pass the given command through a terminal multiplexer, so that the outer terminal performs it instead of the multiplexer.
+///
+/// This is needed for escape codes the multiplexer does not know, tmux only passes them through with `set -g allow-passthrough on`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Passthrough<C: Command>(pub Multiplexer, pub C);
+
+impl<C: Command> Command for Passthrough<C> {
+    fn ansi_code(&self) -> String {
+        let code = self.1.ansi_code();
+
+        match self.0 {
+            // Tmux needs the escape characters of the command to be doubled.
+            Multiplexer::Tmux => format!("\x1BPtmux;{}\x1B\\", code.replace('\x1B', "\x1B\x1B")),
+            // Screen ends the passthrough at the first string terminator and limits its length,
+            // so the terminator of the command is replaced by `BEL` and the command is passed through in parts.
+            Multiplexer::Screen => {
+                let chars: Vec<char> = code.replace("\x1B\\", "\x07").chars().collect();
+                let parts: Vec<String> = chars.chunks(76).map(|part| part.iter().collect()).collect();
+
+                format!("\x1BP{}\x1B\\", parts.join("\x1B\\\x1BP"))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Passthrough, SetClipboard, SetTitle};
+    use terminal::{ClipboardSelection, Multiplexer};
+    use Command;
+
+    #[test]
+    fn set_clipboard() {
+        assert_eq!(
+            SetClipboard(ClipboardSelection::Clipboard, "foobar").ansi_code(),
+            "\x1B]52;c;Zm9vYmFy\x1B\\"
+        );
+        assert_eq!(
+            SetClipboard(ClipboardSelection::Primary, "").ansi_code(),
+            "\x1B]52;p;\x1B\\"
+        );
+    }
+
+    #[test]
+    fn tmux_passthrough_doubles_the_escape_characters() {
+        assert_eq!(
+            Passthrough(Multiplexer::Tmux, SetTitle("hi")).ansi_code(),
+            "\x1BPtmux;\x1B\x1B]2;hi\x1B\x1B\\\x1B\\"
+        );
+    }
+
+    #[test]
+    fn short_screen_passthrough_is_one_part() {
+        assert_eq!(
+            Passthrough(Multiplexer::Screen, SetTitle("hi")).ansi_code(),
+            "\x1BP\x1B]2;hi\x07\x1B\\"
+        );
+    }
+
+    #[test]
+    fn long_screen_passthrough_is_split_in_parts() {
+        let text = "x".repeat(200);
+        let code = Passthrough(Multiplexer::Screen, SetTitle(&text)).ansi_code();
+
+        assert!(code.starts_with("\x1BP") && code.ends_with("\x1B\\"));
+
+        let parts: Vec<&str> = code[2..code.len() - 2].split("\x1B\\\x1BP").collect();
+
+        // `ESC ] 2 ;` + 200 characters + `BEL` is 205 characters.
+        let lengths: Vec<usize> = parts.iter().map(|part| part.chars().count()).collect();
+        assert_eq!(lengths, vec![76, 76, 53]);
+        assert_eq!(parts.concat(), format!("\x1B]2;{}\x07", text));
+    }
+
+    #[test]
+    fn screen_passthrough_splits_on_characters() {
+        let text = "é".repeat(100);
+        let code = Passthrough(Multiplexer::Screen, SetTitle(&text)).ansi_code();
+        let parts: Vec<&str> = code[2..code.len() - 2].split("\x1B\\\x1BP").collect();
+
+        assert!(parts.iter().all(|part| part.chars().count() <= 76));
+        assert_eq!(parts.concat(), format!("\x1B]2;{}\x07", text));
+    }
+}
@@ -6,6 +6,7 @@
 //! - clearing resizing scrolling the terminal.
 //!
 
+pub mod commands;
 pub mod terminal;
 
 mod ansi_terminal;
@@ -15,12 +16,15 @@ mod winapi_terminal;
 use self::ansi_terminal::AnsiTerminal;
 #[cfg(target_os = "windows")]
 use self::winapi_terminal::WinApiTerminal;
-use std::rc::Rc;
+use std::sync::Arc;
 
 pub use self::terminal::terminal;
-use Context;
+#[cfg(not(target_os = "windows"))]
+pub use self::terminal::{ResizeEvents, ResizeHandle};
+use Result;
 
 /// Enum that can be used for the kind of clearing that can be done in the terminal.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum ClearType {
     All,
     FromCursorDown,
@@ -29,6 +33,38 @@ pub enum ClearType {
     UntilNewLine,
 }
 
+/// The selection of the terminal that is used as clipboard, most terminals only support `Clipboard` and `Primary`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ClipboardSelection {
+    /// The clipboard that is used for copy and paste.
+    Clipboard,
+    /// The primary selection, this is the selected text that is pasted with the middle mouse button on X11.
+    Primary,
+    /// The secondary selection.
+    Secondary,
+    /// The selection that is configured in the terminal, this is the primary selection or the clipboard.
+    Select,
+}
+
+impl ClipboardSelection {
+    /// The code of this selection in the clipboard escape code.
+    pub fn code(&self) -> char {
+        match *self {
+            ClipboardSelection::Clipboard => 'c',
+            ClipboardSelection::Primary => 'p',
+            ClipboardSelection::Secondary => 'q',
+            ClipboardSelection::Select => 's',
+        }
+    }
+}
+
+/// A terminal multiplexer that escape codes can be passed through to the outer terminal with `Passthrough`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Multiplexer {
+    Tmux,
+    Screen,
+}
+
 ///! This trait defines the actions that can be preformed with the terminal.
 ///! This trait can be implemented so that an concrete implementation of the ITerminal can forfill
 ///! the wishes to work on an specific platform.
@@ -37,17 +73,37 @@ pub enum ClearType {
 ///!
 ///! This trait is implemented for `WINAPI` (Windows specific) and `ANSI` (Unix specific),
 ///! so that cursor related actions can be preformed on both unix and windows systems.
-pub trait ITerminal {
+pub trait ITerminal: Send + Sync {
     /// Clear the current cursor by specifying the clear type
-    fn clear(&self, clear_type: ClearType);
+    fn clear(&self, clear_type: ClearType) -> Result<()>;
     /// Get the terminal size (x,y)
-    fn terminal_size(&self) -> (u16, u16);
+    fn terminal_size(&self) -> Result<(u16, u16)>;
     /// Scroll `n` lines up in the current terminal.
-    fn scroll_up(&self, count: i16);
+    fn scroll_up(&self, count: i16) -> Result<()>;
     /// Scroll `n` lines down in the current terminal.
-    fn scroll_down(&self, count: i16);
+    fn scroll_down(&self, count: i16) -> Result<()>;
     /// Resize terminal to the given width and height.
-    fn set_size(&self, width: i16, height: i16);
+    fn set_size(&self, width: i16, height: i16) -> Result<()>;
+    /// Limit scrolling to the lines `top` to `bottom` (inclusive).
+    fn set_scroll_region(&self, top: u16, bottom: u16) -> Result<()>;
+    /// Let the whole screen scroll again.
+    fn reset_scroll_region(&self) -> Result<()>;
+    /// Insert `n` blank lines at the cursor.
+    fn insert_lines(&self, count: u16) -> Result<()>;
+    /// Delete `n` lines at the cursor.
+    fn delete_lines(&self, count: u16) -> Result<()>;
+    /// Insert `n` blank characters at the cursor.
+    fn insert_chars(&self, count: u16) -> Result<()>;
+    /// Delete `n` characters at the cursor.
+    fn delete_chars(&self, count: u16) -> Result<()>;
+    /// Erase `n` characters starting at the cursor.
+    fn erase_chars(&self, count: u16) -> Result<()>;
+    /// Set the title of the terminal window.
+    fn set_title(&self, title: &str) -> Result<()>;
+    /// Set the icon name of the terminal window.
+    fn set_icon_name(&self, name: &str) -> Result<()>;
+    /// Copy the given text to the given selection of the terminal.
+    fn set_clipboard(&self, selection: ClipboardSelection, text: &str) -> Result<()>;
     /// Close the current terminal
     fn exit(&self);
 }
@@ -4,24 +4,49 @@
 use super::super::shared::functions;
 use super::super::style;
 use super::*;
-use Context;
+use {Context, Result};
+#[cfg(not(target_os = "windows"))]
+use shared::base64;
 
 use std::fmt;
 use std::io::Write;
 
-use std::rc::Rc;
+use std::sync::Arc;
+#[cfg(not(target_os = "windows"))]
+use std::io;
+#[cfg(not(target_os = "windows"))]
+use std::ops::Deref;
+#[cfg(not(target_os = "windows"))]
+use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(not(target_os = "windows"))]
+use std::sync::mpsc::{self, Receiver};
+#[cfg(not(target_os = "windows"))]
+use std::thread::{self, JoinHandle};
+#[cfg(not(target_os = "windows"))]
+use std::time::Duration;
+
+#[cfg(not(target_os = "windows"))]
+use kernel::unix_kernel::{
+    input as kernel_input, signal as kernel_signal, signal::ResizeListener,
+    terminal as kernel_terminal,
+};
+
+/// The time the terminal gets to send the content of the clipboard.
+#[cfg(not(target_os = "windows"))]
+const CLIPBOARD_TIMEOUT: Duration = Duration::from_secs(2);
 
 /// Struct that stores an specific platform implementation for terminal related actions.
 pub struct Terminal {
     terminal: Box<ITerminal>,
-    context: Rc<Context>,
+    context: Arc<Context>,
 }
 
 impl Terminal {
     /// Create new terminal instance whereon terminal related actions can be performed.
-    pub fn new(context: Rc<Context>) -> Terminal {
+    pub fn new(context: Arc<Context>) -> Terminal {
         #[cfg(target_os = "windows")]
-        let terminal = functions::get_module::<Box<ITerminal>>(
+        let terminal = functions::get_module_for_context::<Box<ITerminal>>(
+            &context,
             WinApiTerminal::new(context.clone()),
             AnsiTerminal::new(context.clone()),
         ).unwrap();
@@ -60,8 +85,8 @@ impl Terminal {
     /// term.clear(terminal::ClearType::UntilNewLine);
     ///
     /// ```
-    pub fn clear(&self, clear_type: ClearType) {
-        self.terminal.clear(clear_type);
+    pub fn clear(&self, clear_type: ClearType) -> Result<()> {
+        self.terminal.clear(clear_type)
     }
 
     /// Get the terminal size (x,y).
@@ -77,12 +102,180 @@ impl Terminal {
     /// let context = Context::new();
     /// let mut term = terminal::terminal(&context);
     ///
-    /// let size = term.terminal_size();
-    /// println!("{:?}", size);
+    /// if let Ok((columns, rows)) = term.terminal_size() {
+    ///     println!("{}, {}", columns, rows);
+    /// }
+    ///
+    /// ```
+    pub fn terminal_size(&self) -> Result<(u16, u16)> {
+        self.terminal.terminal_size()
+    }
+
+    /// Check whether the output of the context is connected to a terminal.
+    ///
+    /// This is `false` when the output of the program is redirected to a file or piped into an other program.
+    /// Crossterm then writes its escape codes to `/dev/tty` on unix systems,
+    /// so that the output that goes into the pipe does not contain escape codes.
+    /// The output of `Context::with_output` is not seen as a terminal.
+    ///
+    /// #Example
+    ///
+    /// ```rust
+    ///
+    /// extern crate crossterm;
+    /// use crossterm::terminal;
+    /// use crossterm::Context;
+    ///
+    /// let context = Context::new();
+    /// let term = terminal::terminal(&context);
+    ///
+    /// if !term.is_tty() {
+    ///     println!("the output is redirected");
+    /// }
+    ///
+    /// ```
+    pub fn is_tty(&self) -> bool {
+        self.context.output_is_tty
+    }
+
+    /// Check whether the terminal can show hyperlinks, see `StyledObject::link`.
+    ///
+    /// Terminals can not be asked for this, so it is detected with the environment variables the known terminals set.
+    /// Set `FORCE_HYPERLINK` to `1` or `0` to override the detection.
+    ///
+    /// #Example
+    ///
+    /// ```rust
+    ///
+    /// extern crate crossterm;
+    /// use crossterm::terminal;
+    /// use crossterm::Context;
+    ///
+    /// let context = Context::new();
+    /// let term = terminal::terminal(&context);
+    ///
+    /// if !term.supports_hyperlinks() {
+    ///     println!("the links are shown as plain text");
+    /// }
+    ///
+    /// ```
+    pub fn supports_hyperlinks(&self) -> bool {
+        functions::supports_hyperlinks(&self.context)
+    }
+
+    /// Call the given callback with the new terminal size every time the terminal is resized.
+    ///
+    /// The callback is called from a background thread, so it also works for programs that do not read input.
+    /// The thread stops when the returned `ResizeHandle` is dropped.
+    /// When you read input with `TerminalInput` you can use `Event::Resize` instead.
+    /// The size has the same form as the size returned by `terminal_size()`.
+    ///
+    /// #Example
+    ///
+    /// ```rust,no_run
+    ///
+    /// extern crate crossterm;
+    /// use crossterm::terminal;
+    /// use crossterm::Context;
+    ///
+    /// let context = Context::new();
+    /// let term = terminal::terminal(&context);
+    ///
+    /// let _handle = term.on_resize(|columns, rows| println!("new size: {}, {}", columns, rows)).unwrap();
+    ///
+    /// ```
+    #[cfg(not(target_os = "windows"))]
+    pub fn on_resize<F>(&self, mut callback: F) -> Result<ResizeHandle>
+    where
+        F: FnMut(u16, u16) + Send + 'static,
+    {
+        let handle = spawn_resize_thread(&self.context, move |columns, rows| {
+            callback(columns, rows);
+            true
+        })?;
+
+        Ok(handle)
+    }
+
+    /// Get a channel that receives the new terminal size every time the terminal is resized.
+    ///
+    /// The background thread that sends the sizes stops when the returned `ResizeEvents` is dropped,
+    /// it dereferences to the `Receiver` of the channel.
+    /// The size has the same form as the size returned by `terminal_size()`.
+    ///
+    /// #Example
+    ///
+    /// ```rust,no_run
+    ///
+    /// extern crate crossterm;
+    /// use crossterm::terminal;
+    /// use crossterm::Context;
+    ///
+    /// let context = Context::new();
+    /// let term = terminal::terminal(&context);
+    ///
+    /// let resizes = term.resize_events().unwrap();
+    ///
+    /// for (columns, rows) in resizes.iter() {
+    ///     println!("new size: {}, {}", columns, rows);
+    /// }
     ///
     /// ```
-    pub fn terminal_size(&self) -> (u16, u16) {
-            return self.terminal.terminal_size();
+    #[cfg(not(target_os = "windows"))]
+    pub fn resize_events(&self) -> Result<ResizeEvents> {
+        let (sender, receiver) = mpsc::channel();
+
+        let handle = spawn_resize_thread(&self.context, move |columns, rows| {
+            sender.send((columns, rows)).is_ok()
+        })?;
+
+        Ok(ResizeEvents {
+            receiver,
+            _handle: handle,
+        })
+    }
+
+    /// Suspend the process like `Ctrl-Z` does in a shell, this returns when the process is continued (`fg`).
+    ///
+    /// In raw mode `Ctrl-Z` is read as a key instead of suspending the process, call this method when that key is read.
+    /// All changes made to the terminal are undone before the process is stopped, so that the shell is usable.
+    /// When the process continues the changes are made again and `redraw` is called,
+    /// the alternate screen is empty by then so the screen has to be drawn again.
+    ///
+    /// #Example
+    ///
+    /// ```rust,no_run
+    ///
+    /// extern crate crossterm;
+    /// use crossterm::terminal;
+    /// use crossterm::Context;
+    ///
+    /// let context = Context::new();
+    /// let term = terminal::terminal(&context);
+    ///
+    /// term.suspend(|| println!("welcome back")).unwrap();
+    ///
+    /// ```
+    #[cfg(not(target_os = "windows"))]
+    pub fn suspend<F: FnOnce()>(&self, redraw: F) -> Result<()> {
+        self.context.state_manager.lock().unwrap().suspend_changes();
+
+        {
+            let mut screen_manager = self.context.screen_manager.lock().unwrap();
+            // Show the cursor and reset the colors for the shell.
+            screen_manager.write_str(concat!(csi!("?25h"), csi!("0m")))?;
+            screen_manager.flush()?;
+        }
+
+        let stopped = kernel_signal::stop_process();
+
+        let resumed = self.context.state_manager.lock().unwrap().resume_changes();
+        self.context.screen_manager.lock().unwrap().flush()?;
+
+        stopped?;
+        resumed?;
+        redraw();
+        Ok(())
     }
 
     /// Scroll `n` lines up in the current terminal.
@@ -102,8 +295,8 @@ impl Terminal {
     /// let size = term.scroll_up(5);
     ///
     /// ```
-    pub fn scroll_up(&self, count: i16) {
-        self.terminal.scroll_up(count);
+    pub fn scroll_up(&self, count: i16) -> Result<()> {
+        self.terminal.scroll_up(count)
     }
 
     /// Scroll `n` lines up in the current terminal.
@@ -123,8 +316,8 @@ impl Terminal {
     /// let size = term.scroll_down(5);
     ///
     /// ```
-    pub fn scroll_down(&self, count: i16) {
-        self.terminal.scroll_down(count);
+    pub fn scroll_down(&self, count: i16) -> Result<()> {
+        self.terminal.scroll_down(count)
     }
 
     /// Set the terminal size. Note that not all terminals can be set to a very small scale.
@@ -144,12 +337,267 @@ impl Terminal {
     /// let size = term.set_size(10,10);
     ///
     /// ```
-    pub fn set_size(&self, width: i16, height: i16) {
-        self.terminal.set_size(width, height);
+    pub fn set_size(&self, width: i16, height: i16) -> Result<()> {
+        self.terminal.set_size(width, height)
+    }
+
+    /// Limit scrolling to the lines `top` to `bottom` (inclusive), the lines outside this region stay in place.
+    ///
+    /// This can be used for a fixed header or footer above and below a scrolling part.
+    /// The lines are 0 based and the region is reset when the terminal is restored.
+    /// Note that most terminals move the cursor to the top left corner when the region is set.
+    ///
+    /// #Example
+    ///
+    /// ```rust
+    ///
+    /// extern crate crossterm;
+    /// use crossterm::terminal;
+    /// use crossterm::Context;
+    ///
+    /// let context = Context::new();
+    /// let mut term = terminal::terminal(&context);
+    ///
+    /// // Keep the first line as header and the last line of a 24 line terminal as footer.
+    /// term.set_scroll_region(1, 22);
+    ///
+    /// ```
+    pub fn set_scroll_region(&self, top: u16, bottom: u16) -> Result<()> {
+        self.terminal.set_scroll_region(top, bottom)
+    }
+
+    /// Let the whole screen scroll again after `set_scroll_region` was used.
+    ///
+    /// #Example
+    ///
+    /// ```rust
+    ///
+    /// extern crate crossterm;
+    /// use crossterm::terminal;
+    /// use crossterm::Context;
+    ///
+    /// let context = Context::new();
+    /// let mut term = terminal::terminal(&context);
+    ///
+    /// term.reset_scroll_region();
+    ///
+    /// ```
+    pub fn reset_scroll_region(&self) -> Result<()> {
+        self.terminal.reset_scroll_region()
+    }
+
+    /// Insert `n` blank lines at the cursor, the lines below are moved down within the scroll region.
+    ///
+    /// #Example
+    ///
+    /// ```rust
+    ///
+    /// extern crate crossterm;
+    /// use crossterm::terminal;
+    /// use crossterm::Context;
+    ///
+    /// let context = Context::new();
+    /// let mut term = terminal::terminal(&context);
+    ///
+    /// // insert 2 lines at the cursor
+    /// term.insert_lines(2);
+    ///
+    /// ```
+    pub fn insert_lines(&self, count: u16) -> Result<()> {
+        self.terminal.insert_lines(count)
+    }
+
+    /// Delete `n` lines at the cursor, the lines below are moved up within the scroll region.
+    ///
+    /// #Example
+    ///
+    /// ```rust
+    ///
+    /// extern crate crossterm;
+    /// use crossterm::terminal;
+    /// use crossterm::Context;
+    ///
+    /// let context = Context::new();
+    /// let mut term = terminal::terminal(&context);
+    ///
+    /// // delete 2 lines at the cursor
+    /// term.delete_lines(2);
+    ///
+    /// ```
+    pub fn delete_lines(&self, count: u16) -> Result<()> {
+        self.terminal.delete_lines(count)
+    }
+
+    /// Insert `n` blank characters at the cursor, the rest of the line is moved to the right.
+    ///
+    /// #Example
+    ///
+    /// ```rust
+    ///
+    /// extern crate crossterm;
+    /// use crossterm::terminal;
+    /// use crossterm::Context;
+    ///
+    /// let context = Context::new();
+    /// let mut term = terminal::terminal(&context);
+    ///
+    /// // insert 5 spaces at the cursor
+    /// term.insert_chars(5);
+    ///
+    /// ```
+    pub fn insert_chars(&self, count: u16) -> Result<()> {
+        self.terminal.insert_chars(count)
+    }
+
+    /// Delete `n` characters at the cursor, the rest of the line is moved to the left.
+    ///
+    /// #Example
+    ///
+    /// ```rust
+    ///
+    /// extern crate crossterm;
+    /// use crossterm::terminal;
+    /// use crossterm::Context;
+    ///
+    /// let context = Context::new();
+    /// let mut term = terminal::terminal(&context);
+    ///
+    /// // delete 5 characters at the cursor
+    /// term.delete_chars(5);
+    ///
+    /// ```
+    pub fn delete_chars(&self, count: u16) -> Result<()> {
+        self.terminal.delete_chars(count)
+    }
+
+    /// Erase `n` characters starting at the cursor, the rest of the line stays in place.
+    ///
+    /// #Example
+    ///
+    /// ```rust
+    ///
+    /// extern crate crossterm;
+    /// use crossterm::terminal;
+    /// use crossterm::Context;
+    ///
+    /// let context = Context::new();
+    /// let mut term = terminal::terminal(&context);
+    ///
+    /// // erase 5 characters at the cursor
+    /// term.erase_chars(5);
+    ///
+    /// ```
+    pub fn erase_chars(&self, count: u16) -> Result<()> {
+        self.terminal.erase_chars(count)
+    }
+
+    /// Set the title of the terminal window, for example to show the current file or job.
+    ///
+    /// The title of the user is saved the first time the title or icon name is changed and it is restored when the terminal is restored.
+    /// Control characters in the title are removed.
+    ///
+    /// #Example
+    ///
+    /// ```rust
+    ///
+    /// extern crate crossterm;
+    /// use crossterm::terminal;
+    /// use crossterm::Context;
+    ///
+    /// let context = Context::new();
+    /// let mut term = terminal::terminal(&context);
+    ///
+    /// term.set_title("Building crossterm");
+    ///
+    /// ```
+    pub fn set_title(&self, title: &str) -> Result<()> {
+        self.terminal.set_title(title)
+    }
+
+    /// Set the icon name of the terminal window, this is the name shown when the window is minimized.
+    ///
+    /// The icon name is restored together with the title, note that the windows console has no icon name.
+    ///
+    /// #Example
+    ///
+    /// ```rust
+    ///
+    /// extern crate crossterm;
+    /// use crossterm::terminal;
+    /// use crossterm::Context;
+    ///
+    /// let context = Context::new();
+    /// let mut term = terminal::terminal(&context);
+    ///
+    /// term.set_icon_name("build");
+    ///
+    /// ```
+    pub fn set_icon_name(&self, name: &str) -> Result<()> {
+        self.terminal.set_icon_name(name)
+    }
+
+    /// Copy the given text to the given selection of the terminal.
+    ///
+    /// The terminal sets the clipboard of the machine it runs on, so this also works for programs that run on a remote machine over ssh.
+    /// Note that some terminals have to be configured to allow this and that the windows console does not support it.
+    /// Use the `Passthrough` command to set the clipboard from inside tmux or screen when they do not pass it on themselves.
+    ///
+    /// #Example
+    ///
+    /// ```rust
+    ///
+    /// extern crate crossterm;
+    /// use crossterm::terminal::{self, ClipboardSelection};
+    /// use crossterm::Context;
+    ///
+    /// let context = Context::new();
+    /// let mut term = terminal::terminal(&context);
+    ///
+    /// term.set_clipboard(ClipboardSelection::Clipboard, "copied from crossterm");
+    ///
+    /// ```
+    pub fn set_clipboard(&self, selection: ClipboardSelection, text: &str) -> Result<()> {
+        self.terminal.set_clipboard(selection, text)
+    }
+
+    /// Get the text of the given selection of the terminal.
+    ///
+    /// The terminal is asked for the content and gets 2 seconds to answer.
+    /// Most terminals do not allow programs to read the clipboard by default,
+    /// an error of the kind `ErrorKind::Timeout` is returned when the terminal does not answer.
+    ///
+    /// #Example
+    ///
+    /// ```rust,no_run
+    ///
+    /// extern crate crossterm;
+    /// use crossterm::terminal::{self, ClipboardSelection};
+    /// use crossterm::Context;
+    ///
+    /// let context = Context::new();
+    /// let term = terminal::terminal(&context);
+    ///
+    /// match term.get_clipboard(ClipboardSelection::Clipboard) {
+    ///     Ok(text) => println!("The clipboard contains: {}", text),
+    ///     Err(e) => println!("The clipboard could not be read: {}", e),
+    /// }
+    ///
+    /// ```
+    #[cfg(not(target_os = "windows"))]
+    pub fn get_clipboard(&self, selection: ClipboardSelection) -> Result<String> {
+        let encoded = kernel_terminal::clipboard(&self.context, selection.code(), CLIPBOARD_TIMEOUT)?;
+
+        let invalid = |message| io::Error::new(io::ErrorKind::InvalidData, message);
+        let bytes = base64::decode(&encoded).ok_or_else(|| invalid("the clipboard is not valid base64"))?;
+        let text = String::from_utf8(bytes).map_err(|_| invalid("the clipboard does not contain valid UTF-8"))?;
+
+        Ok(text)
     }
 
     /// Wraps an displayable object so it can be formatted with colors and attributes.
     ///
+    /// The colors and attributes are left out when the styling policy disables them, see `style::StylePolicy`.
+    ///
     /// Check `/examples/color` in the libary for more spesific examples.
     ///
     /// #Example
@@ -217,21 +665,100 @@ impl Terminal {
     /// let size = term.write("Some text \n Some text on new line");
     ///
     /// ```
-    pub fn write<D: fmt::Display>(&self, value: D) {
-        let mut mutex = &self.context.screen_manager;
-        {
-            let mut screen_manager = mutex.lock().unwrap();
+    pub fn write<D: fmt::Display>(&self, value: D) -> Result<()> {
+        let mut screen_manager = self.context.screen_manager.lock().unwrap();
 
-            use std::fmt::Write;
-            let mut string = String::new();
-            write!(string, "{}", value).unwrap();
+        use std::fmt::Write;
+        let mut string = String::new();
+        write!(string, "{}", value).unwrap();
 
-            screen_manager.write_string(string);
-            screen_manager.flush();
+        screen_manager.write_content(&string)?;
+        Ok(())
+    }
+}
+
+/// Stops the thread of `Terminal::on_resize` when it is dropped.
+#[cfg(not(target_os = "windows"))]
+#[must_use = "the callback is not called anymore when the handle is dropped"]
+pub struct ResizeHandle {
+    listener: Arc<ResizeListener>,
+    stopped: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+#[cfg(not(target_os = "windows"))]
+impl Drop for ResizeHandle {
+    fn drop(&mut self) {
+        self.stopped.store(true, Ordering::SeqCst);
+        self.listener.wake();
+
+        // Wait for the thread so that the listener is unregistered when this returns,
+        // unless the handle is dropped by the callback itself.
+        if let Some(thread) = self.thread.take() {
+            if thread.thread().id() != thread::current().id() {
+                let _ = thread.join();
+            }
         }
     }
 }
 
+/// The new terminal sizes of `Terminal::resize_events`, the thread that sends them stops when this is dropped.
+#[cfg(not(target_os = "windows"))]
+pub struct ResizeEvents {
+    receiver: Receiver<(u16, u16)>,
+    _handle: ResizeHandle,
+}
+
+#[cfg(not(target_os = "windows"))]
+impl Deref for ResizeEvents {
+    type Target = Receiver<(u16, u16)>;
+
+    fn deref(&self) -> &Receiver<(u16, u16)> {
+        &self.receiver
+    }
+}
+
+/// Start a thread that calls `on_resize` with the new terminal size on every resize,
+/// until `on_resize` returns `false` or the returned handle is dropped.
+#[cfg(not(target_os = "windows"))]
+fn spawn_resize_thread<F>(context: &Context, mut on_resize: F) -> io::Result<ResizeHandle>
+where
+    F: FnMut(u16, u16) -> bool + Send + 'static,
+{
+    // Register the listener before the thread starts so that no resize is missed after this function returns.
+    let listener = Arc::new(ResizeListener::new()?);
+    let stopped = Arc::new(AtomicBool::new(false));
+    let size_fd = kernel_terminal::size_fd(context);
+
+    let mut handle = ResizeHandle {
+        listener: listener.clone(),
+        stopped: stopped.clone(),
+        thread: None,
+    };
+
+    let thread = thread::spawn(move || {
+        while kernel_input::poll_fds(&[listener.fd()], None).is_ok() {
+            if stopped.load(Ordering::SeqCst) {
+                break;
+            }
+
+            if listener.take_resized() {
+                let (columns, rows) = match kernel_terminal::terminal_size(size_fd) {
+                    Ok(size) => size,
+                    Err(_) => break,
+                };
+
+                if !on_resize(columns, rows) {
+                    break;
+                }
+            }
+        }
+    });
+
+    handle.thread = Some(thread);
+    Ok(handle)
+}
+
 /// Get an Terminal implementation whereon terminal related actions can be performed.
 ///
 /// Check `/examples/version/terminal` in the libary for more spesific examples.
@@ -253,6 +780,24 @@ impl Terminal {
 ///
 /// ```
 ///
-pub fn terminal(context: &Rc<Context>) -> Box<Terminal> {
+pub fn terminal(context: &Arc<Context>) -> Box<Terminal> {
     Box::from(Terminal::new(context.clone()))
 }
+
+#[cfg(all(test, not(target_os = "windows")))]
+mod tests {
+    use super::terminal;
+    use Context;
+
+    #[test]
+    fn dropped_resize_handles_stop_listening() {
+        let context = Context::with_output(Box::new(Vec::new()));
+        let term = terminal(&context);
+
+        // There are more handles than listener slots, every slot is free again after the drop.
+        for _ in 0..40 {
+            drop(term.on_resize(|_, _| {}).unwrap());
+            drop(term.resize_events().unwrap());
+        }
+    }
+}
@@ -3,29 +3,31 @@
 
 use super::super::shared::functions;
 use super::super::ScreenManager;
-use super::{ClearType, ITerminal, Rc};
+use super::{Arc, ClearType, ClipboardSelection, ITerminal};
 use cursor::cursor;
 use kernel::windows_kernel::{kernel, terminal};
+use state::commands::win_commands::TitleStackCommand;
 use winapi::um::wincon::{CONSOLE_SCREEN_BUFFER_INFO, COORD, SMALL_RECT};
-use Context;
+use {Context, Error, Result};
 
+use std::io;
 use std::sync::Mutex;
 
 /// This struct is an windows implementation for terminal related actions.
 pub struct WinApiTerminal {
-    context: Rc<Context>,
+    context: Arc<Context>,
 }
 
 impl WinApiTerminal {
-    pub fn new(context: Rc<Context>) -> Box<WinApiTerminal> {
+    pub fn new(context: Arc<Context>) -> Box<WinApiTerminal> {
         Box::from(WinApiTerminal { context })
     }
 }
 
 impl ITerminal for WinApiTerminal {
-    fn clear(&self, clear_type: ClearType) {
+    fn clear(&self, clear_type: ClearType) -> Result<()> {
         let csbi = kernel::get_console_screen_buffer_info(&self.context.screen_manager);
-        let pos = cursor(&self.context).pos();
+        let pos = cursor(&self.context).pos()?;
 
         match clear_type {
             ClearType::All => clear_entire_screen(csbi, &self.context),
@@ -33,14 +35,14 @@ impl ITerminal for WinApiTerminal {
             ClearType::FromCursorUp => clear_before_cursor(pos, csbi, &self.context),
             ClearType::CurrentLine => clear_current_line(pos, csbi, &self.context),
             ClearType::UntilNewLine => clear_until_line(pos, csbi, &self.context),
-        };
+        }
     }
 
-    fn terminal_size(&self) -> (u16, u16) {
-        terminal::terminal_size(&self.context.screen_manager)
+    fn terminal_size(&self) -> Result<(u16, u16)> {
+        Ok(terminal::terminal_size(&self.context.screen_manager))
     }
 
-    fn scroll_up(&self, count: i16) {
+    fn scroll_up(&self, count: i16) -> Result<()> {
         let csbi = kernel::get_console_screen_buffer_info(&self.context.screen_manager);
 
         // Set srctWindow to the current window size and location.
@@ -53,20 +55,19 @@ impl ITerminal for WinApiTerminal {
             srct_window.Bottom = count; // move bottom down
 
             let success = kernel::set_console_info(false, &mut srct_window, &self.context.screen_manager);
-            if success {
-                panic!("Something went wrong when scrolling down");
+            if !success {
+                return Err(io::Error::last_os_error().into());
             }
         }
-    }
 
-    fn scroll_down(&self, count: i16) {
+        Ok(())
+    }
 
+    fn scroll_down(&self, count: i16) -> Result<()> {
         let csbi = kernel::get_console_screen_buffer_info(&self.context.screen_manager);
         // Set srctWindow to the current window size and location.
         let mut srct_window = csbi.srWindow;
 
-        panic!("window top: {} , window bottom: {} | {}, {}", srct_window.Top, srct_window.Bottom, csbi.dwSize.Y, csbi.dwSize.X);
-
         // Check whether the window is too close to the screen buffer top
         if srct_window.Bottom < csbi.dwSize.Y - count {
             srct_window.Top += count; // move top down
@@ -74,20 +75,22 @@ impl ITerminal for WinApiTerminal {
 
             let success = kernel::set_console_info(false, &mut srct_window, &self.context.screen_manager);
 
-            if success {
-                panic!("Something went wrong when scrolling down");
+            if !success {
+                return Err(io::Error::last_os_error().into());
             }
         }
+
+        Ok(())
     }
 
     /// Set the current terminal size
-    fn set_size(&self, width: i16, height: i16) {
+    fn set_size(&self, width: i16, height: i16) -> Result<()> {
         if width <= 0 {
-            panic!("Cannot set the terminal width lower than 1");
+            return Err(invalid_size("Cannot set the terminal width lower than 1"));
         }
 
         if height <= 0 {
-            panic!("Cannot set the terminal height lower then 1")
+            return Err(invalid_size("Cannot set the terminal height lower then 1"));
         }
 
         // Get the position of the current console window
@@ -104,7 +107,7 @@ impl ITerminal for WinApiTerminal {
 
         if csbi.dwSize.X < csbi.srWindow.Left + width {
             if csbi.srWindow.Left >= i16::max_value() - width {
-                panic!("Argument out of range when setting terminal width.");
+                return Err(invalid_size("Argument out of range when setting terminal width."));
             }
 
             size.X = csbi.srWindow.Left + width;
@@ -112,7 +115,7 @@ impl ITerminal for WinApiTerminal {
         }
         if csbi.dwSize.Y < csbi.srWindow.Top + height {
             if csbi.srWindow.Top >= i16::max_value() - height {
-                panic!("Argument out of range when setting terminal height");
+                return Err(invalid_size("Argument out of range when setting terminal height"));
             }
 
             size.Y = csbi.srWindow.Top + height;
@@ -123,7 +126,7 @@ impl ITerminal for WinApiTerminal {
             success = kernel::set_console_screen_buffer_size(size, &self.context.screen_manager);
 
             if !success {
-                panic!("Something went wrong when setting screen buffer size.");
+                return Err(io::Error::last_os_error().into());
             }
         }
 
@@ -143,18 +146,72 @@ impl ITerminal for WinApiTerminal {
             let bounds = kernel::get_largest_console_window_size();
 
             if width > bounds.X {
-                panic!(
-                    "Argument width: {} out of range when setting terminal width.",
-                    width
-                );
+                return Err(invalid_size("Argument width out of range when setting terminal width."));
             }
             if height > bounds.Y {
-                panic!(
-                    "Argument height: {} out of range when setting terminal height",
-                    height
-                );
+                return Err(invalid_size("Argument height out of range when setting terminal height"));
             }
         }
+
+        Ok(())
+    }
+
+    fn set_scroll_region(&self, _top: u16, _bottom: u16) -> Result<()> {
+        Err(unsupported("scroll regions are not supported by the windows console"))
+    }
+
+    fn reset_scroll_region(&self) -> Result<()> {
+        // Without scroll regions the whole screen always scrolls.
+        Ok(())
+    }
+
+    fn insert_lines(&self, _count: u16) -> Result<()> {
+        Err(unsupported("inserting lines is not supported by the windows console"))
+    }
+
+    fn delete_lines(&self, _count: u16) -> Result<()> {
+        Err(unsupported("deleting lines is not supported by the windows console"))
+    }
+
+    fn insert_chars(&self, _count: u16) -> Result<()> {
+        Err(unsupported("inserting characters is not supported by the windows console"))
+    }
+
+    fn delete_chars(&self, _count: u16) -> Result<()> {
+        Err(unsupported("deleting characters is not supported by the windows console"))
+    }
+
+    fn erase_chars(&self, count: u16) -> Result<()> {
+        let csbi = kernel::get_console_screen_buffer_info(&self.context.screen_manager);
+        let (x, y) = cursor(&self.context).pos()?;
+
+        // Like the ANSI code the erasing stops at the end of the line.
+        let cells_to_write = ::std::cmp::min(count as i16, csbi.dwSize.X - x as i16);
+
+        let start_location = COORD {
+            X: x as i16,
+            Y: y as i16,
+        };
+
+        clear(start_location, cells_to_write as u32, &self.context.screen_manager)
+    }
+
+    fn set_title(&self, title: &str) -> Result<()> {
+        // The title of the user is saved the first time the title is changed.
+        TitleStackCommand::new(&self.context)?;
+
+        let title: Vec<u16> = title.encode_utf16().collect();
+        kernel::set_console_title(&title)?;
+        Ok(())
+    }
+
+    fn set_icon_name(&self, _name: &str) -> Result<()> {
+        // The windows console has no icon name, the title is shown in the taskbar.
+        Ok(())
+    }
+
+    fn set_clipboard(&self, _selection: ClipboardSelection, _text: &str) -> Result<()> {
+        Err(unsupported("the clipboard can not be set through the windows console"))
     }
 
     fn exit(&self) {
@@ -162,11 +219,19 @@ impl ITerminal for WinApiTerminal {
     }
 }
 
+fn invalid_size(message: &str) -> Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message).into()
+}
+
+fn unsupported(message: &str) -> Error {
+    io::Error::new(io::ErrorKind::Other, message).into()
+}
+
 pub fn clear_after_cursor(
     pos: (u16, u16),
     csbi: CONSOLE_SCREEN_BUFFER_INFO,
-    context: &Rc<Context>,
-) {
+    context: &Arc<Context>,
+) -> Result<()> {
     let (mut x, mut y) = pos;
 
     // if cursor position is at the outer right position
@@ -183,14 +248,14 @@ pub fn clear_after_cursor(
     // get sum cells before cursor
     let cells_to_write = csbi.dwSize.X as u32 * csbi.dwSize.Y as u32;
 
-    clear(start_location, cells_to_write, &context.screen_manager);
+    clear(start_location, cells_to_write, &context.screen_manager)
 }
 
 pub fn clear_before_cursor(
     pos: (u16, u16),
     csbi: CONSOLE_SCREEN_BUFFER_INFO,
-    context: &Rc<Context>,
-) {
+    context: &Arc<Context>,
+) -> Result<()> {
     let (xpos, ypos) = pos;
 
     // one cell after cursor position
@@ -206,10 +271,10 @@ pub fn clear_before_cursor(
     // get sum cells before cursor
     let cells_to_write = (csbi.dwSize.X as u32 * ypos as u32) + (xpos as u32 + 1);
 
-    clear(start_location, cells_to_write, &context.screen_manager);
+    clear(start_location, cells_to_write, &context.screen_manager)
 }
 
-pub fn clear_entire_screen(csbi: CONSOLE_SCREEN_BUFFER_INFO, context: &Rc<Context>) {
+pub fn clear_entire_screen(csbi: CONSOLE_SCREEN_BUFFER_INFO, context: &Arc<Context>) -> Result<()> {
     // position x at start
     let x = 0;
     // position y at start
@@ -224,17 +289,18 @@ pub fn clear_entire_screen(csbi: CONSOLE_SCREEN_BUFFER_INFO, context: &Rc<Contex
 
     let cells_to_write = csbi.dwSize.X as u32 * csbi.dwSize.Y as u32;
 
-    clear(start_location, cells_to_write, &context.screen_manager);
+    clear(start_location, cells_to_write, &context.screen_manager)?;
 
     // put the cursor back at (0, 0)
-    cursor(&context).goto(0, 0);
+    cursor(&context).goto(0, 0)?;
+    Ok(())
 }
 
 pub fn clear_current_line(
     pos: (u16, u16),
     csbi: CONSOLE_SCREEN_BUFFER_INFO,
-    context: &Rc<Context>,
-) {
+    context: &Arc<Context>,
+) -> Result<()> {
     // position x at start
     let x = 0;
     // position y at start
@@ -249,13 +315,14 @@ pub fn clear_current_line(
 
     let cells_to_write = csbi.dwSize.X as u32;
 
-    clear(start_location, cells_to_write, &context.screen_manager);
+    clear(start_location, cells_to_write, &context.screen_manager)?;
 
     // put the cursor back at 1 cell on current row
-    cursor(&context).goto(0, y);
+    cursor(&context).goto(0, y)?;
+    Ok(())
 }
 
-pub fn clear_until_line(pos: (u16, u16), csbi: CONSOLE_SCREEN_BUFFER_INFO, context: &Rc<Context>) {
+pub fn clear_until_line(pos: (u16, u16), csbi: CONSOLE_SCREEN_BUFFER_INFO, context: &Arc<Context>) -> Result<()> {
     let (x, y) = pos;
 
     // location where to start clearing
@@ -266,13 +333,14 @@ pub fn clear_until_line(pos: (u16, u16), csbi: CONSOLE_SCREEN_BUFFER_INFO, conte
     // get sum cells before cursor
     let cells_to_write = (csbi.dwSize.X - x as i16) as u32;
 
-    clear(start_location, cells_to_write, &context.screen_manager);
+    clear(start_location, cells_to_write, &context.screen_manager)?;
 
     // put the cursor back at original cursor position
-    cursor(&context).goto(x, y);
+    cursor(&context).goto(x, y)?;
+    Ok(())
 }
 
-fn clear(start_loaction: COORD, cells_to_write: u32, screen_manager: &Rc<Mutex<ScreenManager>>) {
+fn clear(start_loaction: COORD, cells_to_write: u32, screen_manager: &Arc<Mutex<ScreenManager>>) -> Result<()> {
     let mut cells_written = 0;
     let mut success = false;
 
@@ -284,7 +352,7 @@ fn clear(start_loaction: COORD, cells_to_write: u32, screen_manager: &Rc<Mutex<S
     );
 
     if !success {
-        panic!("Could not clear screen after cursor");
+        return Err(io::Error::last_os_error().into());
     }
 
     cells_written = 0;
@@ -297,6 +365,8 @@ fn clear(start_loaction: COORD, cells_to_write: u32, screen_manager: &Rc<Mutex<S
     );
 
     if !success {
-        panic!("Couldnot reset attributes after cursor");
+        return Err(io::Error::last_os_error().into());
     }
+
+    Ok(())
 }